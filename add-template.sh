@@ -6,5 +6,8 @@ if [[ "${#}" != 1 ]]; then
 fi
 
 day="${1}"
-cp -i template.rs src/bin/day-$day.rs
-touch src/inputs/input-$day
+cp -i template.rs src/days/day_$day.rs
+printf 'fn main() {\n    advent_of_code_2021::run(file!());\n}\n' > src/bin/day-$day.rs
+touch src/inputs/2021/input-$day
+
+echo "add 'pub mod day_$day;' and a Day entry to src/days/mod.rs to register the new day"
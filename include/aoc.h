@@ -0,0 +1,46 @@
+/* generated by `aoc header`, don't edit by hand */
+#ifndef AOC_H
+#define AOC_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#define AOC_OK 0 /* ok */
+#define AOC_UNKNOWN_DAY 1 /* day isn't implemented */
+#define AOC_BAD_PART 2 /* part needs to be 1 or 2 */
+#define AOC_NULL_POINTER 3 /* null pointer */
+#define AOC_BAD_UTF8 4 /* input isn't utf-8 */
+#define AOC_PARSE_ERROR 5 /* couldn't parse the input */
+#define AOC_BUFFER_TOO_SMALL 6 /* output buffer is too small */
+#define AOC_PANIC 7 /* the solver panicked */
+#define AOC_UNKNOWN_YEAR 8 /* year has no solutions */
+
+/*
+ * solve a part of a day of 2021, writing the answer into out_buf as a nul terminated
+ * string
+ *
+ * out_len starts out as the size of out_buf, and ends up as the length of the answer
+ * (without the nul), or when the buffer's too small, the size it would need to be.
+ * on AOC_PARSE_ERROR, out_buf holds as much of the error as fits instead of an answer.
+ * the input is normalized like an input file, so crlf line endings or a byte order mark
+ * are fine
+ */
+int32_t aoc_solve(uint32_t day, uint32_t part, const uint8_t *input_ptr, size_t input_len,
+                  uint8_t *out_buf, size_t *out_len);
+
+/* aoc_solve for a day of another year's event */
+int32_t aoc_solve_year(uint32_t year, uint32_t day, uint32_t part, const uint8_t *input_ptr,
+                       size_t input_len, uint8_t *out_buf, size_t *out_len);
+
+/* static, nul terminated description of an error code */
+const char *aoc_strerror(int32_t code);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* AOC_H */
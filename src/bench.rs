@@ -0,0 +1,286 @@
+use crate::json::{self, quote, Value};
+use std::env;
+use std::fs::{read_to_string, write};
+use std::path::Path;
+use std::thread::available_parallelism;
+use std::time::Duration;
+
+/// default history file, kept out of version control since timings only mean something on the
+/// machine they were taken on
+pub const HISTORY: &str = "bench-history.json";
+
+/// |z| a rank-sum statistic needs to pass to count, i.e. 5% significance either way
+const Z_CRITICAL: f64 = 1.96;
+
+/// smallest change in the median worth flagging however significant, since a consistent 1% is
+/// usually the machine rather than the code
+const MIN_CHANGE: f64 = 0.05;
+
+/// what a benchmark ran on, results are only compared against the same machine's
+#[derive(Clone, Debug, PartialEq)]
+pub struct Machine {
+    pub host: String,
+    pub os: String,
+    pub arch: String,
+    pub cpus: usize,
+}
+
+impl Machine {
+    pub fn current() -> Self {
+        let host = env::var("HOSTNAME")
+            .ok()
+            .or_else(|| read_to_string("/etc/hostname").ok())
+            .map(|host| host.trim().to_string())
+            .filter(|host| !host.is_empty())
+            .unwrap_or_else(|| String::from("unknown"));
+
+        Self {
+            host,
+            os: env::consts::OS.to_string(),
+            arch: env::consts::ARCH.to_string(),
+            cpus: available_parallelism().map_or(1, |cpus| cpus.get()),
+        }
+    }
+}
+
+/// every timing taken of one part of a day in one run of `aoc bench`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Record {
+    /// commit benchmarked, with -dirty when there were uncommitted changes
+    pub commit: String,
+    /// name given to the run with --label, to compare against later
+    pub label: Option<String>,
+    /// seconds since the epoch
+    pub timestamp: u64,
+    pub machine: Machine,
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    pub samples: Vec<Duration>,
+}
+
+impl Record {
+    pub fn median(&self) -> Duration {
+        median(&self.samples)
+    }
+
+    /// whether the run was of the baseline, by label or commit (prefix)
+    pub fn matches(&self, baseline: &str) -> bool {
+        self.label.as_deref() == Some(baseline) || self.commit.starts_with(baseline)
+    }
+
+    fn to_json(&self) -> String {
+        let samples = self
+            .samples
+            .iter()
+            .map(|sample| sample.as_nanos().to_string())
+            .collect::<Vec<String>>();
+
+        format!(
+            "{{\"commit\":{},\"label\":{},\"timestamp\":{},\"machine\":{{\"host\":{},\"os\":{},\"arch\":{},\"cpus\":{}}},\"year\":{},\"day\":{},\"part\":{},\"samples_ns\":[{}]}}",
+            quote(&self.commit),
+            self.label.as_deref().map_or(String::from("null"), quote),
+            self.timestamp,
+            quote(&self.machine.host),
+            quote(&self.machine.os),
+            quote(&self.machine.arch),
+            self.machine.cpus,
+            self.year,
+            self.day,
+            self.part,
+            samples.join(",")
+        )
+    }
+
+    fn from_json(value: &Value) -> Result<Self, String> {
+        let field = |value: &Value, key: &str| -> Result<Value, String> {
+            value.get(key).cloned().ok_or(format!("missing {:?}", key))
+        };
+        let text = |value: &Value, key: &str| -> Result<String, String> {
+            field(value, key)?
+                .as_str()
+                .map(String::from)
+                .ok_or(format!("expected {:?} to be a string", key))
+        };
+        let number = |value: &Value, key: &str| -> Result<u64, String> {
+            field(value, key)?
+                .as_f64()
+                .filter(|number| *number >= 0.0)
+                .map(|number| number as u64)
+                .ok_or(format!("expected {:?} to be a number", key))
+        };
+
+        let machine = field(value, "machine")?;
+        let samples = field(value, "samples_ns")?
+            .as_array()
+            .ok_or("expected \"samples_ns\" to be an array")?
+            .iter()
+            .map(|sample| sample.as_f64().map(|ns| Duration::from_nanos(ns as u64)))
+            .collect::<Option<Vec<Duration>>>()
+            .ok_or("expected \"samples_ns\" to be numbers")?;
+
+        Ok(Self {
+            commit: text(value, "commit")?,
+            label: field(value, "label")?.as_str().map(String::from),
+            timestamp: number(value, "timestamp")?,
+            machine: Machine {
+                host: text(&machine, "host")?,
+                os: text(&machine, "os")?,
+                arch: text(&machine, "arch")?,
+                cpus: number(&machine, "cpus")? as usize,
+            },
+            year: number(value, "year")? as u32,
+            day: number(value, "day")? as u32,
+            part: number(value, "part")? as u32,
+            samples,
+        })
+    }
+}
+
+/// every record in the history file, none when there isn't one yet
+pub fn load(path: &Path) -> Result<Vec<Record>, String> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let context = |e: String| format!("{}: {}", path.display(), e);
+    let text = read_to_string(path).map_err(|e| context(e.to_string()))?;
+
+    json::parse(&text)
+        .map_err(context)?
+        .as_array()
+        .ok_or_else(|| context(String::from("expected an array of records")))?
+        .iter()
+        .enumerate()
+        .map(|(idx, value)| {
+            Record::from_json(value).map_err(|e| context(format!("record {}: {}", idx + 1, e)))
+        })
+        .collect()
+}
+
+/// write the history, a record per line so it diffs (and merges) a line at a time
+pub fn save(path: &Path, records: &[Record]) -> Result<(), String> {
+    let lines = records.iter().map(Record::to_json).collect::<Vec<String>>();
+    let text = match lines.is_empty() {
+        true => String::from("[]\n"),
+        false => format!("[\n{}\n]\n", lines.join(",\n")),
+    };
+
+    write(path, text).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+pub fn median(samples: &[Duration]) -> Duration {
+    let mut sorted = samples.to_vec();
+    sorted.sort();
+
+    match sorted.len() {
+        0 => Duration::ZERO,
+        n if n % 2 == 0 => (sorted[n / 2 - 1] + sorted[n / 2]) / 2,
+        n => sorted[n / 2],
+    }
+}
+
+/// how a part's timings moved from the baseline's
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Change {
+    Slower,
+    Faster,
+    Unchanged,
+}
+
+/// whether `current` is significantly slower or faster than `baseline`
+///
+/// uses a Mann-Whitney U test rather than comparing means, since timings are skewed by the odd
+/// run that got descheduled, and the normal approximation to it, which is close enough from
+/// around 5 samples a side. a significant change also has to move the median by MIN_CHANGE
+pub fn compare(baseline: &[Duration], current: &[Duration]) -> Change {
+    if baseline.is_empty() || current.is_empty() {
+        return Change::Unchanged;
+    }
+
+    // how many (baseline, current) pairs have the current sample slower, ties counting half
+    let u = current
+        .iter()
+        .flat_map(|c| baseline.iter().map(move |b| (b, c)))
+        .map(|(b, c)| match c.cmp(b) {
+            std::cmp::Ordering::Greater => 1.0,
+            std::cmp::Ordering::Equal => 0.5,
+            std::cmp::Ordering::Less => 0.0,
+        })
+        .sum::<f64>();
+
+    let (n1, n2) = (baseline.len() as f64, current.len() as f64);
+    let mean = n1 * n2 / 2.0;
+    let deviation = (n1 * n2 * (n1 + n2 + 1.0) / 12.0).sqrt();
+    let z = (u - mean) / deviation;
+
+    let before = median(baseline).as_secs_f64();
+    let change = (median(current).as_secs_f64() - before) / before.max(f64::MIN_POSITIVE);
+
+    match (z, change) {
+        (z, change) if z > Z_CRITICAL && change > MIN_CHANGE => Change::Slower,
+        (z, change) if z < -Z_CRITICAL && change < -MIN_CHANGE => Change::Faster,
+        _ => Change::Unchanged,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn micros(samples: &[u64]) -> Vec<Duration> {
+        samples
+            .iter()
+            .map(|&us| Duration::from_micros(us))
+            .collect()
+    }
+
+    #[test]
+    fn test_compare() {
+        let baseline = micros(&[100, 102, 98, 101, 99, 100, 103, 97]);
+
+        let slower = micros(&[130, 128, 135, 131, 129, 140, 127, 132]);
+        assert_eq!(Change::Slower, compare(&baseline, &slower));
+        assert_eq!(Change::Faster, compare(&slower, &baseline));
+
+        // consistently slower, but not by enough to matter
+        let noise = micros(&[101, 103, 99, 102, 100, 101, 104, 98]);
+        assert_eq!(Change::Unchanged, compare(&baseline, &noise));
+
+        // a much slower median, but from too few samples to tell
+        assert_eq!(
+            Change::Unchanged,
+            compare(&micros(&[100, 150]), &micros(&[140, 160]))
+        );
+    }
+
+    #[test]
+    fn test_history() {
+        let record = Record {
+            commit: String::from("8dd702f-dirty"),
+            label: Some(String::from("before \"astar\"")),
+            timestamp: 1_640_000_000,
+            machine: Machine {
+                host: String::from("elf"),
+                os: String::from("linux"),
+                arch: String::from("x86_64"),
+                cpus: 8,
+            },
+            year: 2021,
+            day: 15,
+            part: 2,
+            samples: micros(&[1500, 1_200_000, 7]),
+        };
+
+        let path = env::temp_dir().join(format!("aoc-bench-{}.json", std::process::id()));
+        save(&path, &[record.clone(), record.clone()]).unwrap();
+        let loaded = load(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(Ok(vec![record.clone(), record.clone()]), loaded);
+        assert!(record.matches("8dd7"));
+        assert!(record.matches("before \"astar\""));
+        assert!(!record.matches("1234"));
+        assert_eq!(Duration::from_micros(1500), record.median());
+    }
+}
@@ -0,0 +1,257 @@
+use advent_of_code_2021::bench::{self, Change, Machine, Record};
+use advent_of_code_2021::config::Config;
+use advent_of_code_2021::days::Day;
+use advent_of_code_2021::{events, read_input, Parts, Solver};
+use clap::Args;
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+#[derive(Args)]
+pub struct Options {
+    /// day to benchmark; every day with an input when not given
+    #[clap(short, long)]
+    day: Option<u32>,
+
+    /// which year the days are from
+    #[clap(long)]
+    year: Option<u32>,
+
+    /// which part of the day: 1, 2 or both
+    #[clap(short, long, default_value = "both")]
+    part: Parts,
+
+    /// whose puzzle inputs to use, read from <inputs>/<year>/<profile>/
+    #[clap(long)]
+    profile: Option<String>,
+
+    /// times to solve each part, more samples make smaller slowdowns detectable
+    #[clap(short, long, default_value = "10")]
+    runs: usize,
+
+    /// commit (or a prefix of one) or label of an earlier run to compare against, flagging parts
+    /// that got significantly slower
+    #[clap(long)]
+    compare: Option<String>,
+
+    /// name for this run, to --compare against later
+    #[clap(long)]
+    label: Option<String>,
+
+    /// file the results are added to
+    #[clap(long, parse(from_os_str), default_value = bench::HISTORY)]
+    history: PathBuf,
+}
+
+/// the commit being benchmarked, marked dirty when the tree has uncommitted changes
+fn commit() -> String {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+
+    match (
+        git(&["rev-parse", "--short", "HEAD"]),
+        git(&["status", "--porcelain"]),
+    ) {
+        (Some(commit), Some(status)) if !status.is_empty() => format!("{}-dirty", commit),
+        (Some(commit), _) => commit,
+        (None, _) => String::from("unknown"),
+    }
+}
+
+/// time `runs` solves of the part, after one to warm up that also checks it solves at all
+fn measure(day: &Day, solver: Solver, input: &str, runs: usize) -> Result<Vec<Duration>, String> {
+    let solution = day.solution(solver);
+    solution(input).map_err(|e| format!("day {}: {}", day.number, e))?;
+
+    Ok((0..runs.max(1))
+        .map(|_| {
+            let start = Instant::now();
+            solution(input).ok();
+            start.elapsed()
+        })
+        .collect())
+}
+
+/// the baseline's record of the same part as `record`
+fn matching<'a>(baseline: &'a [Record], record: &Record) -> Option<&'a Record> {
+    baseline
+        .iter()
+        .find(|old| (old.day, old.part) == (record.day, record.part))
+}
+
+/// median timings of this run, against the baseline's when comparing
+fn render(records: &[Record], baseline: Option<&[Record]>) -> String {
+    let timing = |duration: Duration| format!("{:.1?}", duration);
+
+    let header = match baseline {
+        None => format!("{:>12}  {:>12}", "median", "fastest"),
+        Some(_) => format!("{:>12}  {:>12}  {:>8}", "baseline", "current", "delta"),
+    };
+    let mut result = format!("day  part  {}\n", header);
+
+    for record in records {
+        let row = match baseline.map(|baseline| matching(baseline, record)) {
+            None => {
+                let fastest = record.samples.iter().min().copied().unwrap_or_default();
+                format!("{:>12}  {:>12}", timing(record.median()), timing(fastest))
+            }
+            Some(Some(old)) => {
+                let before = old.median().as_secs_f64();
+                let after = record.median().as_secs_f64();
+                let delta = (after - before) / before.max(f64::MIN_POSITIVE) * 100.0;
+                let flag = match bench::compare(&old.samples, &record.samples) {
+                    Change::Slower => "  slower",
+                    Change::Faster => "  faster",
+                    Change::Unchanged => "",
+                };
+                format!(
+                    "{:>12}  {:>12}  {:>+7.1}%{}",
+                    timing(old.median()),
+                    timing(record.median()),
+                    delta,
+                    flag
+                )
+            }
+            Some(None) => format!("{:>12}  {:>12}  {:>8}", "-", timing(record.median()), "new"),
+        };
+        result.push_str(&format!("{:>3}  {:>4}  {}\n", record.day, record.part, row));
+    }
+
+    result
+}
+
+pub fn run(options: Options, config: Config) -> Result<(), String> {
+    let config = config.with_flags(options.year, options.profile);
+
+    let solvers = options.part.solvers();
+
+    let days = match options.day {
+        Some(number) => vec![events::day(config.year.value, number)?],
+        None => events::event(config.year.value)?
+            .days
+            .iter()
+            .filter(|day| config.input_path(day.number).exists())
+            .collect(),
+    };
+
+    let mut history = bench::load(&options.history)?;
+    let machine = Machine::current();
+
+    // the latest run of the baseline on this machine for each part, timings taken elsewhere
+    // don't say anything about this one
+    let baseline = match &options.compare {
+        Some(name) => {
+            let mut baseline = Vec::new();
+            for record in history.iter().rev() {
+                if matching(&baseline, record).is_none()
+                    && record.matches(name)
+                    && record.machine == machine
+                    && record.year == config.year.value
+                {
+                    baseline.push(record.clone());
+                }
+            }
+            if baseline.is_empty() {
+                return Err(format!(
+                    "no runs of {} on this machine in {}",
+                    name,
+                    options.history.display()
+                ));
+            }
+            Some(baseline)
+        }
+        None => None,
+    };
+
+    let commit = commit();
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs());
+
+    let mut records = Vec::new();
+    for day in days {
+        let input = read_input(&config.input_path(day.number), config.strict.value)?;
+
+        for solver in &solvers {
+            eprintln!("[+] day {} part {}", day.number, solver.number());
+            records.push(Record {
+                commit: commit.clone(),
+                label: options.label.clone(),
+                timestamp,
+                machine: machine.clone(),
+                year: config.year.value,
+                day: day.number,
+                part: solver.number(),
+                samples: measure(day, *solver, &input, options.runs)?,
+            });
+        }
+    }
+
+    history.extend(records.iter().cloned());
+    bench::save(&options.history, &history)?;
+
+    print!("{}", render(&records, baseline.as_deref()));
+
+    if let (Some(name), Some(baseline)) = (options.compare, baseline) {
+        let slower = records
+            .iter()
+            .filter_map(|record| Some((matching(&baseline, record)?, record)))
+            .filter(|(old, record)| bench::compare(&old.samples, &record.samples) == Change::Slower)
+            .count();
+        if slower > 0 {
+            return Err(format!("{} part(s) got slower since {}", slower, name));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(day: u32, part: u32, micros: &[u64]) -> Record {
+        Record {
+            commit: String::from("8dd702f"),
+            label: None,
+            timestamp: 0,
+            machine: Machine::current(),
+            year: 2021,
+            day,
+            part,
+            samples: micros.iter().map(|&us| Duration::from_micros(us)).collect(),
+        }
+    }
+
+    #[test]
+    fn test_render() {
+        let records = [
+            record(1, 1, &[100, 101, 99, 100, 102, 98, 100, 101]),
+            record(15, 2, &[130, 131, 129, 130, 132, 128, 130, 131]),
+            record(16, 1, &[5, 5, 5]),
+        ];
+        let baseline = [
+            record(1, 1, &[100, 101, 99, 100, 102, 98, 100, 101]),
+            record(15, 2, &[100, 101, 99, 100, 102, 98, 100, 101]),
+        ];
+
+        assert_eq!(
+            "day  part      baseline       current     delta\n  \
+               1     1       100.0µs       100.0µs     +0.0%\n \
+              15     2       100.0µs       130.0µs    +30.0%  slower\n \
+              16     1             -         5.0µs       new\n",
+            render(&records, Some(&baseline))
+        );
+        assert_eq!(
+            "day  part        median       fastest\n \
+              16     1         5.0µs         5.0µs\n",
+            render(&records[2..], None)
+        );
+    }
+}
@@ -0,0 +1,129 @@
+use advent_of_code_2021::config::Config;
+use advent_of_code_2021::days::Day;
+use advent_of_code_2021::events;
+use advent_of_code_2021::normalize::{normalize, problems, raw_position};
+use clap::Args;
+use std::fs::read_to_string;
+use std::path::PathBuf;
+
+#[derive(Args)]
+pub struct Options {
+    /// day to check; every day with an input when not given
+    #[clap(short, long)]
+    day: Option<u32>,
+
+    /// which year the day is from
+    #[clap(long)]
+    year: Option<u32>,
+
+    /// input to check; defaults to the day's puzzle input
+    #[clap(short, long, parse(from_os_str))]
+    input: Option<PathBuf>,
+
+    /// whose puzzle input to use by default, read from <inputs>/<year>/<profile>/
+    #[clap(long)]
+    profile: Option<String>,
+
+    /// also report what would otherwise be normalized away: crlf, a byte order mark, etc
+    #[clap(long)]
+    strict: bool,
+}
+
+/// every problem with the input at `path`, one `path: line N, column M: message` per line
+fn check(day: &Day, path: &PathBuf, strict: bool) -> Result<Vec<String>, String> {
+    let input = read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+
+    let mut errors = (day.validate)(&normalize(&input));
+    if strict {
+        // strict means reporting against the file as it is, rather than as it's solved
+        errors = errors
+            .into_iter()
+            .map(|error| raw_position(&input, error))
+            .chain(problems(&input))
+            .collect();
+    }
+    errors.sort_by_key(|error| (error.line, error.column));
+
+    Ok(errors
+        .iter()
+        .map(|error| format!("{}: {}", path.display(), error))
+        .collect())
+}
+
+pub fn run(options: Options, config: Config) -> Result<(), String> {
+    let mut config = config.with_flags(options.year, options.profile);
+    config.strict.flag(options.strict.then_some(true));
+
+    let targets = match options.day {
+        Some(number) => {
+            let day = events::day(config.year.value, number)?;
+            let path = options.input.unwrap_or_else(|| config.input_path(number));
+            vec![(day, path)]
+        }
+        None if options.input.is_some() => {
+            return Err(String::from("--input needs a --day to check it against"))
+        }
+        None => events::event(config.year.value)?
+            .days
+            .iter()
+            .map(|day| (day, config.input_path(day.number)))
+            .filter(|(_, path)| path.exists())
+            .collect(),
+    };
+
+    let mut problems = 0;
+
+    for (day, path) in targets {
+        let diagnostics = check(day, &path, config.strict.value)?;
+
+        if diagnostics.is_empty() {
+            println!("day {:>2}: {} ok", day.number, path.display());
+        }
+        for diagnostic in &diagnostics {
+            println!("{}", diagnostic);
+        }
+
+        problems += diagnostics.len();
+    }
+
+    if problems > 0 {
+        return Err(format!("found {} problem(s)", problems));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::{remove_file, write};
+
+    #[test]
+    fn test_check() {
+        let path = std::env::temp_dir().join(format!("aoc-check-{}", std::process::id()));
+        write(&path, "\u{feff}x99 \r\n2x0\n").unwrap();
+        let day = events::day(2021, 1).unwrap();
+        let (checked, strict) = (check(day, &path, false), check(day, &path, true));
+        remove_file(&path).unwrap();
+
+        let at = |diagnostic: &str| format!("{}: {}", path.display(), diagnostic);
+        assert_eq!(
+            Ok(vec![
+                at("line 1, column 1: couldn't parse \"x99\": invalid digit found in string"),
+                at("line 2, column 1: couldn't parse \"2x0\": invalid digit found in string"),
+            ]),
+            checked
+        );
+        // the same problems, where they are in the file itself
+        assert_eq!(
+            Ok(vec![
+                at("line 1, column 1: byte order mark"),
+                at("line 1, column 2: couldn't parse \"x99\": invalid digit found in string"),
+                at("line 1, column 5: trailing whitespace"),
+                at("line 1, column 6: windows line ending"),
+                at("line 2, column 1: couldn't parse \"2x0\": invalid digit found in string"),
+            ]),
+            strict
+        );
+    }
+}
@@ -0,0 +1,170 @@
+use advent_of_code_2021::config::Config;
+use advent_of_code_2021::days::Day;
+use advent_of_code_2021::{events, read_input, Answer, ParseError, Parts, Solver};
+use clap::Args;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+#[derive(Args)]
+pub struct Options {
+    /// day to run
+    #[clap(short, long)]
+    day: u32,
+
+    /// which year the day is from
+    #[clap(long)]
+    year: Option<u32>,
+
+    /// which part of the day: 1, 2 or both
+    #[clap(short, long, default_value = "both")]
+    part: Parts,
+
+    /// input to run on; defaults to the day's puzzle input
+    #[clap(short, long, parse(from_os_str))]
+    input: Option<PathBuf>,
+
+    /// whose puzzle input to use by default, read from <inputs>/<year>/<profile>/
+    #[clap(long)]
+    profile: Option<String>,
+
+    /// times to run each implementation, the fastest run is the one reported
+    #[clap(short, long, default_value = "3")]
+    runs: usize,
+}
+
+/// how a single implementation did
+struct Run {
+    name: &'static str,
+    answer: Result<Answer, ParseError>,
+    elapsed: Duration,
+}
+
+fn measure(day: &Day, solver: Solver, input: &str, runs: usize) -> Vec<Run> {
+    day.implementations(solver)
+        .iter()
+        .map(|implementation| {
+            let mut fastest = Duration::MAX;
+            let mut answer = (implementation.solve)(input);
+
+            for _ in 0..runs.max(1) {
+                let start = Instant::now();
+                answer = (implementation.solve)(input);
+                fastest = fastest.min(start.elapsed());
+            }
+
+            Run {
+                name: implementation.name,
+                answer,
+                elapsed: fastest,
+            }
+        })
+        .collect()
+}
+
+/// whether every implementation came up with the same answer
+fn agree(runs: &[Run]) -> bool {
+    runs.iter().all(|run| run.answer == runs[0].answer)
+}
+
+fn render(number: u32, solver: Solver, runs: &[Run]) -> String {
+    let part = solver.number();
+    let mut result = format!("day {} part {}\n", number, part);
+
+    let fastest = runs.iter().map(|run| run.elapsed).min().unwrap_or_default();
+    let width = runs
+        .iter()
+        .map(|run| run.name.len())
+        .max()
+        .unwrap_or_default();
+
+    for run in runs {
+        let relative = run.elapsed.as_secs_f64() / fastest.as_secs_f64().max(f64::MIN_POSITIVE);
+        let answer = match &run.answer {
+            Ok(answer) => answer.to_string(),
+            Err(e) => format!("error: {}", e),
+        };
+
+        result.push_str(&format!(
+            "  {:<width$}  {:>12}  {:>7.1}x  {}\n",
+            run.name,
+            format!("{:?}", run.elapsed),
+            relative,
+            answer,
+            width = width
+        ));
+    }
+
+    match (runs.len(), agree(runs)) {
+        (1, _) => result.push_str("  only the default implementation, nothing to compare\n"),
+        (n, true) => result.push_str(&format!("  all {} implementations agree\n", n)),
+        (_, false) => result.push_str("  implementations disagree!\n"),
+    }
+
+    result
+}
+
+pub fn run(options: Options, config: Config) -> Result<(), String> {
+    let config = config.with_flags(options.year, options.profile);
+    let day = events::day(config.year.value, options.day)?;
+
+    let solvers = options.part.solvers();
+
+    let path = options
+        .input
+        .unwrap_or_else(|| config.input_path(day.number));
+    let input = read_input(&path, config.strict.value)?;
+
+    let mut disagreements = 0;
+
+    for solver in solvers {
+        let runs = measure(day, solver, &input, options.runs);
+        print!("{}", render(day.number, solver, &runs));
+
+        if !agree(&runs) {
+            disagreements += 1;
+        }
+    }
+
+    if disagreements > 0 {
+        return Err(format!(
+            "implementations disagree on {} part(s) of day {}",
+            disagreements, day.number
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(name: &'static str, answer: i64, millis: u64) -> Run {
+        Run {
+            name,
+            answer: Ok(Answer::Number(answer)),
+            elapsed: Duration::from_millis(millis),
+        }
+    }
+
+    #[test]
+    fn test_render() {
+        let rendered = render(
+            15,
+            Solver::PartOne,
+            &[run("default", 40, 30), run("a-star", 40, 10)],
+        );
+
+        assert!(rendered.starts_with("day 15 part 1\n"));
+        assert!(rendered.contains("  default          30ms      3.0x  40\n"));
+        assert!(rendered.contains("  a-star           10ms      1.0x  40\n"));
+        assert!(rendered.ends_with("all 2 implementations agree\n"));
+
+        let rendered = render(
+            15,
+            Solver::PartOne,
+            &[run("default", 41, 30), run("a-star", 40, 10)],
+        );
+        assert!(rendered.ends_with("implementations disagree!\n"));
+    }
+}
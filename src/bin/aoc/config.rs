@@ -0,0 +1,22 @@
+use advent_of_code_2021::config::Config;
+use clap::{Args, Subcommand};
+
+#[derive(Args)]
+pub struct Options {
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// print every setting's effective value and where it came from
+    Show,
+}
+
+pub fn run(options: Options, config: Config) -> Result<(), String> {
+    match options.command {
+        Command::Show => print!("{}", config.show()),
+    }
+
+    Ok(())
+}
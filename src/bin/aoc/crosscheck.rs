@@ -0,0 +1,298 @@
+use advent_of_code_2021::config::Config;
+use advent_of_code_2021::days::Day;
+use advent_of_code_2021::ledger::Ledger;
+use advent_of_code_2021::{events, input_path, read_input, Solver};
+use clap::Args;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::read_dir;
+use std::path::{Path, PathBuf};
+
+#[derive(Args)]
+pub struct Options {
+    /// directory of answers to check against, as <profile>/day-N.answers files of `part: answer`
+    /// lines, solving each profile's input from <inputs>/<year>/<profile>/
+    #[clap(short, long, parse(from_os_str))]
+    reference: PathBuf,
+
+    /// which year the answers are for
+    #[clap(long)]
+    year: Option<u32>,
+}
+
+const PARTS: [Solver; 2] = [Solver::PartOne, Solver::PartTwo];
+
+/// every profile's reference answers by day, sorted so the report comes out the same every time
+type Reference = BTreeMap<String, BTreeMap<u32, Ledger>>;
+
+/// the day a reference file has answers for, i.e. 7 for day-7.answers
+fn day_number(file: &Path) -> Option<u32> {
+    file.file_name()?
+        .to_str()?
+        .strip_prefix("day-")?
+        .strip_suffix(".answers")?
+        .parse()
+        .ok()
+}
+
+fn entries(dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let context = |e: std::io::Error| format!("{}: {}", dir.display(), e);
+    read_dir(dir)
+        .map_err(context)?
+        .map(|entry| entry.map(|entry| entry.path()).map_err(context))
+        .collect()
+}
+
+/// every profile directory's answers files; anything else in the directory is ignored
+fn gather(dir: &Path) -> Result<Reference, String> {
+    let mut reference = Reference::new();
+
+    for profile in entries(dir)? {
+        let name = match profile.file_name().and_then(|name| name.to_str()) {
+            Some(name) if profile.is_dir() => name.to_string(),
+            _ => continue,
+        };
+
+        let mut days = BTreeMap::new();
+        for file in entries(&profile)? {
+            if let Some(number) = day_number(&file) {
+                days.insert(number, Ledger::read(&file)?);
+            }
+        }
+        reference.insert(name, days);
+    }
+
+    Ok(reference)
+}
+
+/// how one part of a profile's day compares with the reference
+#[derive(Clone, Debug, PartialEq)]
+enum Verdict {
+    Agree,
+    Disagree { expected: String, actual: String },
+    Failed(String),
+    NoInput,
+    NoReference,
+    NotImplemented,
+}
+
+impl Verdict {
+    fn mark(&self) -> char {
+        match self {
+            Verdict::Agree => '=',
+            Verdict::Disagree { .. } => 'x',
+            Verdict::Failed(_) => '!',
+            Verdict::NoInput => '?',
+            Verdict::NoReference => '.',
+            Verdict::NotImplemented => '-',
+        }
+    }
+
+    /// why the part doesn't agree, none when there's nothing wrong
+    fn problem(&self) -> Option<String> {
+        match self {
+            Verdict::Disagree { expected, actual } => {
+                Some(format!("reference {}, got {}", expected, actual))
+            }
+            Verdict::Failed(e) => Some(e.clone()),
+            _ => None,
+        }
+    }
+}
+
+/// solve each part the reference has an answer for, comparing the two; an input that couldn't be
+/// read fails those parts rather than the whole crosscheck
+fn check(day: Option<&Day>, input: Option<Result<&str, &str>>, ledger: &Ledger) -> [Verdict; 2] {
+    PARTS.map(|solver| {
+        let expected = match ledger.get(solver) {
+            Some(expected) => expected,
+            None => return Verdict::NoReference,
+        };
+        let (day, input) = match (day, input) {
+            (None, _) => return Verdict::NotImplemented,
+            (_, None) => return Verdict::NoInput,
+            (_, Some(Err(e))) => return Verdict::Failed(e.to_string()),
+            (Some(day), Some(Ok(input))) => (day, input),
+        };
+
+        match day.solution(solver)(input) {
+            Ok(answer) if answer.to_string() == expected => Verdict::Agree,
+            Ok(answer) => Verdict::Disagree {
+                expected: expected.to_string(),
+                actual: answer.to_string(),
+            },
+            Err(e) => Verdict::Failed(e.to_string()),
+        }
+    })
+}
+
+/// verdicts for each profile's days, in the reference's order
+type Matrix = Vec<(String, BTreeMap<u32, [Verdict; 2]>)>;
+
+fn crosscheck(reference: &Reference, config: &Config) -> Matrix {
+    let year = config.year.value;
+
+    reference
+        .iter()
+        .map(|(profile, days)| {
+            let verdicts = days
+                .iter()
+                .map(|(number, ledger)| {
+                    let path = input_path(&config.inputs.value, year, *number, Some(profile));
+                    let input = path
+                        .exists()
+                        .then(|| read_input(&path, config.strict.value));
+                    let input = input
+                        .as_ref()
+                        .map(|input| input.as_deref().map_err(String::as_str));
+                    let day = events::day(year, *number).ok();
+                    (*number, check(day, input, ledger))
+                })
+                .collect();
+            (profile.clone(), verdicts)
+        })
+        .collect()
+}
+
+/// a row per profile and a column per day, a mark per part, then what went wrong
+fn render(matrix: &Matrix) -> String {
+    let days = matrix
+        .iter()
+        .flat_map(|(_, verdicts)| verdicts.keys().copied())
+        .collect::<BTreeSet<u32>>();
+    let width = matrix
+        .iter()
+        .map(|(profile, _)| profile.len())
+        .chain(["profile".len()])
+        .max()
+        .unwrap_or_default();
+
+    let mut result = format!("{:<width$}", "profile", width = width);
+    for number in &days {
+        result.push_str(&format!("  {:>3}", format!("d{}", number)));
+    }
+    result.push('\n');
+
+    for (profile, verdicts) in matrix {
+        result.push_str(&format!("{:<width$}", profile, width = width));
+        for number in &days {
+            let cell = match verdicts.get(number) {
+                Some(verdicts) => verdicts.iter().map(Verdict::mark).collect::<String>(),
+                None => String::from("  "),
+            };
+            result.push_str(&format!("  {:>3}", cell));
+        }
+        result.push('\n');
+    }
+
+    result.push_str(
+        "\n= agrees   x disagrees   ! failed   ? no input   . no reference answer   - not implemented\n",
+    );
+
+    let problems = matrix
+        .iter()
+        .flat_map(|(profile, verdicts)| {
+            verdicts.iter().flat_map(move |(number, parts)| {
+                parts.iter().enumerate().filter_map(move |(idx, verdict)| {
+                    let problem = verdict.problem()?;
+                    Some(format!(
+                        "{} day {} part {}: {}\n",
+                        profile,
+                        number,
+                        idx + 1,
+                        problem
+                    ))
+                })
+            })
+        })
+        .collect::<String>();
+    if !problems.is_empty() {
+        result.push('\n');
+        result.push_str(&problems);
+    }
+
+    result
+}
+
+pub fn run(options: Options, config: Config) -> Result<(), String> {
+    let config = config.with_flags(options.year, None);
+    events::event(config.year.value)?;
+
+    let reference = gather(&options.reference)?;
+    if reference.is_empty() {
+        return Err(format!(
+            "no <profile>/day-N.answers files in {}",
+            options.reference.display()
+        ));
+    }
+
+    let matrix = crosscheck(&reference, &config);
+    print!("{}", render(&matrix));
+
+    let problems = matrix
+        .iter()
+        .flat_map(|(_, verdicts)| verdicts.values().flatten())
+        .filter(|verdict| verdict.problem().is_some())
+        .count();
+    match problems {
+        0 => Ok(()),
+        problems => Err(format!(
+            "{} part(s) don't agree with {}",
+            problems,
+            options.reference.display()
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::{create_dir_all, remove_dir_all, write};
+
+    #[test]
+    fn test_crosscheck() {
+        let dir = std::env::temp_dir().join(format!("aoc-crosscheck-{}", std::process::id()));
+        let (reference, inputs) = (dir.join("reference"), dir.join("inputs"));
+
+        for (file, contents) in [
+            ("reference/alice/day-6.answers", "1: 5934\n2: 26984457539\n"),
+            ("reference/alice/day-7.answers", "1: 37\n2: 170\n"),
+            ("reference/alice/notes.txt", "not answers"),
+            ("reference/bob/day-6.answers", "1: 5934\n"),
+            ("reference/bob/day-16.answers", "1: 31\n"),
+            ("reference/bob/day-7.answers", "2: 168\n"),
+            ("inputs/2021/alice/input-6", "3,4,3,1,2"),
+            ("inputs/2021/alice/input-7", "16,1,2,0,4,2,7,1,2,14"),
+            ("inputs/2021/bob/input-16", "8A004A801A8002F478"),
+            ("inputs/2021/bob/input-7/unreadable", ""),
+        ] {
+            let path = dir.join(file);
+            create_dir_all(path.parent().unwrap()).unwrap();
+            write(path, contents).unwrap();
+        }
+
+        let mut config = Config::default();
+        config.inputs.value = inputs;
+        let matrix = gather(&reference).map(|reference| crosscheck(&reference, &config));
+        remove_dir_all(&dir).unwrap();
+
+        assert_eq!(
+            format!(
+                "profile   d6   d7  d16\n\
+                 alice     ==   =x     \n\
+                 bob       ?.   .!   -.\n\
+                 \n= agrees   x disagrees   ! failed   ? no input   . no reference answer   - not implemented\n\
+                 \nalice day 7 part 2: reference 170, got 168\n\
+                 bob day 7 part 2: {}: Is a directory (os error 21)\n",
+                dir.join("inputs/2021/bob/input-7").display()
+            ),
+            render(&matrix.unwrap())
+        );
+    }
+
+    #[test]
+    fn test_day_number() {
+        assert_eq!(Some(7), day_number(Path::new("alice/day-7.answers")));
+        assert_eq!(None, day_number(Path::new("alice/day-7.txt")));
+        assert_eq!(None, day_number(Path::new("alice/input-7")));
+    }
+}
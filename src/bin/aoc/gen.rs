@@ -0,0 +1,58 @@
+use advent_of_code_2021::config::Config;
+use advent_of_code_2021::days::day_12;
+use advent_of_code_2021::events;
+use advent_of_code_2021::rng::Rng;
+use clap::Args;
+use std::fs::write;
+use std::io::{stdout, Write};
+use std::path::PathBuf;
+
+#[derive(Args)]
+pub struct Options {
+    /// day to generate an input for
+    #[clap(short, long)]
+    day: u32,
+
+    /// which year the day is from
+    #[clap(long)]
+    year: Option<u32>,
+
+    /// how big to make the input; lines, grid width, boards, etc depending on the day
+    #[clap(short, long, default_value = "100")]
+    size: usize,
+
+    /// seed for the generator, the same seed and size always give the same input
+    #[clap(long, default_value = "2021")]
+    seed: u64,
+
+    /// percentage of day 12's caves that are big
+    #[clap(long)]
+    big_percent: Option<usize>,
+
+    /// where to write the input; stdout when not given
+    #[clap(short, long, parse(from_os_str))]
+    out: Option<PathBuf>,
+}
+
+pub fn run(options: Options, config: Config) -> Result<(), String> {
+    let config = config.with_flags(options.year, None);
+    let day = events::day(config.year.value, options.day)?;
+
+    let mut rng = Rng::new(options.seed);
+    // exactly as generated, since some days' parsers don't take a trailing newline
+    let input = match options.big_percent {
+        Some(percent) if day.number == 12 => {
+            day_12::generate_caves(&mut rng, options.size, percent)
+        }
+        Some(_) => return Err(String::from("--big-percent only applies to day 12")),
+        None => (day.generate)(&mut rng, options.size),
+    };
+
+    match options.out {
+        Some(path) => write(&path, input).map_err(|e| format!("{}: {}", path.display(), e)),
+        None => stdout()
+            .lock()
+            .write_all(input.as_bytes())
+            .map_err(|e| e.to_string()),
+    }
+}
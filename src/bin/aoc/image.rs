@@ -0,0 +1,92 @@
+use advent_of_code_2021::config::Config;
+use advent_of_code_2021::image::{save, ColorMap, Image};
+use advent_of_code_2021::{events, read_input, Solver};
+use clap::Args;
+use std::path::PathBuf;
+
+#[derive(Args)]
+pub struct Options {
+    /// day to draw
+    #[clap(short, long)]
+    day: u32,
+
+    /// which year the day is from
+    #[clap(long)]
+    year: Option<u32>,
+
+    /// which part of the day, 1 or 2
+    #[clap(short, long)]
+    part: Solver,
+
+    /// input to draw; defaults to the day's puzzle input
+    #[clap(short, long, parse(from_os_str))]
+    input: Option<PathBuf>,
+
+    /// whose puzzle input to use by default, read from <inputs>/<year>/<profile>/
+    #[clap(long)]
+    profile: Option<String>,
+
+    /// where to write the image; .pgm, .ppm or .gif
+    #[clap(short, long, parse(from_os_str))]
+    out: PathBuf,
+
+    /// draw the frames of the day's visualization as an animation instead, see --visualize
+    #[clap(short, long)]
+    animate: bool,
+
+    /// pixels per cell, each way
+    #[clap(short, long, default_value = "4")]
+    scale: usize,
+
+    /// hundredths of a second to show each frame of an animation for
+    #[clap(long, default_value = "10")]
+    delay: u16,
+}
+
+pub fn run(options: Options, config: Config) -> Result<(), String> {
+    let config = config.with_flags(options.year, options.profile);
+    let day = events::day(config.year.value, options.day)?;
+
+    let solver = options.part;
+
+    let path = options
+        .input
+        .unwrap_or_else(|| config.input_path(day.number));
+    let input = read_input(&path, config.strict.value)?;
+    let parse_error = |e| format!("couldn't parse {}: {}", path.display(), e);
+
+    let images = if options.animate {
+        let visualize = day
+            .visualize
+            .ok_or(format!("day {} doesn't have a visualization", day.number))?;
+
+        visualize(&input, solver)
+            .map_err(parse_error)?
+            .iter()
+            .map(|frame| Image::render(frame, ColorMap::Text))
+            .collect()
+    } else {
+        let picture = day.picture.ok_or(format!(
+            "day {} doesn't have a picture, try --animate",
+            day.number
+        ))?;
+
+        vec![picture(&input, solver).map_err(parse_error)?]
+    };
+
+    let images = images
+        .iter()
+        .map(|image| image.scaled(options.scale))
+        .collect::<Vec<Image>>();
+    save(&options.out, &images, options.delay)?;
+
+    println!(
+        "{} image(s) of {}x{} written to {}",
+        images.len(),
+        images[0].width,
+        images[0].height,
+        options.out.display()
+    );
+
+    Ok(())
+}
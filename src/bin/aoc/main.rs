@@ -0,0 +1,90 @@
+use advent_of_code_2021::config::Config;
+use advent_of_code_2021::ffi;
+use clap::{Parser, Subcommand};
+use std::process::exit;
+
+mod bench;
+mod check;
+mod compare;
+mod config;
+mod crosscheck;
+mod gen;
+mod image;
+mod minimize;
+mod repl;
+mod report;
+mod run;
+mod serve;
+mod status;
+mod watch;
+
+/// runner for every registered day
+#[derive(Parser)]
+struct Cli {
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// time every day's parts, adding them to a history that later runs are compared against
+    Bench(bench::Options),
+    /// show a calendar of solved days, stars and missing work
+    Status(status::Options),
+    /// solve a day, or every input in a directory with --inputs-dir, tabulating the answers
+    Run(run::Options),
+    /// check inputs for structural problems, reporting every one with its line and column
+    Check(check::Options),
+    /// run every implementation of a day on the same input, checking they agree and timing them
+    Compare(compare::Options),
+    /// generate a random but valid input for a day, as big as needed for load testing
+    Gen(gen::Options),
+    /// solve every profile's inputs, checking the answers against a directory of reference answers
+    Crosscheck(crosscheck::Options),
+    /// show the settings from aoc.toml and AOC_* variables that commands start from
+    Config(config::Options),
+    /// print the C header for the solvers' C ABI, kept in include/aoc.h
+    Header,
+    /// draw a day as an image, or its visualization as an animated gif
+    Image(image::Options),
+    /// write a static html site of every day's answers, timings, examples and pictures
+    Report(report::Options),
+    /// step through a day's simulation interactively, inspecting it along the way
+    Repl(repl::Options),
+    /// answer POST /day/{n}/part/{p} requests over http, with the input as the body
+    Serve(serve::Options),
+    /// shrink an input that gets the wrong answer down to a minimal reproducer
+    Minimize(minimize::Options),
+    /// rebuild and rerun a day's examples and input whenever its source or input changes
+    Watch(watch::Options),
+}
+
+fn main() {
+    let cli = Cli::parse();
+
+    let result = Config::load().and_then(|config| match cli.command {
+        Command::Bench(options) => bench::run(options, config),
+        Command::Status(options) => status::run(options, config),
+        Command::Run(options) => run::run(options, config),
+        Command::Check(options) => check::run(options, config),
+        Command::Compare(options) => compare::run(options, config),
+        Command::Config(options) => config::run(options, config),
+        Command::Crosscheck(options) => crosscheck::run(options, config),
+        Command::Gen(options) => gen::run(options, config),
+        Command::Header => {
+            print!("{}", ffi::header());
+            Ok(())
+        }
+        Command::Image(options) => image::run(options, config),
+        Command::Minimize(options) => minimize::run(options, config),
+        Command::Repl(options) => repl::run(options, config),
+        Command::Report(options) => report::run(options, config),
+        Command::Serve(options) => serve::run(options, config),
+        Command::Watch(options) => watch::run(options, config),
+    });
+
+    if let Err(e) = result {
+        eprintln!("[!] {}", e);
+        exit(1);
+    }
+}
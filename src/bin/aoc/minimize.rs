@@ -0,0 +1,425 @@
+use advent_of_code_2021::config::Config;
+use advent_of_code_2021::days::{Day, Solution, DEFAULT_IMPL};
+use advent_of_code_2021::ledger::Ledger;
+use advent_of_code_2021::minimize::minimize;
+use advent_of_code_2021::{events, read_input, Solver};
+use clap::Args;
+use std::cell::{Cell, RefCell};
+use std::fs::write;
+use std::io::Write;
+use std::panic::{catch_unwind, set_hook, take_hook, AssertUnwindSafe};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::sync::Once;
+
+#[derive(Args)]
+pub struct Options {
+    /// day to run
+    #[clap(short, long)]
+    day: u32,
+
+    /// which year the day is from
+    #[clap(long)]
+    year: Option<u32>,
+
+    /// which part of the day, 1 or 2
+    #[clap(short, long)]
+    part: Solver,
+
+    /// which implementation of the part fails, see `aoc compare`
+    #[clap(long = "impl", default_value = DEFAULT_IMPL)]
+    implementation: String,
+
+    /// implementation of the part trusted to get smaller inputs right; defaults to the part's first
+    /// other implementation
+    #[clap(long, conflicts_with = "reference-cmd")]
+    reference: Option<String>,
+
+    /// command trusted to get smaller inputs right instead, given one on stdin and printing its
+    /// answer, i.e. someone else's solution
+    #[clap(long)]
+    reference_cmd: Option<String>,
+
+    /// correct answer for the input; defaults to the one in the day's ledger, or the reference's
+    #[clap(short, long)]
+    expect: Option<String>,
+
+    /// input to minimize; defaults to the day's puzzle input
+    #[clap(short, long, parse(from_os_str))]
+    input: Option<PathBuf>,
+
+    /// whose puzzle input to use by default, read from <inputs>/<year>/<profile>/
+    #[clap(long)]
+    profile: Option<String>,
+
+    /// where to write the reproducer; defaults to the input's path with .min tacked on
+    #[clap(short, long, parse(from_os_str))]
+    out: Option<PathBuf>,
+}
+
+/// what happened when the solver was given a candidate input
+#[derive(Debug, PartialEq)]
+enum Outcome {
+    /// the day couldn't parse it, so it says nothing about the failure
+    Rejected,
+    Answer(String),
+    /// the input parses, but the solver returned an error with this message
+    Failed(String),
+    /// where in the code the solver panicked
+    Panicked(String),
+}
+
+/// what the right answer to a smaller input is judged by, since it's rarely the whole input's
+enum Oracle {
+    /// another of the day's implementations
+    Implementation(Solution),
+    /// a shell command given the input on stdin, printing the answer
+    Command(String),
+}
+
+impl Oracle {
+    /// the right answer to the input, none when the oracle can't give one
+    fn answer(&self, input: &str) -> Option<String> {
+        match self {
+            Oracle::Implementation(solution) => match quietly(|| solution(input)) {
+                Ok(Ok(answer)) => Some(answer.to_string()),
+                _ => None,
+            },
+            Oracle::Command(command) => {
+                let mut child = Command::new("sh")
+                    .args(["-c", command])
+                    .stdin(Stdio::piped())
+                    .stdout(Stdio::piped())
+                    .stderr(Stdio::null())
+                    .spawn()
+                    .ok()?;
+                // a command that stops reading early still gets to answer
+                let _ = child.stdin.take()?.write_all(input.as_bytes());
+                let output = child.wait_with_output().ok()?;
+
+                let answer = String::from_utf8_lossy(&output.stdout).trim().to_string();
+                (output.status.success() && !answer.is_empty()).then_some(answer)
+            }
+        }
+    }
+}
+
+thread_local! {
+    /// whether this thread's panics are being caught by `quietly`
+    static CATCHING: Cell<bool> = const { Cell::new(false) };
+    /// where this thread last panicked while they were
+    static PANICKED_AT: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+static HOOK: Once = Once::new();
+
+/// run `solve`, catching a panic along with where it happened instead of printing it; the hook
+/// that does so is installed once, and hands panics on other threads, or outside of here, to the
+/// hook that was there before
+fn quietly<T>(solve: impl FnOnce() -> T) -> Result<T, String> {
+    HOOK.call_once(|| {
+        let previous = take_hook();
+        set_hook(Box::new(move |info| match CATCHING.with(Cell::get) {
+            true => PANICKED_AT
+                .with(|at| *at.borrow_mut() = info.location().map(|location| location.to_string())),
+            false => previous(info),
+        }));
+    });
+
+    CATCHING.with(|catching| catching.set(true));
+    let result = catch_unwind(AssertUnwindSafe(solve));
+    CATCHING.with(|catching| catching.set(false));
+
+    result.map_err(|_| PANICKED_AT.with(|at| at.borrow_mut().take().unwrap_or_default()))
+}
+
+fn outcome(day: &Day, solution: Solution, input: &str) -> Outcome {
+    match quietly(|| solution(input)) {
+        Ok(Ok(answer)) => Outcome::Answer(answer.to_string()),
+        Ok(Err(_)) if (day.parse)(input).is_err() => Outcome::Rejected,
+        Ok(Err(e)) => Outcome::Failed(e.to_string()),
+        Err(location) => Outcome::Panicked(location),
+    }
+}
+
+/// whether a candidate fails the way the original input did: an answer the oracle says is wrong
+/// (not necessarily the same wrong answer, since a smaller input has a different right one), the
+/// same error, or a panic in the same place (its message can mention lengths that shrink along
+/// with the input)
+fn same_failure(
+    original: &Outcome,
+    candidate: &Outcome,
+    right: impl FnOnce() -> Option<String>,
+) -> bool {
+    match (original, candidate) {
+        (Outcome::Rejected, _) | (_, Outcome::Rejected) => false,
+        (Outcome::Answer(_), Outcome::Answer(answer)) => {
+            right().is_some_and(|right| right != *answer)
+        }
+        (original, candidate) => original == candidate,
+    }
+}
+
+/// the smallest part of the input that still fails the way the whole of it does, along with how
+/// it fails
+///
+/// a wrong answer needs the oracle to tell whether smaller inputs are still wrong; `expected`,
+/// when it's known, is the whole input's right answer, which the oracle has to agree with
+fn reduce(
+    day: &Day,
+    solution: Solution,
+    input: &str,
+    expected: Option<&str>,
+    oracle: Option<&Oracle>,
+) -> Result<(String, Outcome), String> {
+    let original = outcome(day, solution, input);
+
+    // only a wrong answer needs the oracle, and only if it agrees the answer's wrong
+    let oracle = match &original {
+        Outcome::Rejected => {
+            return Err(String::from(
+                "doesn't parse, so there's no failure to minimize",
+            ))
+        }
+        Outcome::Answer(answer) => {
+            let oracle = oracle.ok_or(
+                "gets an answer, and there's no reference to say whether smaller inputs' are \
+                 wrong (see --reference and --reference-cmd)",
+            )?;
+            let right = oracle
+                .answer(input)
+                .ok_or("the reference doesn't get an answer for it")?;
+            if let Some(expected) = expected.filter(|expected| *expected != right) {
+                return Err(format!(
+                    "the reference gets {} rather than the expected {}, so it can't be trusted \
+                     with smaller inputs",
+                    right, expected
+                ));
+            }
+            if *answer == right {
+                return Err(format!(
+                    "gets the right answer ({}), nothing to minimize",
+                    answer
+                ));
+            }
+            Some(oracle)
+        }
+        Outcome::Failed(_) | Outcome::Panicked(_) => None,
+    };
+
+    let minimal = minimize(input, |candidate| {
+        // an empty input doesn't point at any line, however it fails
+        !candidate.trim().is_empty()
+            && same_failure(&original, &outcome(day, solution, candidate), || {
+                oracle?.answer(candidate)
+            })
+    });
+
+    Ok((minimal, original))
+}
+
+/// the oracle the options ask for, or by default the part's first implementation other than the
+/// one being minimized; none when there isn't one
+fn oracle(options: &Options, day: &Day) -> Result<Option<Oracle>, String> {
+    let solver = options.part;
+    let names = || {
+        day.implementations(solver)
+            .iter()
+            .map(|implementation| implementation.name)
+            .collect::<Vec<&str>>()
+            .join(" or ")
+    };
+
+    Ok(match (&options.reference, &options.reference_cmd) {
+        (_, Some(command)) => Some(Oracle::Command(command.clone())),
+        (Some(name), None) => Some(Oracle::Implementation(
+            day.implementation(solver, name).ok_or_else(|| {
+                format!(
+                    "got unexpected value for --reference: {} (try {})",
+                    name,
+                    names()
+                )
+            })?,
+        )),
+        (None, None) => day
+            .implementations(solver)
+            .into_iter()
+            .find(|implementation| implementation.name != options.implementation)
+            .map(|implementation| Oracle::Implementation(implementation.solve)),
+    })
+}
+
+pub fn run(options: Options, config: Config) -> Result<(), String> {
+    let config = config.with_flags(options.year, options.profile.clone());
+    let day = events::day(config.year.value, options.day)?;
+
+    let solver = options.part;
+    let solution = day
+        .implementation(solver, &options.implementation)
+        .ok_or_else(|| {
+            format!(
+                "got unexpected value for --impl: {}",
+                options.implementation
+            )
+        })?;
+    let oracle = oracle(&options, day)?;
+
+    let expected = match &options.expect {
+        Some(expected) => Some(expected.clone()),
+        None => Ledger::load(config.year.value, day.number)?
+            .get(solver)
+            .map(String::from),
+    };
+
+    let path = options
+        .input
+        .clone()
+        .unwrap_or_else(|| config.input_path(day.number));
+    let input = read_input(&path, config.strict.value)?;
+
+    let (minimal, failure) = reduce(day, solution, &input, expected.as_deref(), oracle.as_ref())
+        .map_err(|e| format!("{} {}", path.display(), e))?;
+
+    let out = options.out.unwrap_or_else(|| {
+        let mut out = path.clone().into_os_string();
+        out.push(".min");
+        PathBuf::from(out)
+    });
+    write(&out, &minimal).map_err(|e| format!("{}: {}", out.display(), e))?;
+
+    let right = match (&failure, &oracle) {
+        (Outcome::Answer(_), Some(oracle)) => oracle
+            .answer(&minimal)
+            .map(|right| format!(" (the reference gets {})", right))
+            .unwrap_or_default(),
+        _ => String::new(),
+    };
+    println!(
+        "{} -> {} lines, still fails with {:?}{}; written to {}",
+        input.lines().count(),
+        minimal.lines().count(),
+        outcome(day, solution, &minimal),
+        right,
+        out.display()
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use advent_of_code_2021::error::ParseError;
+    use advent_of_code_2021::rng::Rng;
+    use advent_of_code_2021::Answer;
+
+    /// day 8 part two, but reading every 0 on a display as a 2
+    fn misreads_zero(input: &str) -> Result<Answer, ParseError> {
+        let day = events::day(2021, 8).unwrap();
+
+        let mut sum = 0;
+        for line in input.lines() {
+            let value = format!("{:0>4}", (day.part_two)(line)?);
+            sum += value.replace('0', "2").parse::<i64>().unwrap();
+        }
+        Ok(Answer::Number(sum))
+    }
+
+    #[test]
+    fn test_same_failure() {
+        let wrong = Outcome::Answer(String::from("2"));
+        let right = |answer: &str| {
+            let answer = answer.to_string();
+            move || Some(answer)
+        };
+
+        assert!(same_failure(
+            &wrong,
+            &Outcome::Answer(String::from("3")),
+            right("4")
+        ));
+        assert!(!same_failure(
+            &wrong,
+            &Outcome::Answer(String::from("3")),
+            right("3")
+        ));
+        assert!(!same_failure(
+            &wrong,
+            &Outcome::Answer(String::from("3")),
+            || None
+        ));
+        assert!(!same_failure(&wrong, &Outcome::Rejected, right("4")));
+
+        let panicked = Outcome::Panicked(String::from("src/days/day_10.rs:147:5"));
+        assert!(same_failure(&panicked, &panicked, || None));
+        assert!(!same_failure(
+            &panicked,
+            &Outcome::Panicked(String::from("src/days/day_10.rs:90:13")),
+            || None
+        ));
+        assert!(!same_failure(
+            &Outcome::Rejected,
+            &Outcome::Rejected,
+            || None
+        ));
+    }
+
+    #[test]
+    fn test_quietly() {
+        assert_eq!(Ok(3), quietly(|| 3));
+
+        let location = quietly(|| panic!("caught")).unwrap_err();
+        assert!(
+            location.starts_with("src/bin/aoc/minimize.rs:"),
+            "{}",
+            location
+        );
+    }
+
+    #[test]
+    fn test_oracle() {
+        let day = events::day(2021, 1).unwrap();
+
+        let oracle = Oracle::Implementation(day.part_one);
+        assert_eq!(Some(String::from("1")), oracle.answer("1\n2"));
+        assert_eq!(None, oracle.answer("x"));
+
+        let oracle = Oracle::Command(String::from("wc -l"));
+        assert_eq!(Some(String::from("2")), oracle.answer("1\n2\n"));
+        assert_eq!(None, Oracle::Command(String::from("false")).answer("1"));
+    }
+
+    #[test]
+    fn test_reduce() {
+        let mut rng = Rng::new(2021);
+
+        // every display that shows a 0 is enough to get the sum wrong on its own
+        let day = events::day(2021, 8).unwrap();
+        let reference = Oracle::Implementation(day.part_two);
+        let input = (day.generate)(&mut rng, 40);
+        assert_ne!(misreads_zero(&input), (day.part_two)(&input));
+
+        let (minimal, failure) =
+            reduce(day, misreads_zero, &input, None, Some(&reference)).unwrap();
+        assert_eq!(1, minimal.lines().count(), "{}", minimal);
+        assert_eq!(
+            Outcome::Answer(misreads_zero(&input).unwrap().to_string()),
+            failure
+        );
+        assert_ne!(misreads_zero(&minimal), (day.part_two)(&minimal));
+
+        let right = (day.part_two)(&input).unwrap().to_string();
+        assert!(reduce(day, misreads_zero, &input, Some(&right), Some(&reference)).is_ok());
+        assert!(reduce(day, misreads_zero, &input, Some("1"), Some(&reference)).is_err());
+        assert!(reduce(day, misreads_zero, &input, None, None).is_err());
+        assert!(reduce(day, day.part_two, &input, None, Some(&reference)).is_err());
+
+        // an error needs no oracle, just to stay the same error
+        let day = events::day(2021, 10).unwrap();
+        let input = "(]\n[)\n{>";
+        let (minimal, failure) = reduce(day, day.part_two, input, None, None).unwrap();
+        assert_eq!(1, minimal.lines().count(), "{}", minimal);
+        assert!(matches!(failure, Outcome::Failed(_)));
+        assert_eq!(failure, outcome(day, day.part_two, &minimal));
+    }
+}
@@ -0,0 +1,435 @@
+use advent_of_code_2021::config::Config;
+use advent_of_code_2021::days::Simulator;
+use advent_of_code_2021::simulation::Simulation;
+use advent_of_code_2021::{events, read_input};
+use clap::Args;
+use std::io::{stdin, stdout, BufRead, IsTerminal, Write};
+use std::path::PathBuf;
+
+/// how many steps run-until takes before giving up on the condition ever holding
+const MAX_STEPS: usize = 1_000_000;
+
+const HELP: &str = "commands:
+  step [k]             take k steps, 1 by default
+  show                 print the whole current state
+  run-until <cond>     step until a condition holds, i.e. `flashes > 100`, `dots == 16` or `synced`
+  reset                start over from the input
+  inspect x,y          show what's at a position
+  measures             print the step count and the day's measures, which conditions can use
+  help                 print this
+  quit                 stop";
+
+#[derive(Args)]
+pub struct Options {
+    /// day to step through; 4, 6, 11 and 13 have simulations
+    #[clap(short, long)]
+    day: u32,
+
+    /// which year the day is from
+    #[clap(long)]
+    year: Option<u32>,
+
+    /// input to simulate; defaults to the day's puzzle input
+    #[clap(short, long, parse(from_os_str))]
+    input: Option<PathBuf>,
+
+    /// whose puzzle input to use by default, read from <inputs>/<year>/<profile>/
+    #[clap(long)]
+    profile: Option<String>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Comparison {
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+}
+
+/// operators run-until understands, longest first so `<=` isn't taken for `<`
+const COMPARISONS: &[(&str, Comparison)] = &[
+    ("==", Comparison::Equal),
+    ("!=", Comparison::NotEqual),
+    ("<=", Comparison::LessOrEqual),
+    (">=", Comparison::GreaterOrEqual),
+    ("<", Comparison::Less),
+    (">", Comparison::Greater),
+];
+
+#[derive(Clone, Debug, PartialEq)]
+enum Operand {
+    Measure(String),
+    Value(i64),
+}
+
+/// a comparison between measures and/or numbers, i.e. `winners == boards` or `fish >= 1000`
+#[derive(Clone, Debug, PartialEq)]
+struct Condition {
+    left: Operand,
+    comparison: Comparison,
+    right: Operand,
+}
+
+impl Condition {
+    /// a comparison, or a lone measure, which holds when it isn't 0
+    fn parse(text: &str) -> Result<Self, String> {
+        let operand = |text: &str| {
+            let text = text.trim();
+            if text.is_empty() || text.contains(char::is_whitespace) {
+                return Err(format!("expected a measure or number, got {:?}", text));
+            }
+            Ok(match text.parse() {
+                Ok(value) => Operand::Value(value),
+                Err(_) => Operand::Measure(text.to_string()),
+            })
+        };
+
+        for &(symbol, comparison) in COMPARISONS {
+            if let Some((left, right)) = text.split_once(symbol) {
+                return Ok(Self {
+                    left: operand(left)?,
+                    comparison,
+                    right: operand(right)?,
+                });
+            }
+        }
+
+        Ok(Self {
+            left: operand(text)?,
+            comparison: Comparison::NotEqual,
+            right: Operand::Value(0),
+        })
+    }
+
+    fn holds(&self, measures: &[(&str, i64)]) -> Result<bool, String> {
+        let value = |operand: &Operand| match operand {
+            Operand::Value(value) => Ok(*value),
+            Operand::Measure(name) => measures
+                .iter()
+                .find(|(measure, _)| measure == name)
+                .map(|(_, value)| *value)
+                .ok_or_else(|| {
+                    let names = measures
+                        .iter()
+                        .map(|(measure, _)| *measure)
+                        .collect::<Vec<&str>>();
+                    format!("no measure called {:?}, try {}", name, names.join(", "))
+                }),
+        };
+
+        let (left, right) = (value(&self.left)?, value(&self.right)?);
+
+        Ok(match self.comparison {
+            Comparison::Equal => left == right,
+            Comparison::NotEqual => left != right,
+            Comparison::Less => left < right,
+            Comparison::LessOrEqual => left <= right,
+            Comparison::Greater => left > right,
+            Comparison::GreaterOrEqual => left >= right,
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Command {
+    Step(usize),
+    Show,
+    RunUntil(Condition),
+    Reset,
+    Inspect(usize, usize),
+    Measures,
+    Help,
+    Quit,
+}
+
+impl Command {
+    /// a line typed at the prompt; None for a blank one
+    fn parse(line: &str) -> Result<Option<Self>, String> {
+        let line = line.trim();
+        let (name, rest) = line.split_once(' ').unwrap_or((line, ""));
+        let rest = rest.trim();
+
+        let command = match name {
+            "" => return Ok(None),
+            "step" | "s" => match rest {
+                "" => Command::Step(1),
+                count => Command::Step(
+                    count
+                        .parse()
+                        .map_err(|_| format!("expected a number of steps, got {:?}", count))?,
+                ),
+            },
+            "show" => Command::Show,
+            "run-until" => Command::RunUntil(Condition::parse(rest)?),
+            "reset" => Command::Reset,
+            "inspect" => {
+                // y is optional for days where a position is just one number
+                let (x, y) = rest.split_once(',').unwrap_or((rest, "0"));
+                match (x.trim().parse(), y.trim().parse()) {
+                    (Ok(x), Ok(y)) => Command::Inspect(x, y),
+                    _ => return Err(format!("expected a position like 3,4, got {:?}", rest)),
+                }
+            }
+            "measures" => Command::Measures,
+            "help" | "?" => Command::Help,
+            "quit" | "exit" | "q" => Command::Quit,
+            _ => return Err(format!("unknown command {:?}, try help", name)),
+        };
+
+        Ok(Some(command))
+    }
+}
+
+/// a day's simulation being stepped through, along with what it takes to start it over
+struct Session<'a> {
+    simulator: Simulator,
+    input: &'a str,
+    simulation: Box<dyn Simulation + 'a>,
+    steps: usize,
+    /// whether the simulation has said there's nothing left to do
+    finished: bool,
+}
+
+impl<'a> Session<'a> {
+    fn new(simulator: Simulator, input: &'a str) -> Result<Self, String> {
+        Ok(Self {
+            simulator,
+            input,
+            simulation: simulator(input).map_err(|e| e.to_string())?,
+            steps: 0,
+            finished: false,
+        })
+    }
+
+    /// the day's measures, after the step count
+    fn measures(&self) -> Vec<(&'static str, i64)> {
+        let mut measures = vec![("steps", self.steps as i64)];
+        measures.extend(self.simulation.measures());
+        measures
+    }
+
+    /// one line summing up where things are
+    fn status(&self) -> String {
+        let mut status = self
+            .measures()
+            .iter()
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect::<Vec<String>>()
+            .join(" ");
+
+        if self.finished {
+            status.push_str(" (finished)");
+        }
+
+        status
+    }
+
+    fn step(&mut self) -> bool {
+        if self.finished {
+            return false;
+        }
+
+        if self.simulation.step() {
+            self.steps += 1;
+        } else {
+            self.finished = true;
+        }
+
+        !self.finished
+    }
+
+    /// carry out a command, returning what to print
+    fn execute(&mut self, command: &Command) -> Result<String, String> {
+        match command {
+            Command::Step(count) => {
+                for _ in 0..*count {
+                    if !self.step() {
+                        break;
+                    }
+                }
+                Ok(self.status())
+            }
+            Command::Show => Ok(self.simulation.show().trim_end().to_string()),
+            Command::RunUntil(condition) => {
+                let start = self.steps;
+
+                while !condition.holds(&self.measures())? {
+                    if self.steps - start == MAX_STEPS {
+                        return Err(format!(
+                            "gave up after {} steps, {}",
+                            MAX_STEPS,
+                            self.status()
+                        ));
+                    }
+                    if !self.step() {
+                        return Err(format!(
+                            "finished without the condition holding, {}",
+                            self.status()
+                        ));
+                    }
+                }
+
+                Ok(self.status())
+            }
+            Command::Reset => {
+                *self = Self::new(self.simulator, self.input)?;
+                Ok(self.status())
+            }
+            Command::Inspect(x, y) => self
+                .simulation
+                .inspect(*x, *y)
+                .ok_or(format!("nothing at {},{}", x, y)),
+            Command::Measures => Ok(self.status()),
+            Command::Help => Ok(HELP.to_string()),
+            Command::Quit => Ok(String::new()),
+        }
+    }
+}
+
+pub fn run(options: Options, config: Config) -> Result<(), String> {
+    let config = config.with_flags(options.year, options.profile);
+    let day = events::day(config.year.value, options.day)?;
+    let simulator = day.simulate.ok_or(format!(
+        "day {} doesn't have a simulation to step through",
+        day.number
+    ))?;
+
+    let path = options
+        .input
+        .unwrap_or_else(|| config.input_path(day.number));
+    let input = read_input(&path, config.strict.value)?;
+
+    let mut session = Session::new(simulator, &input)
+        .map_err(|e| format!("couldn't parse {}: {}", path.display(), e))?;
+
+    // prompts are just noise when commands are piped in
+    let interactive = stdin().is_terminal();
+    if interactive {
+        println!("day {}, {}; try help", day.number, session.status());
+    }
+
+    let mut lines = stdin().lock().lines();
+    loop {
+        if interactive {
+            print!("day {}> ", day.number);
+            stdout().flush().map_err(|e| e.to_string())?;
+        }
+
+        let line = match lines.next() {
+            Some(line) => line.map_err(|e| e.to_string())?,
+            None => return Ok(()),
+        };
+
+        let command = match Command::parse(&line) {
+            Ok(Some(command)) => command,
+            Ok(None) => continue,
+            Err(e) => {
+                println!("[!] {}", e);
+                continue;
+            }
+        };
+        if command == Command::Quit {
+            return Ok(());
+        }
+
+        match session.execute(&command) {
+            Ok(output) => println!("{}", output),
+            Err(e) => println!("[!] {}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use advent_of_code_2021::days::day_11::EXAMPLE;
+
+    fn execute(session: &mut Session, line: &str) -> Result<String, String> {
+        session.execute(&Command::parse(line)?.unwrap())
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Ok(None), Command::parse("  "));
+        assert_eq!(Ok(Some(Command::Step(1))), Command::parse("step"));
+        assert_eq!(Ok(Some(Command::Step(10))), Command::parse("step 10"));
+        assert_eq!(
+            Ok(Some(Command::Inspect(3, 4))),
+            Command::parse("inspect 3, 4")
+        );
+        assert_eq!(
+            Ok(Some(Command::Inspect(5, 0))),
+            Command::parse("inspect 5")
+        );
+        assert!(Command::parse("step ten").is_err());
+        assert!(Command::parse("inspect x,y").is_err());
+        assert!(Command::parse("jump").is_err());
+
+        assert_eq!(
+            Ok(Some(Command::RunUntil(Condition {
+                left: Operand::Measure(String::from("flashes")),
+                comparison: Comparison::GreaterOrEqual,
+                right: Operand::Value(100),
+            }))),
+            Command::parse("run-until flashes>=100")
+        );
+        assert_eq!(
+            Ok(Condition {
+                left: Operand::Measure(String::from("synced")),
+                comparison: Comparison::NotEqual,
+                right: Operand::Value(0),
+            }),
+            Condition::parse("synced")
+        );
+        assert!(Condition::parse("flashes >").is_err());
+        assert!(Condition::parse("two words").is_err());
+    }
+
+    #[test]
+    fn test_session() {
+        let simulator = events::day(2021, 11).unwrap().simulate.unwrap();
+        let mut session = Session::new(simulator, EXAMPLE).unwrap();
+
+        assert_eq!(
+            Ok(String::from("steps=10 flashes=204 synced=0")),
+            execute(&mut session, "step 10")
+        );
+        let status = execute(&mut session, "run-until synced").unwrap();
+        assert!(status.starts_with("steps=195 "), "{}", status);
+        assert!(status.ends_with(" synced=1"), "{}", status);
+        assert!(execute(&mut session, "show")
+            .unwrap()
+            .lines()
+            .all(|line| line == "0000000000"));
+        assert_eq!(
+            Ok(String::from("energy 0")),
+            execute(&mut session, "inspect 9,9")
+        );
+        assert!(execute(&mut session, "inspect 10,0").is_err());
+        assert!(execute(&mut session, "run-until nothing").is_err());
+
+        assert_eq!(
+            Ok(String::from("steps=0 flashes=0 synced=0")),
+            execute(&mut session, "reset")
+        );
+        assert_eq!(
+            Ok(String::from("steps=100 flashes=1656 synced=0")),
+            execute(&mut session, "run-until steps == 100")
+        );
+    }
+
+    #[test]
+    fn test_finished() {
+        let simulator = events::day(2021, 13).unwrap().simulate.unwrap();
+        let input = advent_of_code_2021::days::day_13::EXAMPLE;
+        let mut session = Session::new(simulator, input).unwrap();
+
+        assert_eq!(
+            Ok(String::from("steps=2 folds=2 dots=16 (finished)")),
+            execute(&mut session, "step 5")
+        );
+        assert!(execute(&mut session, "run-until dots < 10").is_err());
+    }
+}
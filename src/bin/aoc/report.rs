@@ -0,0 +1,364 @@
+use advent_of_code_2021::config::Config;
+use advent_of_code_2021::days::Day;
+use advent_of_code_2021::image::save;
+use advent_of_code_2021::ledger::Ledger;
+use advent_of_code_2021::{events, read_input, Answer, ParseError, Solver};
+use clap::Args;
+use std::fs::{create_dir_all, write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+#[derive(Args)]
+pub struct Options {
+    /// directory to write the site to, created if needed
+    #[clap(short, long, parse(from_os_str))]
+    out: PathBuf,
+
+    /// which year to report on
+    #[clap(long)]
+    year: Option<u32>,
+
+    /// whose puzzle inputs to solve, read from <inputs>/<year>/<profile>/
+    #[clap(long)]
+    profile: Option<String>,
+
+    /// pixels per cell of the pictures
+    #[clap(long, default_value = "1")]
+    scale: usize,
+}
+
+const PARTS: [Solver; 2] = [Solver::PartOne, Solver::PartTwo];
+
+const STYLE: &str = "body { font-family: sans-serif; max-width: 60em; margin: 2em auto; }
+table { border-collapse: collapse; }
+td, th { border: 1px solid #ccc; padding: 0.3em 0.6em; text-align: left; }
+td code { white-space: pre; }
+pre { background: #0f0f23; color: #cccccc; padding: 1em; overflow-x: auto; }
+img { image-rendering: pixelated; max-width: 100%; }
+.calendar td { text-align: center; width: 4em; }
+.pass { color: #2a2; }
+.fail { color: #c22; }
+.none { color: #999; }
+";
+
+/// text made safe to put in html
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn page(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n\
+         <link rel=\"stylesheet\" href=\"style.css\">\n</head>\n<body>\n{}</body>\n</html>\n",
+        escape(title),
+        body
+    )
+}
+
+/// a part solved on the day's input
+struct Solved {
+    answer: Result<Answer, ParseError>,
+    elapsed: Duration,
+}
+
+/// everything on a day's page
+struct DayReport {
+    number: u32,
+    ledger: Ledger,
+    /// the answers on the day's input, none when there isn't one
+    solved: Option<Vec<Solved>>,
+    /// part, input line count and expected and actual answers of each example
+    examples: Vec<(Solver, usize, &'static str, Result<Answer, ParseError>)>,
+    /// file name and caption of each picture written next to the page
+    pictures: Vec<(String, String)>,
+    /// caption and text of the last frame of each part's visualization
+    frames: Vec<(String, String)>,
+}
+
+impl DayReport {
+    fn gather(day: &Day, input: Option<&str>, ledger: Ledger) -> Self {
+        let solved = input.map(|input| {
+            PARTS
+                .iter()
+                .map(|solver| {
+                    let start = Instant::now();
+                    let answer = day.solution(*solver)(input);
+                    Solved {
+                        answer,
+                        elapsed: start.elapsed(),
+                    }
+                })
+                .collect()
+        });
+
+        let examples = day
+            .examples
+            .iter()
+            .flat_map(|example| {
+                PARTS.iter().filter_map(move |solver| {
+                    let expected = example.expected(*solver)?;
+                    let lines = example.input.lines().count();
+                    Some((
+                        *solver,
+                        lines,
+                        expected,
+                        day.solution(*solver)(example.input),
+                    ))
+                })
+            })
+            .collect();
+
+        let frames = match (day.visualize, input) {
+            (Some(visualize), Some(input)) => PARTS
+                .iter()
+                .filter_map(|solver| {
+                    let frame = visualize(input, *solver).ok()?.pop()?;
+                    Some((
+                        format!("part {}: {}", solver.number(), frame.caption),
+                        frame.picture,
+                    ))
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+
+        Self {
+            number: day.number,
+            ledger,
+            solved,
+            examples,
+            pictures: Vec::new(),
+            frames,
+        }
+    }
+
+    /// whether the answer is the one the site accepted
+    fn verdict(&self, solver: Solver, answer: &Answer) -> &'static str {
+        match self.ledger.get(solver) {
+            Some(accepted) if accepted == answer.to_string() => {
+                "<span class=\"pass\">accepted</span>"
+            }
+            Some(_) => "<span class=\"fail\">not the accepted answer</span>",
+            None => "<span class=\"none\">unverified</span>",
+        }
+    }
+
+    fn render(&self) -> String {
+        let mut body = format!(
+            "<p><a href=\"index.html\">calendar</a></p>\n<h1>day {}</h1>\n",
+            self.number
+        );
+
+        body.push_str("<h2>answers</h2>\n");
+        match &self.solved {
+            Some(solved) => {
+                body.push_str(
+                    "<table>\n<tr><th>part</th><th>answer</th><th>time</th><th></th></tr>\n",
+                );
+                for (solver, solved) in PARTS.iter().zip(solved) {
+                    let (answer, verdict) = match &solved.answer {
+                        Ok(answer) => (escape(&answer.to_string()), self.verdict(*solver, answer)),
+                        Err(e) => (
+                            format!("<span class=\"fail\">{}</span>", escape(&e.to_string())),
+                            "",
+                        ),
+                    };
+                    body.push_str(&format!(
+                        "<tr><td>{}</td><td><code>{}</code></td><td>{:.1?}</td><td>{}</td></tr>\n",
+                        solver.number(),
+                        answer,
+                        solved.elapsed,
+                        verdict
+                    ));
+                }
+                body.push_str("</table>\n");
+            }
+            None => body.push_str("<p class=\"none\">no input</p>\n"),
+        }
+
+        body.push_str("<h2>examples</h2>\n");
+        if self.examples.is_empty() {
+            body.push_str("<p class=\"none\">no examples</p>\n");
+        } else {
+            body.push_str(
+                "<table>\n<tr><th>part</th><th>lines</th><th>expected</th><th>got</th></tr>\n",
+            );
+            for (solver, lines, expected, answer) in &self.examples {
+                let got = match answer {
+                    Ok(answer) => {
+                        let answer = answer.to_string();
+                        let class = if answer == *expected { "pass" } else { "fail" };
+                        format!("<span class=\"{}\">{}</span>", class, escape(&answer))
+                    }
+                    Err(e) => format!("<span class=\"fail\">{}</span>", escape(&e.to_string())),
+                };
+                body.push_str(&format!(
+                    "<tr><td>{}</td><td>{}</td><td><code>{}</code></td><td><code>{}</code></td></tr>\n",
+                    solver.number(),
+                    lines,
+                    escape(expected),
+                    got
+                ));
+            }
+            body.push_str("</table>\n");
+        }
+
+        if !self.pictures.is_empty() || !self.frames.is_empty() {
+            body.push_str("<h2>visualization</h2>\n");
+        }
+        for (file, caption) in &self.pictures {
+            body.push_str(&format!(
+                "<figure><img src=\"{}\" alt=\"{}\"><figcaption>{}</figcaption></figure>\n",
+                escape(file),
+                escape(caption),
+                escape(caption)
+            ));
+        }
+        for (caption, picture) in &self.frames {
+            body.push_str(&format!(
+                "<p>{}</p>\n<pre>{}</pre>\n",
+                escape(caption),
+                escape(picture)
+            ));
+        }
+
+        page(&format!("day {}", self.number), &body)
+    }
+}
+
+/// the calendar, each implemented day linking to its page
+fn index(year: u32, implemented: &[u32], ledgers: &[Ledger]) -> String {
+    let stars = ledgers.iter().map(Ledger::stars).sum::<usize>();
+    let mut body = format!(
+        "<h1>Advent of Code {}</h1>\n<p>{}/50 stars</p>\n<table class=\"calendar\">\n",
+        year, stars
+    );
+
+    for week in (1..=25).collect::<Vec<u32>>().chunks(5) {
+        body.push_str("<tr>");
+        for &number in week {
+            let earned = match ledgers[number as usize - 1].stars() {
+                2 => "**",
+                1 => "*",
+                _ => "",
+            };
+            let cell = match implemented.contains(&number) {
+                true => format!("<a href=\"day-{}.html\">{}</a> {}", number, number, earned),
+                false => format!("<span class=\"none\">{}</span>", number),
+            };
+            body.push_str(&format!("<td>{}</td>", cell));
+        }
+        body.push_str("</tr>\n");
+    }
+
+    body.push_str("</table>\n");
+    page(&format!("Advent of Code {}", year), &body)
+}
+
+fn write_file(path: &Path, contents: impl AsRef<[u8]>) -> Result<(), String> {
+    write(path, contents).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+pub fn run(options: Options, config: Config) -> Result<(), String> {
+    let config = config.with_flags(options.year, options.profile);
+    let event = events::event(config.year.value)?;
+
+    create_dir_all(&options.out).map_err(|e| format!("{}: {}", options.out.display(), e))?;
+    write_file(&options.out.join("style.css"), STYLE)?;
+
+    let ledgers = (1..=25)
+        .map(|number| Ledger::load(config.year.value, number))
+        .collect::<Result<Vec<Ledger>, String>>()?;
+
+    for day in event.days {
+        eprintln!("[+] day {}", day.number);
+
+        let path = config.input_path(day.number);
+        let input = match path.exists() {
+            true => Some(read_input(&path, config.strict.value)?),
+            false => None,
+        };
+
+        let ledger = ledgers[day.number as usize - 1].clone();
+        let mut report = DayReport::gather(day, input.as_deref(), ledger);
+
+        if let (Some(picture), Some(input)) = (day.picture, &input) {
+            for solver in PARTS {
+                if let Ok(image) = picture(input, solver) {
+                    let file = format!("day-{}-part-{}.gif", day.number, solver.number());
+                    save(&options.out.join(&file), &[image.scaled(options.scale)], 0)?;
+                    report
+                        .pictures
+                        .push((file, format!("part {}", solver.number())));
+                }
+            }
+        }
+
+        write_file(
+            &options.out.join(format!("day-{}.html", day.number)),
+            report.render(),
+        )?;
+    }
+
+    let implemented = event
+        .days
+        .iter()
+        .map(|day| day.number)
+        .collect::<Vec<u32>>();
+    write_file(
+        &options.out.join("index.html"),
+        index(config.year.value, &implemented, &ledgers),
+    )?;
+
+    println!(
+        "report of {} day(s) written to {}",
+        implemented.len(),
+        options.out.join("index.html").display()
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let day = events::day(2021, 6).unwrap();
+        let ledger = Ledger {
+            part_one: Some(String::from("5934")),
+            part_two: Some(String::from("1")),
+        };
+        let rendered = DayReport::gather(day, Some("3,4,3,1,2"), ledger).render();
+
+        assert!(rendered.contains("<h1>day 6</h1>"));
+        assert!(rendered.contains("<code>5934</code></td><td>"));
+        assert!(rendered.contains("<span class=\"pass\">accepted</span>"));
+        assert!(rendered.contains("<span class=\"fail\">not the accepted answer</span>"));
+        assert!(
+            rendered.contains("<td><code>26984457539</code></td><td><code><span class=\"pass\">")
+        );
+
+        let rendered = DayReport::gather(day, None, Ledger::default()).render();
+        assert!(rendered.contains("<p class=\"none\">no input</p>"));
+
+        assert_eq!("a &lt;b&gt; &amp; &quot;c&quot;", escape("a <b> & \"c\""));
+    }
+
+    #[test]
+    fn test_index() {
+        let mut ledgers = vec![Ledger::default(); 25];
+        ledgers[0].part_one = Some(String::from("7"));
+        ledgers[0].part_two = Some(String::from("5"));
+        let rendered = index(2021, &[1, 2], &ledgers);
+
+        assert!(rendered.contains("<p>2/50 stars</p>"));
+        assert!(rendered.contains("<td><a href=\"day-1.html\">1</a> **</td>"));
+        assert!(rendered.contains("<td><a href=\"day-2.html\">2</a> </td>"));
+        assert!(rendered.contains("<td><span class=\"none\">3</span></td>"));
+    }
+}
@@ -0,0 +1,339 @@
+use advent_of_code_2021::config::{Config, Format};
+use advent_of_code_2021::days::{self, Day, Streaming};
+use advent_of_code_2021::json::quote;
+use advent_of_code_2021::{events, read_input, Parts, Solver};
+use clap::Args;
+use std::fs::{read_dir, File};
+use std::io::{stdin, BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+#[derive(Args)]
+pub struct Options {
+    /// day to run
+    #[clap(short, long)]
+    day: u32,
+
+    /// which year the day is from
+    #[clap(long)]
+    year: Option<u32>,
+
+    /// which part of the day: 1, 2 or both
+    #[clap(short, long, default_value = "both")]
+    part: Parts,
+
+    /// which implementation of the part to run, see `aoc compare`
+    #[clap(long = "impl", default_value = days::DEFAULT_IMPL)]
+    implementation: String,
+
+    /// input to run on; defaults to the day's puzzle input
+    #[clap(short, long, parse(from_os_str))]
+    input: Option<PathBuf>,
+
+    /// whose puzzle input to use by default, read from <inputs>/<year>/<profile>/
+    #[clap(long)]
+    profile: Option<String>,
+
+    /// run on every input in the directory instead, tabulating the answers per input
+    #[clap(long, parse(from_os_str), conflicts_with_all = &["input", "profile"])]
+    inputs_dir: Option<PathBuf>,
+
+    /// read the input a line at a time rather than all at once, for inputs too big for memory;
+    /// `--input -` reads stdin
+    #[clap(long)]
+    stream: bool,
+
+    /// print the answers as a table (text) or as json; text unless aoc.toml says otherwise
+    #[clap(long)]
+    format: Option<Format>,
+
+    /// refuse inputs that need normalizing (crlf, a byte order mark, etc) rather than fixing them
+    #[clap(long)]
+    strict: bool,
+}
+
+/// input path standing for stdin when streaming
+const STDIN: &str = "-";
+
+/// answers for a single input, one cell per part that was run
+struct Row {
+    name: String,
+    cells: Vec<String>,
+}
+
+/// every input in `dir` for the given day, sorted by name
+///
+/// plain files are inputs in their own right, while subdirectories are profiles whose input for
+/// the day is picked up if they have one, so both a directory of inputs for the day and
+/// src/inputs itself work. other days' input-N files are skipped
+fn inputs(dir: &Path, day: u32) -> Result<Vec<(String, PathBuf)>, String> {
+    let entries = read_dir(dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+    let wanted = format!("input-{}", day);
+
+    let mut inputs = Vec::new();
+
+    for entry in entries {
+        let entry = entry.map_err(|e| format!("{}: {}", dir.display(), e))?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let path = entry.path();
+
+        if name.starts_with('.') {
+            continue;
+        }
+
+        if path.is_dir() {
+            let input = path.join(&wanted);
+            if input.is_file() {
+                inputs.push((name, input));
+            }
+        } else if name == wanted || !is_day_input(&name) {
+            inputs.push((name, path));
+        }
+    }
+
+    inputs.sort();
+    Ok(inputs)
+}
+
+/// whether the name looks like some day's puzzle input, i.e. input-N
+fn is_day_input(name: &str) -> bool {
+    name.strip_prefix("input-")
+        .is_some_and(|number| number.parse::<u32>().is_ok())
+}
+
+fn solve(
+    day: &Day,
+    solvers: &[Solver],
+    implementation: &str,
+    path: &Path,
+    strict: bool,
+) -> Vec<String> {
+    let input = match read_input(path, strict) {
+        Ok(input) => input,
+        Err(e) => return vec![format!("error: {}", e); solvers.len()],
+    };
+
+    solvers
+        .iter()
+        .map(|solver| {
+            // already checked the implementation exists before getting here
+            let solution = day.implementation(*solver, implementation).unwrap();
+            match solution(&input) {
+                Ok(answer) => answer.to_string(),
+                Err(e) => format!("error: {}", e),
+            }
+        })
+        .collect()
+}
+
+/// like solve, but reading the input a line at a time, once per part; a path of `-` is stdin
+fn stream(streaming: &Streaming, solvers: &[Solver], path: &Path) -> Vec<String> {
+    solvers
+        .iter()
+        .map(|solver| {
+            let mut reader: Box<dyn BufRead> = if path == Path::new(STDIN) {
+                Box::new(stdin().lock())
+            } else {
+                match File::open(path) {
+                    Ok(file) => Box::new(BufReader::new(file)),
+                    Err(e) => return format!("error: {}", e),
+                }
+            };
+
+            match streaming.solution(*solver)(&mut reader) {
+                Ok(answer) => answer.to_string(),
+                Err(e) => format!("error: {}", e),
+            }
+        })
+        .collect()
+}
+
+fn render(solvers: &[Solver], rows: &[Row]) -> String {
+    let width = rows
+        .iter()
+        .map(|row| row.name.len())
+        .chain(["input".len()])
+        .max()
+        .unwrap_or_default();
+    let answer_width = rows
+        .iter()
+        .flat_map(|row| row.cells.iter().map(String::len))
+        .chain(["part 1".len()])
+        .max()
+        .unwrap_or_default();
+
+    let header = solvers
+        .iter()
+        .map(|solver| format!("part {}", solver.number()))
+        .map(|part| format!("  {:<width$}", part, width = answer_width))
+        .collect::<String>();
+    let mut result = format!("{:<width$}{}\n", "input", header, width = width);
+
+    for row in rows {
+        let cells = row
+            .cells
+            .iter()
+            .map(|cell| format!("  {:<width$}", cell, width = answer_width))
+            .collect::<String>();
+        result.push_str(&format!("{:<width$}{}\n", row.name, cells, width = width));
+    }
+
+    // padding the last column is only there to line up the ones after it
+    result
+        .lines()
+        .map(|line| format!("{}\n", line.trim_end()))
+        .collect()
+}
+
+/// the same as render, as a json array with an object per input
+fn render_json(solvers: &[Solver], rows: &[Row]) -> String {
+    let objects = rows
+        .iter()
+        .map(|row| {
+            let cells = solvers
+                .iter()
+                .zip(&row.cells)
+                .map(|(solver, cell)| format!(",\"part_{}\":{}", solver.number(), quote(cell)));
+            format!(
+                "  {{\"input\":{}{}}}",
+                quote(&row.name),
+                cells.collect::<String>()
+            )
+        })
+        .collect::<Vec<String>>();
+
+    format!("[\n{}\n]\n", objects.join(",\n"))
+}
+
+pub fn run(options: Options, config: Config) -> Result<(), String> {
+    let mut config = config.with_flags(options.year, options.profile);
+    config.format.flag(options.format);
+    config.strict.flag(options.strict.then_some(true));
+    let day = events::day(config.year.value, options.day)?;
+
+    let solvers = options.part.solvers();
+
+    for solver in &solvers {
+        if day
+            .implementation(*solver, &options.implementation)
+            .is_none()
+        {
+            let names = day
+                .implementations(*solver)
+                .iter()
+                .map(|implementation| implementation.name)
+                .collect::<Vec<&str>>();
+            return Err(format!(
+                "got unexpected value for --impl: {} (try {})",
+                options.implementation,
+                names.join(" or ")
+            ));
+        }
+    }
+
+    let inputs = match options.inputs_dir {
+        Some(dir) => {
+            let inputs = inputs(&dir, day.number)?;
+            if inputs.is_empty() {
+                return Err(format!(
+                    "no inputs for day {} in {}",
+                    day.number,
+                    dir.display()
+                ));
+            }
+            inputs
+        }
+        None => {
+            let path = options
+                .input
+                .unwrap_or_else(|| config.input_path(day.number));
+            vec![(path.display().to_string(), path)]
+        }
+    };
+
+    let streaming = match options.stream {
+        true => Some(day.streaming.ok_or(format!(
+            "day {} can't stream its input, it needs all of it at once",
+            day.number
+        ))?),
+        false => None,
+    };
+    if streaming.is_some() && options.implementation != days::DEFAULT_IMPL {
+        return Err(String::from("--stream only has the default implementation"));
+    }
+    if inputs.iter().any(|(_, path)| path == Path::new(STDIN)) && solvers.len() > 1 {
+        return Err(String::from(
+            "stdin can only be read once, pick a part with --part",
+        ));
+    }
+
+    let rows = inputs
+        .iter()
+        .map(|(name, path)| Row {
+            name: name.clone(),
+            cells: match &streaming {
+                Some(streaming) => stream(streaming, &solvers, path),
+                None => solve(
+                    day,
+                    &solvers,
+                    &options.implementation,
+                    path,
+                    config.strict.value,
+                ),
+            },
+        })
+        .collect::<Vec<Row>>();
+
+    match config.format.value {
+        Format::Text => print!("{}", render(&solvers, &rows)),
+        Format::Json => print!("{}", render_json(&solvers, &rows)),
+    }
+
+    let failures = rows
+        .iter()
+        .filter(|row| row.cells.iter().any(|cell| cell.starts_with("error: ")))
+        .count();
+    if failures > 0 {
+        return Err(format!("{} of {} input(s) failed", failures, rows.len()));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_day_input() {
+        assert!(is_day_input("input-4"));
+        assert!(!is_day_input("input-alice"));
+        assert!(!is_day_input("alice.txt"));
+    }
+
+    #[test]
+    fn test_render() {
+        let rows = [
+            Row {
+                name: String::from("alice"),
+                cells: vec![String::from("1521"), String::from("1543")],
+            },
+            Row {
+                name: String::from("bob-input"),
+                cells: vec![String::from("error: line 2: bad"), String::from("7")],
+            },
+        ];
+
+        assert_eq!(
+            "input      part 1              part 2\n\
+             alice      1521                1543\n\
+             bob-input  error: line 2: bad  7\n",
+            render(&[Solver::PartOne, Solver::PartTwo], &rows)
+        );
+
+        assert_eq!(
+            "[\n  {\"input\":\"alice\",\"part_1\":\"1521\",\"part_2\":\"1543\"},\n  \
+             {\"input\":\"bob-input\",\"part_1\":\"error: line 2: bad\",\"part_2\":\"7\"}\n]\n",
+            render_json(&[Solver::PartOne, Solver::PartTwo], &rows)
+        );
+    }
+}
@@ -0,0 +1,646 @@
+use advent_of_code_2021::config::Config;
+use advent_of_code_2021::days::Day;
+use advent_of_code_2021::json::quote;
+use advent_of_code_2021::{events, normalize, Answer, ParseError, Solver};
+use clap::Args;
+use std::io::{self, BufRead, BufReader, ErrorKind, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{channel, RecvTimeoutError};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// longest request line or header that's accepted
+const MAX_LINE: usize = 8 * 1024;
+
+#[derive(Args)]
+pub struct Options {
+    /// port to listen on
+    #[clap(short, long, default_value = "2021")]
+    port: u16,
+
+    /// address to listen on; only this machine by default
+    #[clap(long, default_value = "127.0.0.1")]
+    host: String,
+
+    /// largest input accepted, in bytes
+    #[clap(long, default_value = "1048576")]
+    max_body: usize,
+
+    /// seconds a request gets to arrive in full, then again to be solved, before it's given up
+    /// on; 10 unless aoc.toml says otherwise
+    #[clap(long)]
+    timeout: Option<u64>,
+
+    /// most inputs solved at once, counting ones still solving after their request timed out;
+    /// one per core by default
+    #[clap(long)]
+    max_solves: Option<usize>,
+
+    /// most connections handled at once, counting ones still sending their request
+    #[clap(long, default_value = "64")]
+    max_connections: usize,
+
+    /// refuse inputs that need normalizing (crlf, a byte order mark, etc) rather than fixing them
+    #[clap(long)]
+    strict: bool,
+}
+
+#[derive(Clone, Copy)]
+struct Limits {
+    max_body: usize,
+    timeout: Duration,
+    max_solves: usize,
+    max_connections: usize,
+    strict: bool,
+}
+
+/// how many threads of a kind are running, i.e. solvers: a timed out solver can't be stopped, so
+/// this is what keeps slow requests from piling up threads without end
+#[derive(Clone, Default)]
+struct Running(Arc<AtomicUsize>);
+
+impl Running {
+    /// a place for another thread, none when all `max` of them are taken
+    fn claim(&self, max: usize) -> Option<Slot> {
+        self.0
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |running| {
+                (running < max).then_some(running + 1)
+            })
+            .ok()?;
+        Some(Slot(self.0.clone()))
+    }
+}
+
+/// a claimed place for a thread, given back when the thread finishes with it
+struct Slot(Arc<AtomicUsize>);
+
+impl Drop for Slot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+#[derive(Debug)]
+struct Request {
+    method: String,
+    path: String,
+    body: Vec<u8>,
+}
+
+#[derive(Debug, PartialEq)]
+struct Response {
+    status: u16,
+    body: String,
+}
+
+impl Response {
+    fn error(status: u16, message: &str) -> Self {
+        Self {
+            status,
+            body: format!("{{\"error\":{}}}", quote(message)),
+        }
+    }
+
+    fn reason(&self) -> &'static str {
+        match self.status {
+            200 => "OK",
+            400 => "Bad Request",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            408 => "Request Timeout",
+            411 => "Length Required",
+            413 => "Payload Too Large",
+            422 => "Unprocessable Entity",
+            500 => "Internal Server Error",
+            503 => "Service Unavailable",
+            504 => "Gateway Timeout",
+            _ => "Unknown",
+        }
+    }
+}
+
+/// a connection that has until the deadline to send its request, however slowly it trickles in;
+/// the socket's own timeout only covers a single read
+struct Deadline {
+    stream: TcpStream,
+    until: Instant,
+}
+
+impl Read for Deadline {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let left = self.until.saturating_duration_since(Instant::now());
+        if left.is_zero() {
+            return Err(io::Error::new(ErrorKind::TimedOut, "past the deadline"));
+        }
+
+        self.stream.set_read_timeout(Some(left))?;
+        self.stream.read(buf)
+    }
+}
+
+fn error_json(error: &ParseError) -> String {
+    format!(
+        "{{\"line\":{},\"column\":{},\"message\":{}}}",
+        error.line,
+        error
+            .column
+            .map_or(String::from("null"), |column| column.to_string()),
+        quote(&error.message)
+    )
+}
+
+/// one line of the request head, without its line ending
+fn read_line(reader: &mut impl BufRead) -> Result<String, Response> {
+    let mut line = Vec::new();
+    reader
+        .take(MAX_LINE as u64 + 1)
+        .read_until(b'\n', &mut line)
+        .map_err(|_| Response::error(408, "timed out reading the request"))?;
+
+    if line.len() > MAX_LINE {
+        return Err(Response::error(400, "request line or header too long"));
+    }
+
+    String::from_utf8(line)
+        .map(|line| line.trim_end_matches(['\r', '\n']).to_string())
+        .map_err(|_| Response::error(400, "request head isn't utf-8"))
+}
+
+fn read_request(reader: &mut impl BufRead, max_body: usize) -> Result<Request, Response> {
+    let request_line = read_line(reader)?;
+    let mut parts = request_line.split_whitespace();
+    let (method, path) = match (parts.next(), parts.next()) {
+        (Some(method), Some(path)) => (method.to_string(), path.to_string()),
+        _ => return Err(Response::error(400, "malformed request line")),
+    };
+
+    let mut length = None;
+    loop {
+        let header = read_line(reader)?;
+        if header.is_empty() {
+            break;
+        }
+
+        if let Some((name, value)) = header.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                let value = value
+                    .trim()
+                    .parse::<usize>()
+                    .map_err(|_| Response::error(400, "bad content-length"))?;
+                length = Some(value);
+            }
+        }
+    }
+
+    let length = match (method.as_str(), length) {
+        ("POST", None) => return Err(Response::error(411, "content-length is required")),
+        (_, length) => length.unwrap_or_default(),
+    };
+    if length > max_body {
+        return Err(Response::error(
+            413,
+            &format!("input is over the {} byte limit", max_body),
+        ));
+    }
+
+    let mut body = vec![0; length];
+    reader
+        .read_exact(&mut body)
+        .map_err(|_| Response::error(408, "timed out reading the input"))?;
+
+    Ok(Request { method, path, body })
+}
+
+/// year, day and part from a path like /year/2021/day/4/part/2, or /day/4/part/2 for the
+/// default year; the part's left to `Solver` to make sense of
+fn parse_path(path: &str) -> Option<(u32, u32, &str)> {
+    match path.trim_end_matches('/').split('/').collect::<Vec<&str>>()[..] {
+        ["", "day", day, "part", part] => Some((events::DEFAULT_YEAR, day.parse().ok()?, part)),
+        ["", "year", year, "day", day, "part", part] => {
+            Some((year.parse().ok()?, day.parse().ok()?, part))
+        }
+        _ => None,
+    }
+}
+
+fn errors_response(errors: &[ParseError]) -> Response {
+    let errors = errors.iter().map(error_json).collect::<Vec<String>>();
+    Response {
+        status: 422,
+        body: format!("{{\"errors\":[{}]}}", errors.join(",")),
+    }
+}
+
+/// normalize and validate the input the way an input file would be, then solve it on another
+/// thread, giving up on it after the timeout
+fn solve(
+    day: &'static Day,
+    solver: Solver,
+    input: &str,
+    limits: Limits,
+    solving: &Running,
+) -> Response {
+    if limits.strict {
+        let problems = normalize::problems(input);
+        if !problems.is_empty() {
+            return errors_response(&problems);
+        }
+    }
+    let input = normalize::normalize(input);
+
+    let errors = (day.validate)(&input);
+    if !errors.is_empty() {
+        return errors_response(&errors);
+    }
+
+    let slot = match solving.claim(limits.max_solves) {
+        Some(slot) => slot,
+        None => return Response::error(503, "too many inputs being solved, try again later"),
+    };
+
+    let (sender, receiver) = channel();
+    thread::spawn(move || {
+        let start = Instant::now();
+        let answer = catch_unwind(AssertUnwindSafe(|| day.solution(solver)(&input)));
+        drop(slot);
+        sender.send((answer, start.elapsed())).ok();
+    });
+    let timeout = limits.timeout;
+
+    // there's no stopping a solver that's taking too long, it's left to finish in the background
+    let (answer, elapsed) = match receiver.recv_timeout(timeout) {
+        Ok(result) => result,
+        Err(RecvTimeoutError::Timeout) => {
+            return Response::error(504, &format!("gave up after {:?}", timeout))
+        }
+        Err(RecvTimeoutError::Disconnected) => return Response::error(500, "solver went away"),
+    };
+
+    match answer {
+        Ok(Ok(answer)) => {
+            let answer = match answer {
+                Answer::Number(value) => value.to_string(),
+                Answer::Text(value) => quote(&value),
+            };
+            Response {
+                status: 200,
+                body: format!(
+                    "{{\"day\":{},\"part\":{},\"answer\":{},\"elapsed_ms\":{:.3},\"errors\":[]}}",
+                    day.number,
+                    solver.number(),
+                    answer,
+                    elapsed.as_secs_f64() * 1000.0
+                ),
+            }
+        }
+        Ok(Err(e)) => errors_response(&[e]),
+        Err(_) => Response::error(500, "the solver panicked"),
+    }
+}
+
+fn route(request: Request, limits: Limits, solving: &Running) -> Response {
+    let (year, number, part) = match parse_path(&request.path) {
+        Some(route) => route,
+        None => {
+            return Response::error(
+                404,
+                "try POST /day/{n}/part/{p} or /year/{y}/day/{n}/part/{p}",
+            )
+        }
+    };
+
+    if request.method != "POST" {
+        return Response::error(405, "only POST is supported");
+    }
+
+    let day = match events::day(year, number) {
+        Ok(day) => day,
+        Err(e) => return Response::error(404, &e),
+    };
+    let solver = match part.parse::<Solver>() {
+        Ok(solver) => solver,
+        Err(e) => return Response::error(404, &e),
+    };
+
+    match String::from_utf8(request.body) {
+        Ok(input) => solve(day, solver, &input, limits, solving),
+        Err(_) => Response::error(400, "input isn't utf-8"),
+    }
+}
+
+fn handle(mut stream: TcpStream, limits: Limits, solving: &Running) -> io::Result<()> {
+    stream.set_write_timeout(Some(limits.timeout))?;
+
+    let start = Instant::now();
+    let mut reader = BufReader::new(Deadline {
+        stream: stream.try_clone()?,
+        until: start + limits.timeout,
+    });
+    let (summary, response) = match read_request(&mut reader, limits.max_body) {
+        Ok(request) => (
+            format!("{} {}", request.method, request.path),
+            route(request, limits, solving),
+        ),
+        Err(response) => (String::from("-"), response),
+    };
+
+    eprintln!("{} {} {:.1?}", summary, response.status, start.elapsed());
+    respond(&mut stream, &response)
+}
+
+fn respond(stream: &mut TcpStream, response: &Response) -> io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        response.reason(),
+        response.body.len(),
+        response.body
+    )
+}
+
+fn serve(listener: TcpListener, limits: Limits) {
+    let solving = Running::default();
+    let connections = Running::default();
+
+    for stream in listener.incoming() {
+        let mut stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                eprintln!("[!] {}", e);
+                continue;
+            }
+        };
+
+        let slot = match connections.claim(limits.max_connections) {
+            Some(slot) => slot,
+            None => {
+                // the request isn't read, so this is all the connection ever hears
+                let response = Response::error(503, "too many connections, try again later");
+                eprintln!("- {} -", response.status);
+                let refused = stream
+                    .set_write_timeout(Some(Duration::from_secs(1)))
+                    .and_then(|_| respond(&mut stream, &response));
+                if let Err(e) = refused {
+                    eprintln!("[!] {}", e);
+                }
+                continue;
+            }
+        };
+
+        let solving = solving.clone();
+        thread::spawn(move || {
+            if let Err(e) = handle(stream, limits, &solving) {
+                eprintln!("[!] {}", e);
+            }
+            drop(slot);
+        });
+    }
+}
+
+pub fn run(options: Options, mut config: Config) -> Result<(), String> {
+    config.timeout.flag(options.timeout);
+    config.strict.flag(options.strict.then_some(true));
+
+    let max_solves = options
+        .max_solves
+        .unwrap_or_else(|| thread::available_parallelism().map_or(1, |cores| cores.get()));
+
+    let address = format!("{}:{}", options.host, options.port);
+    let listener = TcpListener::bind(&address).map_err(|e| format!("{}: {}", address, e))?;
+
+    eprintln!("listening on http://{}/day/{{n}}/part/{{p}}", address);
+
+    serve(
+        listener,
+        Limits {
+            max_body: options.max_body,
+            timeout: Duration::from_secs(config.timeout.value),
+            max_solves,
+            max_connections: options.max_connections,
+            strict: config.strict.value,
+        },
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use advent_of_code_2021::days;
+
+    const LIMITS: Limits = Limits {
+        max_body: 64,
+        timeout: Duration::from_secs(5),
+        max_solves: 2,
+        max_connections: 4,
+        strict: false,
+    };
+
+    fn request(raw: &str) -> Result<Request, Response> {
+        read_request(&mut raw.as_bytes(), LIMITS.max_body)
+    }
+
+    #[test]
+    fn test_read_request() {
+        let parsed =
+            request("POST /day/1/part/1 HTTP/1.1\r\nContent-Length: 4\r\n\r\n1\n2\nextra").unwrap();
+        assert_eq!(
+            ("POST", "/day/1/part/1"),
+            (parsed.method.as_str(), parsed.path.as_str())
+        );
+        assert_eq!(b"1\n2\n".to_vec(), parsed.body);
+
+        assert_eq!(411, request("POST / HTTP/1.1\r\n\r\n").unwrap_err().status);
+        assert_eq!(
+            413,
+            request("POST / HTTP/1.1\r\nContent-Length: 65\r\n\r\n")
+                .unwrap_err()
+                .status
+        );
+        assert_eq!(
+            408,
+            request("POST / HTTP/1.1\r\nContent-Length: 9\r\n\r\nshort")
+                .unwrap_err()
+                .status
+        );
+        assert_eq!(400, request("nonsense\r\n\r\n").unwrap_err().status);
+    }
+
+    #[test]
+    fn test_parse_path() {
+        assert_eq!(Some((2021, 4, "2")), parse_path("/day/4/part/2"));
+        assert_eq!(Some((2021, 4, "2")), parse_path("/day/4/part/2/"));
+        assert_eq!(Some((2022, 4, "2")), parse_path("/year/2022/day/4/part/2"));
+        assert_eq!(None, parse_path("/day/4"));
+        assert_eq!(None, parse_path("/day/four/part/2"));
+        assert_eq!(None, parse_path("/year/2021/day/4"));
+    }
+
+    fn post(path: &str, body: &str, limits: Limits) -> Response {
+        route(
+            Request {
+                method: String::from("POST"),
+                path: String::from(path),
+                body: body.as_bytes().to_vec(),
+            },
+            limits,
+            &Running::default(),
+        )
+    }
+
+    #[test]
+    fn test_route() {
+        let post = |path: &str, body: &str| post(path, body, LIMITS);
+
+        let response = post("/day/17/part/1", days::day_17::EXAMPLE);
+        assert_eq!(200, response.status);
+        assert!(response
+            .body
+            .starts_with("{\"day\":17,\"part\":1,\"answer\":45,\"elapsed_ms\":"));
+
+        let response = post("/day/9/part/1", "12\n3x\n");
+        assert_eq!(422, response.status);
+        assert_eq!(
+            "{\"errors\":[{\"line\":2,\"column\":2,\"message\":\"'x' isn't a height\"}]}",
+            response.body
+        );
+
+        assert_eq!(
+            200,
+            post("/year/2021/day/17/part/1", days::day_17::EXAMPLE).status
+        );
+        assert_eq!(404, post("/year/2015/day/1/part/1", "").status);
+        assert_eq!(404, post("/day/16/part/1", "").status);
+        assert_eq!(404, post("/day/1/part/3", "").status);
+        assert_eq!(404, post("/day/1/part/one", "").status);
+        assert_eq!(404, post("/", "").status);
+    }
+
+    #[test]
+    fn test_serve() {
+        let mut stream = TcpStream::connect(listen(LIMITS)).unwrap();
+        write!(
+            stream,
+            "POST /day/17/part/2 HTTP/1.1\r\nContent-Length: {}\r\n\r\n{}",
+            days::day_17::EXAMPLE.len(),
+            days::day_17::EXAMPLE
+        )
+        .unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains("\"answer\":112"));
+    }
+
+    /// serve on a port of its own, returning the address to connect to
+    fn listen(limits: Limits) -> std::net::SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        thread::spawn(move || serve(listener, limits));
+        address
+    }
+
+    #[test]
+    fn test_deadline() {
+        let limits = Limits {
+            timeout: Duration::from_secs(1),
+            ..LIMITS
+        };
+        let mut stream = TcpStream::connect(listen(limits)).unwrap();
+        let start = Instant::now();
+
+        // a byte at a time never leaves the socket waiting long, but the request never arrives
+        write!(stream, "POST /day/1/part/1 HTTP/1.1\r\n").unwrap();
+        for byte in b"X-Slow: ".iter().cycle().take(20) {
+            if stream.write_all(&[*byte]).is_err() {
+                break;
+            }
+            thread::sleep(Duration::from_millis(200));
+        }
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).ok();
+        assert!(response.starts_with("HTTP/1.1 408 "), "{}", response);
+        assert!(start.elapsed() < Duration::from_secs(4));
+    }
+
+    #[test]
+    fn test_max_connections() {
+        let limits = Limits {
+            max_connections: 1,
+            ..LIMITS
+        };
+        let address = listen(limits);
+
+        // the first connection holds the only place while it takes its time sending a request
+        let _first = TcpStream::connect(address).unwrap();
+        let mut second = TcpStream::connect(address).unwrap();
+
+        let mut response = String::new();
+        second.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 503 "), "{}", response);
+        assert!(response.contains("too many connections"));
+    }
+
+    #[test]
+    fn test_solve_timeout() {
+        let limits = Limits {
+            timeout: Duration::from_nanos(1),
+            ..LIMITS
+        };
+        let day = days::get(15).unwrap();
+        let input = (day.generate)(&mut advent_of_code_2021::rng::Rng::new(15), 100);
+
+        let response = solve(day, Solver::PartTwo, &input, limits, &Running::default());
+        assert_eq!(504, response.status, "{}", response.body);
+    }
+
+    #[test]
+    fn test_normalize() {
+        // the same input pasted from a windows editor, as a file of it would be read
+        let pasted = format!("\u{feff}{} \r\n", days::day_17::EXAMPLE);
+        let response = post("/day/17/part/1", &pasted, LIMITS);
+        assert_eq!(200, response.status, "{}", response.body);
+        assert!(response.body.contains("\"answer\":45,"));
+
+        let strict = Limits {
+            strict: true,
+            ..LIMITS
+        };
+        let response = post("/day/17/part/1", &pasted, strict);
+        assert_eq!(422, response.status);
+        assert!(
+            response.body.contains("byte order mark"),
+            "{}",
+            response.body
+        );
+    }
+
+    #[test]
+    fn test_max_solves() {
+        let solving = Running::default();
+
+        let (first, second) = (solving.claim(2), solving.claim(2));
+        assert!(first.is_some() && second.is_some());
+        assert!(solving.claim(2).is_none());
+
+        let response = solve(
+            days::get(17).unwrap(),
+            Solver::PartOne,
+            days::day_17::EXAMPLE,
+            LIMITS,
+            &solving,
+        );
+        assert_eq!(503, response.status);
+
+        drop(first);
+        assert!(solving.claim(2).is_some());
+    }
+}
@@ -0,0 +1,156 @@
+use advent_of_code_2021::config::Config;
+use advent_of_code_2021::ledger::Ledger;
+use advent_of_code_2021::{events, Solver};
+use clap::Args;
+use std::fs::metadata;
+
+#[derive(Args)]
+pub struct Options {
+    /// which year's calendar to show
+    #[clap(long)]
+    year: Option<u32>,
+}
+
+const PARTS: [Solver; 2] = [Solver::PartOne, Solver::PartTwo];
+
+/// everything known about a single day of the calendar
+#[derive(Debug, Default)]
+struct DayStatus {
+    number: u32,
+    implemented: bool,
+    has_input: bool,
+    has_example: [bool; 2],
+    ledger: Ledger,
+}
+
+impl DayStatus {
+    fn gather(config: &Config, number: u32) -> Self {
+        let day = events::day(config.year.value, number).ok();
+
+        // an empty input file is what add-template.sh leaves behind, so it doesn't count
+        let has_input = metadata(config.input_path(number))
+            .map(|meta| meta.len() > 0)
+            .unwrap_or(false);
+
+        let ledger = Ledger::load(config.year.value, number).unwrap_or_else(|e| {
+            eprintln!("[!] ignoring unreadable ledger: {}", e);
+            Ledger::default()
+        });
+
+        Self {
+            number,
+            implemented: day.is_some(),
+            has_input,
+            has_example: PARTS.map(|solver| day.is_some_and(|d| d.has_example(solver))),
+            ledger,
+        }
+    }
+
+    /// two character cell for the calendar
+    fn stars(&self) -> &'static str {
+        match (self.implemented, self.ledger.stars()) {
+            (_, 2) => "**",
+            (_, 1) => "*.",
+            (true, _) => "..",
+            (false, _) => "--",
+        }
+    }
+
+    /// everything left to do for the day, empty when there's nothing
+    fn missing(&self) -> Vec<String> {
+        let mut missing = Vec::new();
+
+        if !self.implemented {
+            missing.push(String::from("not implemented"));
+        }
+
+        if !self.has_input {
+            missing.push(String::from("missing input"));
+        }
+
+        if self.implemented {
+            for (idx, solver) in PARTS.iter().enumerate() {
+                if !self.has_example[idx] {
+                    missing.push(format!("part {} untested", idx + 1));
+                }
+                if self.ledger.get(*solver).is_none() {
+                    missing.push(format!("part {} unverified", idx + 1));
+                }
+            }
+        }
+
+        missing
+    }
+}
+
+fn render(year: u32, calendar: &[DayStatus]) -> String {
+    let stars = calendar.iter().map(|day| day.ledger.stars()).sum::<usize>();
+
+    let mut result = format!("Advent of Code {}: {}/50 stars\n\n", year, stars);
+
+    for week in calendar.chunks(5) {
+        let row = week
+            .iter()
+            .map(|day| format!("{:>2} {}", day.number, day.stars()))
+            .collect::<Vec<String>>();
+        result.push_str(&format!("  {}\n", row.join("   ")));
+    }
+
+    result.push_str("\n  ** both parts   *. part one   .. no stars   -- not implemented\n\n");
+
+    for day in calendar {
+        let missing = day.missing();
+        if !missing.is_empty() {
+            result.push_str(&format!("day {:>2}: {}\n", day.number, missing.join(", ")));
+        }
+    }
+
+    result
+}
+
+pub fn run(options: Options, config: Config) -> Result<(), String> {
+    let config = config.with_flags(options.year, None);
+
+    // a year that isn't registered is far more likely a typo than an event with nothing solved
+    events::event(config.year.value)?;
+
+    let calendar = (1..=25)
+        .map(|number| DayStatus::gather(&config, number))
+        .collect::<Vec<DayStatus>>();
+    print!("{}", render(config.year.value, &calendar));
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let mut calendar = (1..=25)
+            .map(|number| DayStatus {
+                number,
+                ..Default::default()
+            })
+            .collect::<Vec<DayStatus>>();
+
+        calendar[0] = DayStatus {
+            number: 1,
+            implemented: true,
+            has_input: true,
+            has_example: [true, false],
+            ledger: Ledger {
+                part_one: Some(String::from("7")),
+                part_two: None,
+            },
+        };
+
+        let rendered = render(2021, &calendar);
+
+        assert!(rendered.starts_with("Advent of Code 2021: 1/50 stars\n"));
+        assert!(rendered.contains("\n   1 *.    2 --    3 --    4 --    5 --\n"));
+        assert!(rendered.contains("day  1: part 2 untested, part 2 unverified\n"));
+        assert!(rendered.contains("day 16: not implemented, missing input\n"));
+    }
+}
@@ -0,0 +1,291 @@
+use advent_of_code_2021::config::Config;
+use advent_of_code_2021::ledger::Ledger;
+use advent_of_code_2021::{events, Exit, Parts, Solver};
+use clap::Args;
+use std::collections::HashMap;
+use std::env::var_os;
+use std::fs::metadata;
+use std::io::{stdout, IsTerminal};
+use std::path::PathBuf;
+use std::process::Command;
+use std::thread::sleep;
+use std::time::{Duration, Instant, SystemTime};
+
+#[derive(Args)]
+pub struct Options {
+    /// day to watch
+    #[clap(short, long)]
+    day: u32,
+
+    /// which part of the day: 1, 2 or both
+    #[clap(short, long, default_value = "both")]
+    part: Parts,
+
+    /// which year the day is from
+    #[clap(long)]
+    year: Option<u32>,
+
+    /// whose puzzle input to run on, read from <inputs>/<year>/<profile>/
+    #[clap(long)]
+    profile: Option<String>,
+
+    /// milliseconds between checking the files for changes
+    #[clap(long, default_value = "500")]
+    interval: u64,
+}
+
+/// when each file was last modified, none for a file that doesn't exist (yet)
+type Stamps = HashMap<PathBuf, Option<SystemTime>>;
+
+fn stamps(paths: &[PathBuf]) -> Stamps {
+    paths
+        .iter()
+        .map(|path| {
+            let modified = metadata(path).and_then(|meta| meta.modified()).ok();
+            (path.clone(), modified)
+        })
+        .collect()
+}
+
+/// files that were created, modified or deleted between the two looks
+fn changed(before: &Stamps, after: &Stamps) -> Vec<PathBuf> {
+    let mut changed = after
+        .iter()
+        .filter(|(path, modified)| before.get(*path) != Some(*modified))
+        .map(|(path, _)| path.clone())
+        .collect::<Vec<PathBuf>>();
+    changed.sort();
+    changed
+}
+
+/// the part's example results, picked out of everything else the day prints, then how it did on
+/// the real input: going by the exit code, its answer (the last line printed) against the
+/// ledger's, or what went wrong
+fn summarize(
+    solver: Solver,
+    examples: &str,
+    code: Option<i32>,
+    solved: &str,
+    error: &str,
+    ledger: &Ledger,
+) -> String {
+    let mut result = String::new();
+    let label = format!("part {}", solver.number());
+
+    let lines = examples
+        .lines()
+        .filter(|line| line.starts_with("[+] example") || line.starts_with("[!] example"))
+        .collect::<Vec<&str>>();
+    for line in &lines {
+        result.push_str(&format!("{}{} {}\n", &line[..4], label, &line[4..]));
+    }
+    if lines.is_empty() {
+        result.push_str(&format!("[ ] {} examples: none\n", label));
+    }
+
+    let answer = solved.lines().rev().find(|line| !line.trim().is_empty());
+    let error = error.trim();
+    let line = match (code, answer, ledger.get(solver)) {
+        (Some(0), None, _) => format!("[!] {} input: no answer", label),
+        (Some(0), Some(answer), Some(accepted)) if answer == accepted => {
+            format!("[+] {} input: {} (accepted)", label, answer)
+        }
+        (Some(0), Some(answer), Some(accepted)) => format!(
+            "[!] {} input: {} (the accepted answer is {})",
+            label, answer, accepted
+        ),
+        (Some(0), Some(answer), None) => format!("[ ] {} input: {} (unverified)", label, answer),
+        (Some(code), _, _) if code == Exit::BadInput as i32 => {
+            format!("[!] {} input: bad input: {}", label, error)
+        }
+        (Some(code), _, _) if code == Exit::SolverFailed as i32 => {
+            format!("[!] {} input: solving failed: {}", label, error)
+        }
+        (Some(code), _, _) => format!("[!] {} input: exited with {}: {}", label, code, error),
+        (None, _, _) => format!("[!] {} input: killed: {}", label, error),
+    };
+    result.push_str(&line);
+    result.push('\n');
+
+    result
+}
+
+/// rebuild the day's binary, then run the examples and the input for each part through the built
+/// binary; straight rather than through `cargo run`, which replays the build's warnings
+fn cycle(options: &Options, config: &Config, solvers: &[Solver]) -> Result<(), String> {
+    let binary = format!("day-{}", options.day);
+    let start = Instant::now();
+
+    let build = Command::new("cargo")
+        .args(["build", "--quiet", "--bin", &binary])
+        .output()
+        .map_err(|e| format!("couldn't run cargo: {}", e))?;
+    if !build.status.success() {
+        eprint!("{}", String::from_utf8_lossy(&build.stderr));
+        println!("[!] build failed");
+        return Ok(());
+    }
+    println!("[+] built {} in {:.1?}", binary, start.elapsed());
+
+    let ledger = Ledger::load(config.year.value, options.day)?;
+    let built = var_os("CARGO_TARGET_DIR")
+        .map_or_else(|| PathBuf::from("target"), PathBuf::from)
+        .join("debug")
+        .join(&binary);
+    let run = |solver: Solver, extra: &[&str]| {
+        let mut args = vec![
+            String::from("--part"),
+            solver.number().to_string(),
+            String::from("--year"),
+            config.year.value.to_string(),
+        ];
+        if let Some(profile) = &config.profile.value {
+            args.extend([String::from("--profile"), profile.clone()]);
+        }
+        args.extend(extra.iter().map(|arg| arg.to_string()));
+
+        Command::new(&built)
+            .args(args)
+            .output()
+            .map_err(|e| format!("couldn't run {}: {}", binary, e))
+    };
+
+    for solver in solvers {
+        let examples = run(*solver, &["--examples"])?;
+        let solved = run(*solver, &[])?;
+        print!(
+            "{}",
+            summarize(
+                *solver,
+                &String::from_utf8_lossy(&examples.stdout),
+                solved.status.code(),
+                &String::from_utf8_lossy(&solved.stdout),
+                &String::from_utf8_lossy(&solved.stderr),
+                &ledger
+            )
+        );
+    }
+
+    Ok(())
+}
+
+pub fn run(options: Options, config: Config) -> Result<(), String> {
+    let config = config.with_flags(options.year, options.profile.clone());
+    events::day(config.year.value, options.day)?;
+
+    let solvers = options.part.solvers();
+
+    // the examples and their expected answers live in the day's source and the registry
+    let paths = vec![
+        PathBuf::from(format!("src/days/day_{}.rs", options.day)),
+        PathBuf::from("src/days/mod.rs"),
+        PathBuf::from(format!("src/bin/day-{}.rs", options.day)),
+        config.input_path(options.day),
+    ];
+    let mut seen = stamps(&paths);
+    let mut changes: Vec<PathBuf> = Vec::new();
+
+    loop {
+        if stdout().is_terminal() {
+            print!("\x1b[2J\x1b[H");
+        }
+        let files = changes
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect::<Vec<String>>();
+        match files.is_empty() {
+            true => println!("[*] watching day {}", options.day),
+            false => println!("[*] changed: {}", files.join(", ")),
+        }
+
+        cycle(&options, &config, &solvers)?;
+
+        loop {
+            sleep(Duration::from_millis(options.interval));
+            let now = stamps(&paths);
+            changes = changed(&seen, &now);
+            if !changes.is_empty() {
+                seen = now;
+                break;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summarize() {
+        let ledger = Ledger {
+            part_one: Some(String::from("4512")),
+            part_two: None,
+        };
+        let examples = "board 3\n[+] example 1: 4512\n[!] example 2: expected 5, got 6\n";
+
+        assert_eq!(
+            "[+] part 1 example 1: 4512\n\
+             [!] part 1 example 2: expected 5, got 6\n\
+             [+] part 1 input: 4512 (accepted)\n",
+            summarize(
+                Solver::PartOne,
+                examples,
+                Some(0),
+                "debugging\n4512\n",
+                "",
+                &ledger
+            )
+        );
+        assert_eq!(
+            "[ ] part 2 examples: none\n[ ] part 2 input: 1924 (unverified)\n",
+            summarize(Solver::PartTwo, "", Some(0), "1924\n", "", &ledger)
+        );
+
+        // whatever else ends up on stderr, exiting cleanly means there's an answer
+        let warning = "warning: unused variable: `idx`\n";
+        assert!(summarize(
+            Solver::PartOne,
+            examples,
+            Some(0),
+            "4512\n",
+            warning,
+            &ledger
+        )
+        .ends_with("[+] part 1 input: 4512 (accepted)\n"));
+
+        let error = "couldn't parse input-4: line 1: missing called numbers\n";
+        assert!(
+            summarize(Solver::PartOne, examples, Some(3), "", error, &ledger).ends_with(
+                "[!] part 1 input: bad input: couldn't parse input-4: line 1: missing called numbers\n"
+            )
+        );
+        let error = "day 4 part 1: score overflowed an i32 (100000 * 100000)\n";
+        assert!(
+            summarize(Solver::PartOne, examples, Some(4), "", error, &ledger).ends_with(
+                "[!] part 1 input: solving failed: day 4 part 1: score overflowed an i32 (100000 * 100000)\n"
+            )
+        );
+        assert!(
+            summarize(Solver::PartOne, examples, Some(0), "17\n", "", &ledger)
+                .ends_with("[!] part 1 input: 17 (the accepted answer is 4512)\n")
+        );
+    }
+
+    #[test]
+    fn test_changed() {
+        let path = std::env::temp_dir().join(format!("aoc-watch-{}", std::process::id()));
+        let paths = [path.clone()];
+
+        let missing = stamps(&paths);
+        assert_eq!(Some(&None), missing.get(&path));
+        assert!(changed(&missing, &stamps(&paths)).is_empty());
+
+        std::fs::write(&path, "1").unwrap();
+        let created = stamps(&paths);
+        assert_eq!(vec![path.clone()], changed(&missing, &created));
+
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(vec![path.clone()], changed(&created, &stamps(&paths)));
+    }
+}
@@ -0,0 +1,166 @@
+//! arithmetic for answers that can outgrow an i32, see the `checked` feature
+//!
+//! with the feature, arithmetic that overflows and conversions that don't fit are errors naming
+//! the day, part and operation. without it arithmetic is plain arithmetic, which panics on overflow
+//! in a debug build and wraps in a release one, and conversions truncate like `as`
+
+use crate::error::ParseError;
+use crate::Solver;
+use std::fmt::Display;
+
+/// integers an answer gets narrowed to an i32 from
+pub trait Wide: Copy + Display + TryInto<i32> {
+    /// the low 32 bits, what `as i32` gives
+    fn truncate(self) -> i32;
+}
+
+impl Wide for u32 {
+    fn truncate(self) -> i32 {
+        self as i32
+    }
+}
+
+impl Wide for u64 {
+    fn truncate(self) -> i32 {
+        self as i32
+    }
+}
+
+impl Wide for usize {
+    fn truncate(self) -> i32 {
+        self as i32
+    }
+}
+
+/// a step of working out a day's answer, i.e. day 7's fuel cost, for saying which one overflowed
+#[derive(Copy, Clone, Debug)]
+pub struct Operation {
+    pub day: u32,
+    pub solver: Solver,
+    pub name: &'static str,
+}
+
+impl Operation {
+    pub fn new(day: u32, solver: Solver, name: &'static str) -> Self {
+        Self { day, solver, name }
+    }
+
+    fn overflow(&self, detail: String) -> ParseError {
+        ParseError::overall(format!(
+            "day {} part {}: {} overflowed an i32 ({})",
+            self.day,
+            self.solver.number(),
+            self.name,
+            detail
+        ))
+    }
+
+    pub fn add(&self, left: i32, right: i32) -> Result<i32, ParseError> {
+        match cfg!(feature = "checked") {
+            true => left
+                .checked_add(right)
+                .ok_or_else(|| self.overflow(format!("{} + {}", left, right))),
+            false => Ok(left + right),
+        }
+    }
+
+    pub fn sub(&self, left: i32, right: i32) -> Result<i32, ParseError> {
+        match cfg!(feature = "checked") {
+            true => left
+                .checked_sub(right)
+                .ok_or_else(|| self.overflow(format!("{} - {}", left, right))),
+            false => Ok(left - right),
+        }
+    }
+
+    pub fn mul(&self, left: i32, right: i32) -> Result<i32, ParseError> {
+        match cfg!(feature = "checked") {
+            true => left
+                .checked_mul(right)
+                .ok_or_else(|| self.overflow(format!("{} * {}", left, right))),
+            false => Ok(left * right),
+        }
+    }
+
+    /// the value as an i32, for answers worked out in something wider
+    pub fn narrow(&self, value: impl Wide) -> Result<i32, ParseError> {
+        match cfg!(feature = "checked") {
+            true => value
+                .try_into()
+                .map_err(|_| self.overflow(format!("{} is too big", value))),
+            false => Ok(value.truncate()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_operations() {
+        let operation = Operation::new(12, Solver::PartTwo, "path count");
+
+        assert_eq!(Ok(7), operation.add(3, 4));
+        assert_eq!(Ok(-1), operation.sub(3, 4));
+        assert_eq!(Ok(12), operation.mul(3, 4));
+        assert_eq!(Ok(i32::MAX), operation.narrow(i32::MAX as usize));
+
+        let narrowed = [
+            (operation.narrow(1usize << 32), 0, "4294967296 is too big"),
+            (
+                operation.narrow(u64::MAX),
+                -1,
+                "18446744073709551615 is too big",
+            ),
+        ];
+
+        for (result, truncated, detail) in narrowed {
+            let expected = match cfg!(feature = "checked") {
+                true => Err(ParseError::overall(format!(
+                    "day 12 part 2: path count overflowed an i32 ({})",
+                    detail
+                ))),
+                false => Ok(truncated),
+            };
+            assert_eq!(expected, result);
+        }
+
+        assert_eq!(
+            "day 12 part 2: path count overflowed an i32 (2147483647 + 1)",
+            operation
+                .overflow(String::from("2147483647 + 1"))
+                .to_string()
+        );
+    }
+
+    #[cfg(feature = "checked")]
+    #[test]
+    fn test_overflow() {
+        let operation = Operation::new(12, Solver::PartTwo, "path count");
+
+        let overflows = [
+            (operation.add(i32::MAX, 1), "2147483647 + 1"),
+            (operation.sub(i32::MIN, 1), "-2147483648 - 1"),
+            (operation.mul(65536, 65536), "65536 * 65536"),
+        ];
+
+        for (result, detail) in overflows {
+            assert_eq!(
+                Err(ParseError::overall(format!(
+                    "day 12 part 2: path count overflowed an i32 ({})",
+                    detail
+                ))),
+                result
+            );
+        }
+    }
+
+    // without the feature, overflowing is the build's to deal with
+    #[cfg(all(not(feature = "checked"), debug_assertions))]
+    #[test]
+    #[should_panic(expected = "overflow")]
+    fn test_overflow_panics() {
+        let _ = Operation::new(12, Solver::PartTwo, "path count").add(i32::MAX, 1);
+    }
+}
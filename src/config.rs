@@ -0,0 +1,485 @@
+use crate::{events, input_path, ParseError};
+use std::env;
+use std::fmt::{Display, Formatter};
+use std::fs::read_to_string;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// config file looked for in the directory the runner is started from, i.e. the repo root
+pub const FILE: &str = "aoc.toml";
+
+/// every setting, along with the environment variable that overrides it
+pub const SETTINGS: [(&str, &str); 7] = [
+    ("year", "AOC_YEAR"),
+    ("inputs", "AOC_INPUTS"),
+    ("profile", "AOC_PROFILE"),
+    ("format", "AOC_FORMAT"),
+    ("timeout", "AOC_TIMEOUT"),
+    ("session", "AOC_SESSION"),
+    ("strict", "AOC_STRICT"),
+];
+
+/// where a setting's value came from
+#[derive(Clone, Debug, PartialEq)]
+pub enum Source {
+    Default,
+    File(PathBuf),
+    Env(&'static str),
+    Flag,
+}
+
+impl Display for Source {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Source::Default => write!(f, "default"),
+            Source::File(path) => write!(f, "{}", path.display()),
+            Source::Env(var) => write!(f, "${}", var),
+            Source::Flag => write!(f, "flag"),
+        }
+    }
+}
+
+/// a setting's value along with where it came from
+#[derive(Clone, Debug, PartialEq)]
+pub struct Setting<T> {
+    pub value: T,
+    pub source: Source,
+}
+
+impl<T> Setting<T> {
+    fn new(value: T) -> Self {
+        Self {
+            value,
+            source: Source::Default,
+        }
+    }
+
+    /// take the flag's value when it was given, since flags beat everything else
+    pub fn flag(&mut self, value: Option<T>) {
+        if let Some(value) = value {
+            self.value = value;
+            self.source = Source::Flag;
+        }
+    }
+}
+
+/// how subcommands print their results
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("unknown format {:?} (try text or json)", value)),
+        }
+    }
+}
+
+impl Display for Format {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Format::Text => write!(f, "text"),
+            Format::Json => write!(f, "json"),
+        }
+    }
+}
+
+/// effective runner settings, from the defaults, the user's and the repo's aoc.toml, AOC_*
+/// environment variables and finally the flags, each overriding the ones before it
+#[derive(Clone, Debug, PartialEq)]
+pub struct Config {
+    /// year commands use when none is given
+    pub year: Setting<u32>,
+    /// directory holding each year's inputs
+    pub inputs: Setting<PathBuf>,
+    /// whose puzzle inputs to use, from inputs/<year>/<profile>/
+    pub profile: Setting<Option<String>>,
+    /// how `aoc run` prints its answers
+    pub format: Setting<Format>,
+    /// seconds `aoc serve` gives a request to arrive and be solved
+    pub timeout: Setting<u64>,
+    /// file holding the adventofcode.com session cookie
+    pub session: Setting<Option<PathBuf>>,
+    /// refuse inputs that need normalizing (crlf, a byte order mark, etc) rather than fixing them
+    pub strict: Setting<bool>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            year: Setting::new(events::DEFAULT_YEAR),
+            inputs: Setting::new(PathBuf::from("src/inputs")),
+            profile: Setting::new(None),
+            format: Setting::new(Format::Text),
+            timeout: Setting::new(10),
+            session: Setting::new(config_dir().map(|dir| dir.join("session"))),
+            strict: Setting::new(false),
+        }
+    }
+}
+
+impl Config {
+    /// settings from the user's config, then the repo's, then the environment
+    pub fn load() -> Result<Self, String> {
+        let files = [
+            config_dir().map(|dir| dir.join(FILE)),
+            Some(PathBuf::from(FILE)),
+        ];
+        Self::load_from(files.iter().flatten(), |var| env::var(var).ok())
+    }
+
+    /// settings from each of the files that exist, later ones winning, then the variables
+    /// `var` has values for
+    pub fn load_from<'a>(
+        files: impl IntoIterator<Item = &'a PathBuf>,
+        var: impl Fn(&str) -> Option<String>,
+    ) -> Result<Self, String> {
+        let mut config = Self::default();
+
+        for path in files {
+            match read_to_string(path) {
+                Ok(text) => config
+                    .merge(&text, path)
+                    .map_err(|e| format!("{}: {}", path.display(), e))?,
+                Err(e) if e.kind() == ErrorKind::NotFound => {}
+                Err(e) => return Err(format!("{}: {}", path.display(), e)),
+            }
+        }
+
+        for (key, name) in SETTINGS {
+            if let Some(value) = var(name) {
+                config
+                    .set(key, &value, Source::Env(name))
+                    .map_err(|e| format!("${}: {}", name, e))?;
+            }
+        }
+
+        Ok(config)
+    }
+
+    /// apply the `key = value` lines of a config file, which is the little of toml needed:
+    /// comments, and strings or bare numbers as values
+    fn merge(&mut self, text: &str, path: &Path) -> Result<(), ParseError> {
+        for (idx, line) in text.lines().enumerate() {
+            let line = strip_comment(line).trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| ParseError::new(idx, "expected key = value"))?;
+            let value = unquote(value.trim()).map_err(|e| ParseError::new(idx, e))?;
+
+            self.set(key.trim(), &value, Source::File(path.to_path_buf()))
+                .map_err(|e| ParseError::new(idx, e))?;
+        }
+
+        Ok(())
+    }
+
+    fn set(&mut self, key: &str, value: &str, source: Source) -> Result<(), String> {
+        fn parse<T: FromStr>(value: &str, what: &str) -> Result<T, String> {
+            value
+                .parse()
+                .map_err(|_| format!("expected {}, got {:?}", what, value))
+        }
+
+        // an empty profile or session is a way of unsetting one a less specific config set
+        let optional = |value: &str| Some(value.to_string()).filter(|value| !value.is_empty());
+
+        match key {
+            "year" => {
+                self.year = Setting {
+                    value: parse(value, "a year")?,
+                    source,
+                }
+            }
+            "inputs" => {
+                self.inputs = Setting {
+                    value: PathBuf::from(value),
+                    source,
+                }
+            }
+            "profile" => {
+                self.profile = Setting {
+                    value: optional(value),
+                    source,
+                }
+            }
+            "format" => {
+                self.format = Setting {
+                    value: value.parse()?,
+                    source,
+                }
+            }
+            "timeout" => {
+                self.timeout = Setting {
+                    value: parse(value, "a number of seconds")?,
+                    source,
+                }
+            }
+            "session" => {
+                self.session = Setting {
+                    value: optional(value).map(PathBuf::from),
+                    source,
+                }
+            }
+            "strict" => {
+                self.strict = Setting {
+                    value: parse(value, "true or false")?,
+                    source,
+                }
+            }
+            _ => {
+                let keys = SETTINGS.map(|(key, _)| key);
+                return Err(format!(
+                    "unknown setting {:?} (try {})",
+                    key,
+                    keys.join(", ")
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// the config with the --year and --profile flags most commands take applied
+    pub fn with_flags(mut self, year: Option<u32>, profile: Option<String>) -> Self {
+        self.year.flag(year);
+        self.profile.flag(profile.map(Some));
+        self
+    }
+
+    /// location of a day's input in the configured inputs directory, year and profile
+    pub fn input_path(&self, day: u32) -> PathBuf {
+        input_path(
+            &self.inputs.value,
+            self.year.value,
+            day,
+            self.profile.value.as_deref(),
+        )
+    }
+
+    /// every setting's value and where it came from, as they'd be written in aoc.toml
+    pub fn show(&self) -> String {
+        let quote = |value: &str| format!("{:?}", value);
+        let optional = |value: Option<String>| value.map_or(String::from("\"\""), |v| quote(&v));
+
+        let rows = [
+            (self.year.value.to_string(), &self.year.source),
+            (
+                quote(&self.inputs.value.to_string_lossy()),
+                &self.inputs.source,
+            ),
+            (optional(self.profile.value.clone()), &self.profile.source),
+            (quote(&self.format.value.to_string()), &self.format.source),
+            (self.timeout.value.to_string(), &self.timeout.source),
+            (
+                optional(
+                    self.session
+                        .value
+                        .as_ref()
+                        .map(|path| path.to_string_lossy().into_owned()),
+                ),
+                &self.session.source,
+            ),
+            (self.strict.value.to_string(), &self.strict.source),
+        ];
+
+        let width = rows
+            .iter()
+            .map(|(value, _)| value.len())
+            .max()
+            .unwrap_or_default();
+
+        SETTINGS
+            .iter()
+            .zip(rows)
+            .map(|((key, _), (value, source))| {
+                format!(
+                    "{:<7} = {:<width$}  # {}\n",
+                    key,
+                    value,
+                    source,
+                    width = width
+                )
+            })
+            .collect()
+    }
+}
+
+/// the user's config directory for aoc, $XDG_CONFIG_HOME/aoc or ~/.config/aoc
+fn config_dir() -> Option<PathBuf> {
+    env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".config")))
+        .map(|dir| dir.join("aoc"))
+}
+
+/// the line up to a # that isn't inside a string
+fn strip_comment(line: &str) -> &str {
+    let mut quoted = false;
+    let mut escaped = false;
+
+    for (idx, ch) in line.char_indices() {
+        match ch {
+            _ if escaped => escaped = false,
+            '\\' if quoted => escaped = true,
+            '"' => quoted = !quoted,
+            '#' if !quoted => return &line[..idx],
+            _ => {}
+        }
+    }
+
+    line
+}
+
+/// a toml value as text: a basic string without its quotes and escapes, anything else as is
+fn unquote(value: &str) -> Result<String, String> {
+    let inner = match value.strip_prefix('"') {
+        Some(rest) => rest
+            .strip_suffix('"')
+            .filter(|_| value.len() > 1)
+            .ok_or(format!("unterminated string {}", value))?,
+        None if value.is_empty() => return Err(String::from("missing value")),
+        None => return Ok(value.to_string()),
+    };
+
+    let mut result = String::new();
+    let mut chars = inner.chars();
+
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\\' => match chars.next() {
+                Some('"') => result.push('"'),
+                Some('\\') => result.push('\\'),
+                Some('n') => result.push('\n'),
+                Some('t') => result.push('\t'),
+                other => return Err(format!("unsupported escape \\{}", other.unwrap_or(' '))),
+            },
+            '"' => return Err(format!("unexpected quote in {}", value)),
+            ch => result.push(ch),
+        }
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge() {
+        let mut config = Config::default();
+        let path = Path::new("aoc.toml");
+
+        config
+            .merge(
+                "# settings for this checkout\n\
+                 year = 2021\n\
+                 \n\
+                 profile = \"alice\"  # not mine\n\
+                 inputs = \"puzzles/#1\"\n\
+                 format = \"json\"\n",
+                path,
+            )
+            .unwrap();
+
+        assert_eq!(Some(String::from("alice")), config.profile.value);
+        assert_eq!(
+            Source::File(PathBuf::from("aoc.toml")),
+            config.profile.source
+        );
+        assert_eq!(PathBuf::from("puzzles/#1"), config.inputs.value);
+        assert_eq!(Format::Json, config.format.value);
+        assert_eq!(Source::Default, config.timeout.source);
+        assert_eq!(
+            PathBuf::from("puzzles/#1/2021/alice/input-4"),
+            config.input_path(4)
+        );
+
+        assert_eq!(
+            Err(ParseError::new(
+                1,
+                "unknown setting \"colour\" (try year, inputs, profile, format, timeout, session, strict)"
+            )),
+            config.merge("\ncolour = \"red\"", path)
+        );
+        assert_eq!(
+            Err(ParseError::new(
+                0,
+                "expected a number of seconds, got \"soon\""
+            )),
+            config.merge("timeout = \"soon\"", path)
+        );
+        assert_eq!(
+            Err(ParseError::new(0, "unterminated string \"alice")),
+            config.merge("profile = \"alice", path)
+        );
+        assert_eq!(
+            Err(ParseError::new(0, "expected key = value")),
+            config.merge("[runner]", path)
+        );
+    }
+
+    #[test]
+    fn test_precedence() {
+        let repo = env::temp_dir().join(format!("aoc-config-{}.toml", std::process::id()));
+        std::fs::write(&repo, "year = 2022\ntimeout = 30\nprofile = \"bob\"\n").unwrap();
+        let missing = PathBuf::from("no/such/aoc.toml");
+
+        let mut config = Config::load_from([&missing, &repo], |var| match var {
+            "AOC_TIMEOUT" => Some(String::from("60")),
+            "AOC_PROFILE" => Some(String::new()),
+            _ => None,
+        })
+        .unwrap();
+        std::fs::remove_file(&repo).unwrap();
+
+        assert_eq!(
+            Setting {
+                value: 2022,
+                source: Source::File(repo.clone())
+            },
+            config.year
+        );
+        assert_eq!(
+            Setting {
+                value: 60,
+                source: Source::Env("AOC_TIMEOUT")
+            },
+            config.timeout
+        );
+        assert_eq!(None, config.profile.value);
+
+        config.year.flag(None);
+        assert_eq!(Source::File(repo), config.year.source);
+        config.year.flag(Some(2021));
+        assert_eq!(
+            Setting {
+                value: 2021,
+                source: Source::Flag
+            },
+            config.year
+        );
+
+        assert_eq!(
+            Err(String::from(
+                "$AOC_FORMAT: unknown format \"yaml\" (try text or json)"
+            )),
+            Config::load_from([], |var| (var == "AOC_FORMAT")
+                .then(|| String::from("yaml")))
+        );
+    }
+}
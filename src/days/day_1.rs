@@ -0,0 +1,128 @@
+use crate::error::{parse_value, ParseError};
+use crate::rng::Rng;
+use crate::stream::solve_lines;
+use crate::validate::{ensure_valid, number};
+use std::io::BufRead;
+
+/// a line's depth measurement
+fn depth(idx: usize, line: &str) -> Result<i32, ParseError> {
+    number::<i32>(line, idx, 0)
+}
+
+/// every depth needs to be a number
+pub fn validate(input: &str) -> Vec<ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .filter_map(|(idx, line)| depth(idx, line).err())
+        .collect()
+}
+
+pub(crate) fn parse(input: &str) -> Result<Vec<i32>, ParseError> {
+    ensure_valid(validate(input))?;
+
+    input
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| parse_value(line, idx))
+        .collect()
+}
+
+/// how many values are bigger than the one before; the first counts as an increase on nothing
+fn count_increases<T: Copy + Default + PartialOrd>(values: impl Iterator<Item = T>) -> i32 {
+    let mut counter = -1;
+    let mut last_value = T::default();
+
+    for value in values {
+        if value > last_value {
+            counter += 1;
+        }
+        last_value = value;
+    }
+
+    counter
+}
+
+/// sums of each three measurement window, without needing every measurement at once; in an i64,
+/// since three depths can add up to more than an i32 holds
+fn window_sums(values: impl Iterator<Item = i32>) -> impl Iterator<Item = i64> {
+    let mut window = [0; 3];
+
+    values.enumerate().filter_map(move |(idx, value)| {
+        window.rotate_left(1);
+        window[2] = i64::from(value);
+        (idx >= 2).then(|| window.iter().sum())
+    })
+}
+
+pub fn part_one(input: &str) -> Result<i32, ParseError> {
+    Ok(count_increases(parse(input)?.into_iter()))
+}
+
+pub fn part_two(input: &str) -> Result<i32, ParseError> {
+    let numbers = parse(input)?;
+
+    Ok(count_increases(numbers.array_windows::<3>().map(
+        |window| window.iter().copied().map(i64::from).sum::<i64>(),
+    )))
+}
+
+/// part_one, a line at a time
+pub fn stream_part_one(reader: &mut dyn BufRead) -> Result<i32, ParseError> {
+    solve_lines(reader, depth, |depths| count_increases(depths))
+}
+
+/// part_two, a line at a time
+pub fn stream_part_two(reader: &mut dyn BufRead) -> Result<i32, ParseError> {
+    solve_lines(reader, depth, |depths| count_increases(window_sums(depths)))
+}
+
+/// `size` depth measurements, drifting deeper like the real input
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut depth = rng.range(100, 200);
+
+    (0..size.max(1))
+        .map(|_| {
+            depth = (depth + rng.range(-10, 20)).max(0);
+            depth.to_string()
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::property;
+
+    #[test]
+    fn test_wide_windows() {
+        // each window adds up to more than an i32 holds
+        let input = "16464\n1621474836\n1621474836\n1621474836";
+        assert_eq!(Ok(1), part_two(input));
+        assert_eq!(Ok(1), stream_part_two(&mut input.as_bytes()));
+    }
+
+    #[test]
+    fn test_stream() {
+        property::check(
+            "streaming the input gets the same answers as reading it all",
+            |rng| (rng.below(1000) as u64, rng.below(50)),
+            |(seed, size)| {
+                let input = generate(&mut Rng::new(*seed), *size);
+
+                let expected = (part_one(&input), part_two(&input));
+                let actual = (
+                    stream_part_one(&mut input.as_bytes()),
+                    stream_part_two(&mut input.as_bytes()),
+                );
+
+                if expected == actual {
+                    Ok(())
+                } else {
+                    Err(format!("read {:?}, streamed {:?}", expected, actual))
+                }
+            },
+        );
+    }
+}
@@ -0,0 +1,309 @@
+use crate::checked::Operation;
+use crate::error::ParseError;
+use crate::explain::{Event, Sink};
+use crate::rng::Rng;
+use crate::validate::{bad_chars, ensure_valid};
+use crate::Solver;
+use std::collections::HashMap;
+
+/// the first closer on the line with no chunk open for it to close
+fn unopened(idx: usize, line: &str) -> Option<ParseError> {
+    let mut depth = 0;
+
+    for (col_idx, ch) in line.chars().enumerate() {
+        match ch {
+            '(' | '[' | '{' | '<' => depth += 1,
+            ')' | ']' | '}' | '>' if depth == 0 => {
+                return Some(ParseError::at(
+                    idx,
+                    col_idx,
+                    format!("{:?} closes a chunk that was never opened", ch),
+                ))
+            }
+            ')' | ']' | '}' | '>' => depth -= 1,
+            _ => {}
+        }
+    }
+
+    None
+}
+
+/// lines can only be made of brackets, and can't close more chunks than they open
+pub fn validate(input: &str) -> Vec<ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .flat_map(|(idx, line)| {
+            match bad_chars(line, idx, |ch| "()[]{}<>".contains(ch), "a bracket") {
+                errors if errors.is_empty() => unopened(idx, line).into_iter().collect(),
+                errors => errors,
+            }
+        })
+        .collect()
+}
+
+/// lines of chunks, made up of nothing but brackets
+pub(crate) fn parse(input: &str) -> Result<Vec<&str>, ParseError> {
+    ensure_valid(validate(input))?;
+
+    Ok(input.lines().collect())
+}
+
+pub fn part_one(input: &str) -> Result<i32, ParseError> {
+    syntax_score(input, &mut ())
+}
+
+pub fn part_two(input: &str) -> Result<usize, ParseError> {
+    completion_score(input, &mut ())
+}
+
+/// the closer that matches an opener
+fn closer(opener: char) -> char {
+    PAIRS
+        .iter()
+        .find(|(open, _)| *open == opener)
+        .map_or(opener, |(_, close)| *close)
+}
+
+/// score of every mismatched closer, explaining where each one is and what it should have been
+pub(crate) fn syntax_score(input: &str, sink: &mut dyn Sink) -> Result<i32, ParseError> {
+    let mut stack = Vec::new();
+    let lookup = HashMap::from([(')', '('), (']', '['), ('>', '<'), ('}', '{')]);
+
+    let sum = Operation::new(10, Solver::PartOne, "syntax error score");
+    let mut score = 0;
+    let scores = HashMap::from([(')', 3), (']', 57), ('>', 25137), ('}', 1197)]);
+
+    for (idx, line) in parse(input)?.into_iter().enumerate() {
+        for (col, ch) in line.chars().enumerate() {
+            match ch {
+                '(' | '[' | '{' | '<' => {
+                    stack.push(ch);
+                }
+                ')' | ']' | '}' | '>' => {
+                    let opener = stack.pop().unwrap();
+                    let expected = lookup.get(&ch).unwrap();
+                    if opener != *expected {
+                        score = sum.add(score, *scores.get(&ch).unwrap())?;
+                        sink.emit(
+                            Event::new("corrupted")
+                                .with("line", idx + 1)
+                                .with("column", col + 1)
+                                .with("expected", closer(opener).to_string())
+                                .with("found", ch.to_string())
+                                .with("score", *scores.get(&ch).unwrap()),
+                        );
+                    }
+                }
+                _ => unreachable!(),
+            }
+        }
+    }
+
+    Ok(score)
+}
+
+fn score_part_two(closers: &[char]) -> usize {
+    let scores = HashMap::from([('(', 1), ('[', 2), ('<', 4), ('{', 3)]);
+
+    closers.iter().fold(0, |mut acc, ch| {
+        acc = acc * 5 + scores.get(ch).unwrap();
+        acc
+    })
+}
+
+/// middle score of the incomplete lines' completions, explaining each completion
+pub(crate) fn completion_score(input: &str, sink: &mut dyn Sink) -> Result<usize, ParseError> {
+    let mut scores = Vec::new();
+    let lookup = HashMap::from([
+        (')', '('),
+        (']', '['),
+        ('>', '<'),
+        ('}', '{'),
+        ('(', ')'),
+        ('[', ']'),
+        ('<', '>'),
+        ('{', '}'),
+    ]);
+
+    'outer: for (idx, line) in parse(input)?.into_iter().enumerate() {
+        // fresh stack per line this time, as the remainder will be used to determine the answer
+        let mut stack = Vec::new();
+
+        for ch in line.chars() {
+            match ch {
+                '(' | '[' | '{' | '<' => {
+                    stack.push(ch);
+                }
+                ')' | ']' | '}' | '>' => {
+                    let opener = stack.pop().unwrap();
+                    let expected = lookup.get(&ch).unwrap();
+                    if opener != *expected {
+                        // malformed lines aren't considered for part two, just go to next line
+                        continue 'outer;
+                    }
+                }
+                _ => unreachable!(),
+            }
+        }
+
+        // in reaching the end of the character iterator, we have an incomplete line (malformed are
+        // discarded), so we can score it for later
+
+        stack.reverse(); // score needs to be computed in the reverse order of the stack
+        let score = score_part_two(&stack);
+        sink.emit(
+            Event::new("incomplete")
+                .with("line", idx + 1)
+                .with(
+                    "completion",
+                    stack.iter().map(|ch| closer(*ch)).collect::<String>(),
+                )
+                .with("score", score),
+        );
+        scores.push(score);
+    }
+
+    if scores.is_empty() {
+        return Err(ParseError::overall(
+            "no incomplete lines to take the middle score of",
+        ));
+    }
+
+    scores.sort_unstable();
+    let middle = scores[scores.len() / 2];
+    sink.emit(
+        Event::new("middle")
+            .with("of", scores.len())
+            .with("score", middle),
+    );
+    Ok(middle)
+}
+
+const PAIRS: [(char, char); 4] = [('(', ')'), ('[', ']'), ('{', '}'), ('<', '>')];
+
+/// random chunks that are either left open or corrupted by a single mismatched closer
+fn generate_line(rng: &mut Rng, corrupted: bool) -> String {
+    let length = rng.range(8, 40);
+    let corrupt_at = rng.range(1, length - 1);
+
+    let mut line = String::new();
+    let mut stack = Vec::new();
+
+    for idx in 0..length {
+        if corrupted && idx == corrupt_at && !stack.is_empty() {
+            let expected = stack.pop().unwrap();
+            let wrong = loop {
+                let (_, closer) = *rng.choose(&PAIRS);
+                if closer != expected {
+                    break closer;
+                }
+            };
+            line.push(wrong);
+        } else if stack.is_empty() || (stack.len() < 20 && rng.chance(55)) {
+            let (opener, closer) = *rng.choose(&PAIRS);
+            line.push(opener);
+            stack.push(closer);
+        } else {
+            line.push(stack.pop().unwrap());
+        }
+    }
+
+    if stack.is_empty() {
+        // complete lines aren't part of the puzzle
+        let (opener, _) = *rng.choose(&PAIRS);
+        line.push(opener);
+    }
+
+    line
+}
+
+/// `size` lines, an odd number of them incomplete so part two has a middle score
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+
+    let mut incomplete = rng.range(1, size as i64) as usize;
+    if incomplete.is_multiple_of(2) {
+        incomplete -= 1;
+    }
+
+    let mut corrupted = vec![false; incomplete];
+    corrupted.resize(size, true);
+    rng.shuffle(&mut corrupted);
+
+    corrupted
+        .iter()
+        .map(|corrupted| generate_line(rng, *corrupted))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+pub const EXAMPLE: &str = "[({(<(())[]>[[{[]{<()<>>
+[(()[<>])]({[<{<<[]>>(
+{([(<{}[<>[]}>{[]{[(<()>
+(((({<>}<{<{<>}{[]{[]{}
+[[<[([]))<([[{}[[()]]]
+[{[{({}]{}}([{[{{{}}([]
+{<[[]]>}<{[{[{[]{()[[[]
+[<(<(<(<{}))><([]([]()
+<{([([[(<>()){}]>(<<{{
+<{([{{}}[<[[[<>{}]]]>[]]";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_one() {
+        assert_eq!(Ok(26397), part_one(EXAMPLE));
+    }
+
+    #[test]
+    fn test_two() {
+        assert_eq!(Ok(288957), part_two(EXAMPLE));
+    }
+
+    #[test]
+    fn test_explain() {
+        let mut events = Vec::new();
+        assert_eq!(Ok(26397), syntax_score(EXAMPLE, &mut events));
+        assert_eq!(
+            vec![
+                "corrupted: line=3 column=13 expected=] found=} score=1197",
+                "corrupted: line=5 column=9 expected=] found=) score=3",
+                "corrupted: line=6 column=8 expected=) found=] score=57",
+                "corrupted: line=8 column=11 expected=> found=) score=3",
+                "corrupted: line=9 column=17 expected=] found=> score=25137",
+            ],
+            events.iter().map(Event::text).collect::<Vec<String>>()
+        );
+
+        let mut events = Vec::new();
+        assert_eq!(Ok(288957), completion_score(EXAMPLE, &mut events));
+        assert_eq!(6, events.len());
+        assert_eq!(
+            "incomplete: line=1 completion=}}]])})] score=288957",
+            events[0].text()
+        );
+        assert_eq!("middle: of=5 score=288957", events[5].text());
+    }
+
+    #[test]
+    fn test_incomplete() {
+        let error = Err(ParseError::overall(
+            "no incomplete lines to take the middle score of",
+        ));
+        assert_eq!(error, part_two(""));
+        assert_eq!(error, part_two("(]"));
+        assert_eq!(Ok(0), part_one(""));
+
+        assert_eq!(
+            vec![ParseError::at(
+                1,
+                2,
+                "']' closes a chunk that was never opened"
+            )],
+            validate("(]\n()]")
+        );
+        assert!(part_one(")").is_err());
+    }
+}
@@ -0,0 +1,386 @@
+use crate::error::ParseError;
+use crate::rng::Rng;
+use crate::simulation::Simulation;
+use crate::validate::{bad_chars, ensure_valid, rectangular};
+use crate::visualize::{Frame, Visualize};
+use crate::Solver;
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+
+/// energy levels need to be digits, laid out in a rectangle
+pub fn validate(input: &str) -> Vec<ParseError> {
+    let mut errors = rectangular(input);
+
+    for (idx, line) in input.lines().enumerate() {
+        errors.extend(bad_chars(
+            line,
+            idx,
+            |ch| ch.is_ascii_digit(),
+            "an energy level",
+        ));
+    }
+
+    errors
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct Cave {
+    octopuses: Vec<Vec<Octopus>>,
+    flashes: i32,
+    synced: bool,
+}
+
+impl Cave {
+    pub(crate) fn new(input: &str) -> Result<Self, ParseError> {
+        ensure_valid(validate(input))?;
+
+        let mut rows = Vec::new();
+
+        for line in input.lines() {
+            let mut row = Vec::new();
+            for ch in line.chars() {
+                row.push(Octopus::new(ch.to_digit(10).unwrap() as usize));
+            }
+            rows.push(row);
+        }
+
+        Ok(Self {
+            octopuses: rows,
+            ..Default::default()
+        })
+    }
+
+    /// every octopus's energy level, row by row
+    fn energies(&self) -> Vec<usize> {
+        self.octopuses
+            .iter()
+            .flatten()
+            .map(|octopus| octopus.energy)
+            .collect()
+    }
+
+    fn flash_neighbors(&mut self, row: i32, col: i32) {
+        let row_max = self.octopuses.len() as i32 - 1;
+        let col_max = self.octopuses[0].len() as i32 - 1;
+
+        // octopuses whose flash hasn't reached its neighbors yet; a stack rather than recursion,
+        // since a flash can set off the whole grid, which overflows the stack on big ones
+        let mut flashing = vec![(row, col)];
+
+        while let Some((row, col)) = flashing.pop() {
+            let row_start = if row > 0 { -1 } else { 0 };
+            let row_end = if row < row_max { 1 } else { 0 };
+
+            let col_start = if col > 0 { -1 } else { 0 };
+            let col_end = if col < col_max { 1 } else { 0 };
+
+            // for each neighbor, increase their energy by one
+            for x in row_start..=row_end {
+                for y in col_start..=col_end {
+                    if x != 0 || y != 0 {
+                        let x_idx = (row + x) as usize;
+                        let y_idx = (col + y) as usize;
+
+                        let neighbor = &mut self.octopuses[x_idx][y_idx];
+
+                        neighbor.energy += 1;
+
+                        if neighbor.energy > 9 && !neighbor.flashed {
+                            // neighbor exceeded 9 and hasn't flashed during this step
+                            neighbor.flashed = true;
+                            self.flashes += 1;
+                            flashing.push((row + x, col + y));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn step(&mut self, count: usize) {
+        (0..count).for_each(|_| {
+            // First, the energy level of each octopus increases by 1.
+            for row in self.octopuses.iter_mut() {
+                for octopus in row.iter_mut() {
+                    octopus.energy += 1;
+                }
+            }
+
+            // Then, any octopus with an energy level greater than 9 flashes.
+            for i in 0..self.octopuses.len() {
+                for ii in 0..self.octopuses[0].len() {
+                    if self.octopuses[i][ii].energy > 9 && !self.octopuses[i][ii].flashed {
+                        // set current octopus status to 'has flashed'
+                        self.octopuses[i][ii].flashed = true;
+                        self.flashes += 1;
+                        self.flash_neighbors(i as i32, ii as i32);
+                    }
+                }
+            }
+
+            let mut synced = true;
+
+            // Finally, any octopus that flashed during this step has its energy level set to 0
+            for row in self.octopuses.iter_mut() {
+                for octopus in row.iter_mut() {
+                    if !octopus.flashed {
+                        synced = false;
+                    }
+                    if octopus.flashed {
+                        octopus.energy = 0;
+                        octopus.flashed = false;
+                    }
+                }
+            }
+
+            if synced {
+                self.synced = true;
+            }
+        });
+    }
+}
+
+impl Display for Cave {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut result = String::new();
+
+        for row in &self.octopuses {
+            for octopus in row {
+                result.push_str(&format!("{}", octopus.energy));
+            }
+            result.push('\n');
+        }
+
+        write!(f, "{}", result)
+    }
+}
+
+impl Visualize for Cave {
+    /// a frame per step, 100 of them for part one or until every octopus flashes at once for
+    /// part two; octopuses that just flashed are the 0s
+    fn frames(&mut self, solver: Solver) -> Vec<Frame> {
+        let mut frames = vec![Frame::new("before any steps", self)];
+        let mut steps = 0;
+
+        loop {
+            let done = match solver {
+                Solver::PartOne => steps == 100,
+                Solver::PartTwo => self.synced,
+            };
+            if done {
+                return frames;
+            }
+
+            self.step(1);
+            steps += 1;
+
+            frames.push(Frame::new(
+                format!("after step {}: {} flashes", steps, self.flashes),
+                self,
+            ));
+        }
+    }
+}
+
+impl Simulation for Cave {
+    /// a step of every octopus gaining energy and flashing
+    fn step(&mut self) -> bool {
+        Cave::step(self, 1);
+        true
+    }
+
+    fn show(&self) -> String {
+        self.to_string()
+    }
+
+    /// energy of the octopus in column `x` of row `y`, counting from 0
+    fn inspect(&self, x: usize, y: usize) -> Option<String> {
+        let octopus = self.octopuses.get(y)?.get(x)?;
+        Some(format!("energy {}", octopus.energy))
+    }
+
+    /// synced is 1 once every octopus has flashed at once
+    fn measures(&self) -> Vec<(&'static str, i64)> {
+        vec![
+            ("flashes", self.flashes as i64),
+            ("synced", self.synced as i64),
+        ]
+    }
+}
+
+#[derive(Debug, Default)]
+struct Octopus {
+    flashed: bool,
+    energy: usize,
+}
+
+impl Octopus {
+    fn new(energy: usize) -> Self {
+        Self {
+            energy,
+            ..Default::default()
+        }
+    }
+}
+
+pub fn part_one(input: &str) -> Result<i32, ParseError> {
+    let mut cave = Cave::new(input)?;
+
+    cave.step(100);
+    Ok(cave.flashes)
+}
+
+pub fn part_two(input: &str) -> Result<i32, ParseError> {
+    let mut cave = Cave::new(input)?;
+    let mut index = 0;
+
+    // the cave only depends on its energy levels, so seeing them twice means it's going round in
+    // circles that never sync up
+    let mut seen = HashSet::new();
+
+    loop {
+        cave.step(1);
+
+        index += 1;
+
+        if cave.synced {
+            return Ok(index);
+        }
+
+        if !seen.insert(cave.energies()) {
+            return Err(ParseError::overall(format!(
+                "after {} steps the octopuses are repeating themselves without ever all \
+                 flashing at once",
+                index
+            )));
+        }
+    }
+}
+
+/// grids wider than this aren't checked by simulating them, which gets slow
+const SIMULATED_SIZE: usize = 100;
+
+/// `size` x `size` grid of energy levels that eventually all flash at once, which part two relies
+/// on to finish
+///
+/// plenty of random grids never synchronize, large ones especially, so after every few failed
+/// attempts the range of starting energy levels is narrowed, which all but guarantees it. grids
+/// wider than `SIMULATED_SIZE` skip straight to two neighbouring levels: the first octopus to
+/// flash sets off every one at the lower level too, so they all flash at once within ten steps
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    let mut attempt = 0;
+
+    let grid = |rng: &mut Rng, lowest: i64, highest: i64| {
+        (0..size)
+            .map(|_| {
+                (0..size)
+                    .map(|_| rng.range(lowest, highest).to_string())
+                    .collect::<String>()
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    };
+
+    if size > SIMULATED_SIZE {
+        let lowest = rng.range(0, 8);
+        return grid(rng, lowest, lowest + 1);
+    }
+
+    loop {
+        let highest = 9 - (attempt / 4).min(7);
+        attempt += 1;
+
+        let grid = grid(rng, 0, highest);
+        let mut cave = Cave::new(&grid).unwrap();
+
+        for _ in 0..1000 {
+            cave.step(1);
+
+            if cave.synced {
+                return grid;
+            }
+        }
+    }
+}
+
+pub const EXAMPLE: &str = "5483143223
+2745854711
+5264556173
+6141336146
+6357385478
+4167524645
+2176841721
+6882881134
+4846848554
+5283751526";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snapshot;
+    #[test]
+    fn test_one() {
+        assert_eq!(Ok(1656), part_one(EXAMPLE));
+    }
+
+    #[test]
+    fn test_two() {
+        assert_eq!(Ok(195), part_two(EXAMPLE));
+    }
+
+    #[test]
+    fn test_never_synced() {
+        assert_eq!(
+            Err(ParseError::overall(
+                "after 10 steps the octopuses are repeating themselves without ever all flashing \
+                 at once"
+            )),
+            part_two("0\n5")
+        );
+    }
+
+    #[test]
+    fn test_frames() {
+        let frames = Cave::new(EXAMPLE).unwrap().frames(Solver::PartOne);
+        assert_eq!(101, frames.len());
+        assert_eq!(format!("{}\n", EXAMPLE), frames[0].picture);
+        assert_eq!("after step 100: 1656 flashes", frames[100].caption);
+
+        let frames = Cave::new(EXAMPLE).unwrap().frames(Solver::PartTwo);
+        assert_eq!(196, frames.len());
+        assert!(frames[195].picture.lines().all(|line| line == "0000000000"));
+    }
+    #[test]
+    fn test_simulation() {
+        let mut cave = Cave::new(EXAMPLE).unwrap();
+        for _ in 0..10 {
+            assert!(Simulation::step(&mut cave));
+        }
+
+        assert_eq!(vec![("flashes", 204), ("synced", 0)], cave.measures());
+        assert_eq!(Some(String::from("energy 0")), cave.inspect(0, 0));
+        assert_eq!(Some(String::from("energy 6")), cave.inspect(9, 0));
+        assert_eq!(None, cave.inspect(10, 0));
+    }
+    #[test]
+    fn test_display() {
+        let mut cave = Cave::new(EXAMPLE).unwrap();
+        let mut rendered = String::new();
+
+        for step in 0..=3 {
+            if step > 0 {
+                cave.step(1);
+            }
+            rendered.push_str(&format!("after step {}:\n{}\n", step, cave));
+        }
+
+        snapshot::check("day_11_cave", &rendered);
+    }
+    #[test]
+    fn test_generate_large() {
+        let input = generate(&mut Rng::new(2021), SIMULATED_SIZE + 1);
+        assert_eq!(SIMULATED_SIZE + 1, input.lines().count());
+        assert!(part_two(&input).unwrap() <= 10);
+    }
+}
@@ -0,0 +1,396 @@
+use crate::checked::Operation;
+use crate::error::ParseError;
+use crate::rng::Rng;
+use crate::validate::{ensure_valid, fields};
+use crate::Solver;
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Eq, Hash, PartialEq)]
+enum Cave {
+    Small(String),
+    Big(String),
+    Start,
+    End,
+}
+
+impl FromStr for Cave {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "start" => Ok(Cave::Start),
+            "end" => Ok(Cave::End),
+            _ => {
+                if s.is_empty() || !s.chars().all(|ch| ch.is_ascii_alphabetic()) {
+                    Err(format!("{:?} isn't a cave name", s))
+                } else if s.chars().all(|ch| ch.is_lowercase()) {
+                    Ok(Cave::Small(s.to_string()))
+                } else {
+                    Ok(Cave::Big(s.to_string()))
+                }
+            }
+        }
+    }
+}
+
+/// every line needs to connect two caves, like `A-b`, without connecting two big caves, and both
+/// start and end need to show up somewhere
+pub fn validate(input: &str) -> Vec<ParseError> {
+    let mut errors = Vec::new();
+    let mut seen = Vec::new();
+
+    for (idx, line) in input.lines().enumerate() {
+        let names = fields(line, "-");
+        if names.len() != 2 {
+            errors.push(ParseError::at(idx, 0, "expected a connection like `A-b`"));
+            continue;
+        }
+
+        let mut caves = Vec::new();
+        for (col_idx, name) in names {
+            match Cave::from_str(name) {
+                Ok(cave) => caves.push(cave),
+                Err(e) => errors.push(ParseError::at(idx, col_idx, e)),
+            }
+        }
+
+        if caves.len() == 2 && matches!((&caves[0], &caves[1]), (Cave::Big(_), Cave::Big(_))) {
+            // two connected big caves could be bounced between forever
+            errors.push(ParseError::at(
+                idx,
+                0,
+                "big caves can't connect to each other",
+            ));
+        }
+
+        seen.extend(caves);
+    }
+
+    for cave in [Cave::Start, Cave::End] {
+        if !seen.contains(&cave) {
+            errors.push(ParseError::new(
+                input.lines().count(),
+                format!("missing {:?}", cave),
+            ));
+        }
+    }
+
+    errors
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct Caves {
+    map: HashMap<Cave, Vec<Cave>>,
+}
+
+impl FromStr for Caves {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ensure_valid(validate(s))?;
+
+        let mut caves = Caves::default();
+
+        for line in s.lines() {
+            let (key, value) = line.split_once('-').unwrap();
+
+            // convert from str to cave
+            let key = Cave::from_str(key).unwrap();
+            let value = Cave::from_str(value).unwrap();
+
+            // get the key's vec if it exists, otherwise, create a new vec and either way insert
+            // the value
+            caves
+                .map
+                .entry(key.clone())
+                .or_insert_with(Vec::new)
+                .push(value.clone());
+
+            caves.map.entry(value).or_insert_with(Vec::new).push(key);
+        }
+
+        Ok(caves)
+    }
+}
+
+impl Caves {
+    fn count_paths(&mut self, solver: Solver) -> usize {
+        let mut path = Vec::new();
+        let mut count = 0;
+
+        match solver {
+            Solver::PartOne => self.explore_one(Cave::Start, &mut path, &mut count),
+            Solver::PartTwo => self.explore_two(Cave::Start, &mut path, &mut count, false),
+        };
+
+        count
+    }
+
+    fn neighbors(&self, cave: &Cave) -> Vec<Cave> {
+        self.map.get(cave).unwrap().clone()
+    }
+
+    fn explore_two(
+        &mut self,
+        cave: Cave,
+        path: &mut Vec<Cave>,
+        count: &mut usize,
+        visited_twice: bool,
+    ) {
+        path.push(cave.clone()); // add current cave to path
+
+        // println!("[{:?}] {:?} ? {}", cave, path, visited_twice);
+
+        // for every neighbor, recursively explore paths available to that neighbor, incrementing
+        // count for every fully explored path
+        for neighbor in self.neighbors(&cave) {
+            if matches!(neighbor, Cave::Start) {
+                // don't revisit the start
+                continue;
+            }
+
+            if matches!(neighbor, Cave::End) {
+                // increment number of paths by 1 when we reach the end
+                #[cfg(test)]
+                println!("[END] - {:?}", path);
+                *count += 1;
+            }
+
+            if matches!(neighbor, Cave::Big(_)) {
+                // big caves can be re-visited, just yeet them when seen
+                self.explore_two(neighbor.clone(), path, count, visited_twice);
+            }
+
+            if matches!(neighbor, Cave::Small(_)) && (!visited_twice || !path.contains(&neighbor)) {
+                let two_visit_sentry = visited_twice || path.contains(&neighbor);
+                self.explore_two(neighbor, path, count, two_visit_sentry);
+            }
+        }
+
+        // remove current cave
+        path.pop();
+    }
+
+    fn explore_one(&mut self, cave: Cave, path: &mut Vec<Cave>, count: &mut usize) {
+        path.push(cave.clone()); // add current cave to path
+
+        // for every neighbor, recursively explore paths available to that neighbor, incrementing
+        // count for every fully explored path
+        for neighbor in self.neighbors(&cave) {
+            if matches!(neighbor, Cave::Start) {
+                // don't revisit the start
+                continue;
+            }
+
+            if matches!(neighbor, Cave::End) {
+                // increment number of paths by 1 when we reach the end
+                #[cfg(test)]
+                println!("[END] - {:?}", path);
+                *count += 1;
+            }
+
+            if matches!(neighbor, Cave::Big(_)) {
+                // big caves can be re-visited, just yeet them when seen
+                self.explore_one(neighbor.clone(), path, count);
+            }
+
+            if matches!(neighbor, Cave::Small(_)) && !path.contains(&neighbor) {
+                // small caves need to not already be in the path to be yote
+                self.explore_one(neighbor.clone(), path, count);
+            }
+        }
+
+        // remove current cave
+        path.pop();
+    }
+}
+
+pub fn part_one(input: &str) -> Result<i32, ParseError> {
+    let mut caves = Caves::from_str(input)?;
+    Operation::new(12, Solver::PartOne, "path count").narrow(caves.count_paths(Solver::PartOne))
+}
+
+pub fn part_two(input: &str) -> Result<i32, ParseError> {
+    let mut caves = Caves::from_str(input)?;
+    Operation::new(12, Solver::PartTwo, "path count").narrow(caves.count_paths(Solver::PartTwo))
+}
+
+/// name for the idx'th of `count` caves, uppercase for big caves; two letters like the real
+/// input, more once there are too many caves for two
+fn cave_name(idx: usize, count: usize, big: bool) -> String {
+    let base = if big { b'A' } else { b'a' };
+
+    let mut letters = 2;
+    while 26usize.pow(letters) < count {
+        letters += 1;
+    }
+
+    let name = (0..letters)
+        .rev()
+        .map(|place| base + (idx / 26usize.pow(place) % 26) as u8)
+        .collect::<Vec<u8>>();
+    String::from_utf8(name).unwrap()
+}
+
+/// add an edge between two caves, unless it'd be a loop or a duplicate
+fn connect<'a>(
+    edges: &mut Vec<(&'a str, &'a str)>,
+    seen: &mut HashSet<(&'a str, &'a str)>,
+    left: &'a (String, bool),
+    right: &'a (String, bool),
+) {
+    let (left, right) = (left.0.as_str(), right.0.as_str());
+
+    if left != right && !seen.contains(&(right, left)) && seen.insert((left, right)) {
+        edges.push((left, right));
+    }
+}
+
+/// connected cave system with `size` caves besides start and end, roughly a quarter of them big
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    generate_caves(rng, size, 25)
+}
+
+/// connected cave system with `size` caves besides start and end, `big_percent` of them big
+///
+/// big caves are never connected to each other, otherwise there'd be infinitely many paths
+pub fn generate_caves(rng: &mut Rng, size: usize, big_percent: usize) -> String {
+    let size = size.max(2);
+    let bigs = (size * big_percent.min(100) / 100).clamp(1, size - 1);
+
+    // names are picked from a space a few times bigger than needed, so they look random
+    let space = (size * 4).max(26 * 26);
+    let mut names = HashSet::new();
+    let mut caves = Vec::new();
+    while caves.len() < size {
+        let big = caves.len() < bigs;
+        let name = cave_name(rng.below(space), space, big);
+
+        if name != "start" && name != "end" && names.insert(name.clone()) {
+            caves.push((name, big));
+        }
+    }
+    rng.shuffle(&mut caves);
+
+    let start = (String::from("start"), false);
+    let end = (String::from("end"), false);
+
+    let mut edges = Vec::new();
+    let mut seen = HashSet::new();
+
+    // every cave hangs off start or a cave before it, which keeps the whole thing connected
+    for idx in 0..caves.len() {
+        // picking idx itself stands for start; a big cave might take a few tries to find a
+        // small one, falling back to start
+        let mut other = &start;
+        for _ in 0..10 {
+            let candidate = rng.below(idx + 1);
+            if candidate == idx {
+                break;
+            }
+            if !(caves[candidate].1 && caves[idx].1) {
+                other = &caves[candidate];
+                break;
+            }
+        }
+        connect(&mut edges, &mut seen, &caves[idx], other);
+    }
+
+    for _ in 0..rng.range(1, 2) {
+        let cave = rng.choose(&caves);
+        connect(&mut edges, &mut seen, cave, &end);
+    }
+
+    for _ in 0..size / 2 {
+        let (left, right) = (rng.choose(&caves), rng.choose(&caves));
+        if !(left.1 && right.1) {
+            connect(&mut edges, &mut seen, left, right);
+        }
+    }
+
+    rng.shuffle(&mut edges);
+
+    edges
+        .iter()
+        .map(|(left, right)| {
+            if rng.chance(50) {
+                format!("{}-{}", left, right)
+            } else {
+                format!("{}-{}", right, left)
+            }
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+pub const EXAMPLE: &str = "start-A
+start-b
+A-c
+A-b
+b-d
+A-end
+b-end";
+
+pub const EXAMPLE_1: &str = "dc-end
+HN-start
+start-kj
+dc-start
+dc-HN
+LN-dc
+HN-end
+kj-sa
+kj-HN
+kj-dc";
+
+pub const EXAMPLE_2: &str = "fs-end
+he-DX
+fs-he
+start-DX
+pj-DX
+end-zg
+zg-sl
+zg-pj
+pj-he
+RW-he
+fs-DX
+pj-RW
+zg-RW
+start-pj
+he-WI
+zg-he
+pj-fs
+start-RW";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_one() {
+        assert_eq!(Ok(10), part_one(EXAMPLE));
+        assert_eq!(Ok(19), part_one(EXAMPLE_1));
+        assert_eq!(Ok(226), part_one(EXAMPLE_2));
+    }
+
+    #[test]
+    fn test_two() {
+        // assert_eq!(Ok(36), part_two(EXAMPLE));
+        // assert_eq!(Ok(103), part_two(EXAMPLE));
+        // assert_eq!(Ok(3509), part_two(EXAMPLE));
+    }
+    #[test]
+    fn test_generate_caves() {
+        let input = generate_caves(&mut Rng::new(2021), 1000, 40);
+        assert!(validate(&input).is_empty());
+
+        let caves = input
+            .lines()
+            .flat_map(|line| line.split('-'))
+            .filter(|cave| !matches!(*cave, "start" | "end"))
+            .collect::<HashSet<&str>>();
+        let big = |cave: &str| cave.chars().all(|ch| ch.is_ascii_uppercase());
+
+        assert_eq!(1000, caves.len());
+        assert_eq!(400, caves.iter().filter(|cave| big(cave)).count());
+        assert!(input.lines().all(|line| !line.split('-').all(big)));
+    }
+}
@@ -0,0 +1,517 @@
+use crate::checked::Operation;
+use crate::error::{parse_value, ParseError};
+use crate::explain::{Event, Sink};
+use crate::rng::Rng;
+use crate::simulation::Simulation;
+use crate::validate::{ensure_valid, fields, number};
+use crate::visualize::{Frame, Visualize};
+use crate::Solver;
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+
+#[derive(Copy, Clone, Debug, Default, Hash, Eq, PartialEq)]
+pub(crate) struct Dot {
+    x: usize,
+    y: usize,
+}
+
+impl Dot {
+    fn new(x: usize, y: usize) -> Self {
+        Self { x, y }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Paper {
+    dots: HashSet<Dot>,
+}
+
+impl Display for Paper {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut result = String::new();
+
+        // get largest x/y values
+        let max_x = self.dots.iter().max_by_key(|dot| dot.x).unwrap();
+        let max_y = self.dots.iter().max_by_key(|dot| dot.y).unwrap();
+
+        for row in 0..=max_y.y {
+            for col in 0..=max_x.x {
+                match self.dots.get(&Dot::new(col, row)) {
+                    Some(_) => result.push('#'),
+                    None => result.push('.'),
+                }
+            }
+            result.push('\n');
+        }
+
+        write!(f, "{}", result)
+    }
+}
+
+impl Paper {
+    fn new(dots: HashSet<Dot>) -> Self {
+        Self { dots }
+    }
+
+    /// fold along each instruction, just the first for part one, explaining how many dots are
+    /// left after each
+    fn fold(&mut self, instructions: &[(&str, usize)], solver: Solver, sink: &mut dyn Sink) {
+        for &(axis, fold_line) in instructions {
+            // can't iter_mut a hashset, and can't do an immutable borrow while we alter the set
+            // from within the loop, so clone
+            for dot in self.dots.clone().iter() {
+                match axis {
+                    "x" => {
+                        if dot.x > fold_line {
+                            // somewhere to the right the fold line
+                            let distance_from_fold = dot.x - fold_line;
+
+                            self.dots.remove(dot);
+                            self.dots
+                                .insert(Dot::new(fold_line - distance_from_fold, dot.y));
+                        }
+                    }
+                    "y" => {
+                        if dot.y > fold_line {
+                            // somewhere below the fold line
+                            let distance_from_fold = dot.y - fold_line;
+
+                            self.dots.remove(dot);
+                            self.dots
+                                .insert(Dot::new(dot.x, fold_line - distance_from_fold));
+                        }
+                    }
+                    _ => unreachable!(),
+                }
+            }
+            sink.emit(
+                Event::new("fold")
+                    .with("axis", axis)
+                    .with("line", fold_line)
+                    .with("dots", self.dots.len()),
+            );
+            if matches!(solver, Solver::PartOne) {
+                break; // break out after first instruction
+            }
+        }
+    }
+}
+
+/// dots like `6,10`, a blank line, then folds like `fold along y=7`; there needs to be at least
+/// one of each, and no fold can put dots past the edge of the paper
+pub fn validate(input: &str) -> Vec<ParseError> {
+    let mut errors = Vec::new();
+    let mut lines = input.lines().enumerate();
+
+    // where the dots end up along each axis as folds happen, used to make sure folds stay on the
+    // paper; axes fold independently, so there's no need to track whole dots
+    let (mut xs, mut ys) = (Vec::new(), Vec::new());
+
+    for (idx, line) in lines.by_ref().take_while(|(_, line)| !line.is_empty()) {
+        let coordinates = fields(line, ",");
+        if coordinates.len() != 2 {
+            errors.push(ParseError::at(idx, 0, "expected a dot like `6,10`"));
+            continue;
+        }
+
+        match (
+            number::<usize>(coordinates[0].1, idx, coordinates[0].0),
+            number::<usize>(coordinates[1].1, idx, coordinates[1].0),
+        ) {
+            (Ok(x), Ok(y)) => {
+                xs.push(x);
+                ys.push(y);
+            }
+            (x, y) => errors.extend(x.err().into_iter().chain(y.err())),
+        }
+    }
+
+    if xs.is_empty() && errors.is_empty() {
+        errors.push(ParseError::new(0, "expected at least one dot"));
+    }
+
+    let mut folds = 0;
+    for (idx, line) in lines {
+        folds += 1;
+
+        let instruction = match line.strip_prefix("fold along ") {
+            Some(instruction) => fields(instruction, "="),
+            None => Vec::new(),
+        };
+        if instruction.len() != 2 {
+            errors.push(ParseError::at(
+                idx,
+                0,
+                "expected a fold like `fold along y=7`",
+            ));
+            continue;
+        }
+
+        // columns are relative to the instruction, which starts after `fold along `
+        let (axis, fold_line) = (instruction[0].1, instruction[1]);
+        let fold_line = match number::<usize>(fold_line.1, idx, 11 + fold_line.0) {
+            Ok(fold_line) => fold_line,
+            Err(e) => {
+                errors.push(e);
+                continue;
+            }
+        };
+
+        let positions = match axis {
+            "x" => &mut xs,
+            "y" => &mut ys,
+            _ => {
+                errors.push(ParseError::at(idx, 11, format!("{:?} isn't an axis", axis)));
+                continue;
+            }
+        };
+
+        if positions
+            .iter()
+            .any(|position| *position > fold_line.saturating_mul(2))
+        {
+            errors.push(ParseError::at(
+                idx,
+                11,
+                format!("folding along {}={} goes past the edge", axis, fold_line),
+            ));
+            continue;
+        }
+
+        for position in positions.iter_mut() {
+            if *position > fold_line {
+                *position = fold_line - (*position - fold_line);
+            }
+        }
+    }
+
+    if folds == 0 {
+        errors.push(ParseError::new(
+            input.lines().count(),
+            "expected at least one fold",
+        ));
+    }
+
+    errors
+}
+
+/// axis and line for each fold, i.e. ("y", 7) for `fold along y=7`
+type Instructions<'a> = Vec<(&'a str, usize)>;
+
+pub(crate) fn parse(input: &str) -> Result<(HashSet<Dot>, Instructions<'_>), ParseError> {
+    ensure_valid(validate(input))?;
+
+    let mut lines = input.lines().enumerate();
+
+    let mut dots = HashSet::new();
+    for (idx, line) in lines.by_ref().take_while(|(_, line)| !line.is_empty()) {
+        let (x, y) = line.split_once(',').unwrap();
+        dots.insert(Dot::new(parse_value(x, idx)?, parse_value(y, idx)?));
+    }
+
+    let mut instructions = Vec::new();
+    for (idx, line) in lines {
+        let (axis, fold_line) = line[11..].split_once('=').unwrap();
+        instructions.push((axis, parse_value(fold_line, idx)?));
+    }
+
+    Ok((dots, instructions))
+}
+
+/// paper along with the folds to make, for watching it fold
+pub(crate) struct Origami<'a> {
+    paper: Paper,
+    instructions: Instructions<'a>,
+    /// how many of the instructions have been followed
+    folds: usize,
+}
+
+impl<'a> Origami<'a> {
+    pub(crate) fn new(input: &'a str) -> Result<Self, ParseError> {
+        let (dots, instructions) = parse(input)?;
+        Ok(Self {
+            paper: Paper::new(dots),
+            instructions,
+            folds: 0,
+        })
+    }
+
+    /// make the next fold, returning it; None once every fold's been made
+    fn fold(&mut self) -> Option<(&'a str, usize)> {
+        let instruction = *self.instructions.get(self.folds)?;
+        self.paper
+            .fold(std::slice::from_ref(&instruction), Solver::PartOne, &mut ());
+        self.folds += 1;
+
+        Some(instruction)
+    }
+}
+
+impl Visualize for Origami<'_> {
+    /// the paper before folding, then after each fold; just the first for part one
+    fn frames(&mut self, solver: Solver) -> Vec<Frame> {
+        let mut frames = vec![Frame::new(
+            format!("{} dots before folding", self.paper.dots.len()),
+            &self.paper,
+        )];
+
+        while let Some((axis, fold_line)) = self.fold() {
+            frames.push(Frame::new(
+                format!(
+                    "fold along {}={}: {} dots",
+                    axis,
+                    fold_line,
+                    self.paper.dots.len()
+                ),
+                &self.paper,
+            ));
+
+            if solver == Solver::PartOne {
+                break;
+            }
+        }
+
+        frames
+    }
+}
+
+impl Simulation for Origami<'_> {
+    /// makes the next fold
+    fn step(&mut self) -> bool {
+        self.fold().is_some()
+    }
+
+    fn show(&self) -> String {
+        self.paper.to_string()
+    }
+
+    /// whether there's a dot at `x`,`y`, counting from 0 like the input does
+    fn inspect(&self, x: usize, y: usize) -> Option<String> {
+        Some(match self.paper.dots.contains(&Dot::new(x, y)) {
+            true => format!("dot at {},{}", x, y),
+            false => format!("no dot at {},{}", x, y),
+        })
+    }
+
+    fn measures(&self) -> Vec<(&'static str, i64)> {
+        vec![
+            ("folds", self.folds as i64),
+            ("dots", self.paper.dots.len() as i64),
+        ]
+    }
+}
+
+pub fn part_one(input: &str) -> Result<i32, ParseError> {
+    fold_once(input, &mut ())
+}
+
+pub fn part_two(input: &str) -> Result<String, ParseError> {
+    fold_all(input, &mut ())
+}
+
+/// dots left after the first fold
+pub(crate) fn fold_once(input: &str, sink: &mut dyn Sink) -> Result<i32, ParseError> {
+    let (dots, instructions) = parse(input)?;
+
+    let mut paper = Paper::new(dots);
+    paper.fold(&instructions, Solver::PartOne, sink);
+
+    Operation::new(13, Solver::PartOne, "dot count").narrow(paper.dots.len())
+}
+
+/// the paper after every fold, which the code is read off; explaining gives it a row at a time
+pub(crate) fn fold_all(input: &str, sink: &mut dyn Sink) -> Result<String, ParseError> {
+    let (dots, instructions) = parse(input)?;
+
+    let mut paper = Paper::new(dots);
+    paper.fold(&instructions, Solver::PartTwo, sink);
+
+    let rendered = paper.to_string();
+    for (y, row) in rendered.lines().enumerate() {
+        sink.emit(Event::new("row").with("y", y).with("dots", row));
+    }
+
+    // printing the answer ends the last row
+    Ok(rendered.trim_end().to_string())
+}
+
+/// up to `size` dots followed by folds that always fold the paper exactly in half, with more folds
+/// the more dots there are
+///
+/// built backwards: dots start out on the folded paper and are randomly unfolded across each fold
+/// line, innermost first, so no dot ever lands on a fold line
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let (mut width, mut height) = (rng.range(5, 40) as usize, rng.range(6, 12) as usize);
+    let (mut x_count, mut y_count) = (rng.range(1, 4), rng.range(1, 4));
+
+    // each fold doubles the room for dots, so add folds until there's plenty for them all
+    while (width * height) << (x_count + y_count) < 2 * size {
+        if x_count <= y_count {
+            x_count += 1;
+        } else {
+            y_count += 1;
+        }
+    }
+
+    let mut x_folds = Vec::new();
+    for _ in 0..x_count {
+        x_folds.push(width);
+        width = 2 * width + 1;
+    }
+
+    let mut y_folds = Vec::new();
+    for _ in 0..y_count {
+        y_folds.push(height);
+        height = 2 * height + 1;
+    }
+
+    let mut dots = HashSet::new();
+    for _ in 0..size.max(1) {
+        let mut dot = Dot::new(rng.below(x_folds[0]), rng.below(y_folds[0]));
+
+        for fold in &x_folds {
+            if rng.chance(50) {
+                dot.x = 2 * fold - dot.x;
+            }
+        }
+        for fold in &y_folds {
+            if rng.chance(50) {
+                dot.y = 2 * fold - dot.y;
+            }
+        }
+
+        dots.insert(dot);
+    }
+
+    let mut result = dots
+        .iter()
+        .map(|dot| format!("{},{}", dot.x, dot.y))
+        .collect::<Vec<String>>()
+        .join("\n");
+    result.push('\n');
+
+    // outermost folds come first; interleave the two axes without reordering either one
+    while !x_folds.is_empty() || !y_folds.is_empty() {
+        let along_x = y_folds.is_empty() || (!x_folds.is_empty() && rng.chance(50));
+        let (axis, fold) = if along_x {
+            ("x", x_folds.pop().unwrap())
+        } else {
+            ("y", y_folds.pop().unwrap())
+        };
+        result.push_str(&format!("\nfold along {}={}", axis, fold));
+    }
+
+    result
+}
+
+pub const EXAMPLE: &str = "6,10
+0,14
+9,10
+0,3
+10,4
+4,11
+6,0
+6,12
+4,1
+0,13
+10,12
+3,4
+3,0
+8,4
+1,10
+2,14
+8,10
+9,0
+
+fold along y=7
+fold along x=5";
+
+/// the example's paper once it's been folded
+pub const SQUARE: &str = "#####
+#...#
+#...#
+#...#
+#####";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snapshot;
+    #[test]
+    fn test_one() {
+        assert_eq!(Ok(17), part_one(EXAMPLE));
+    }
+
+    #[test]
+    fn test_two() {
+        assert_eq!(Ok(SQUARE.to_string()), part_two(EXAMPLE));
+    }
+
+    #[test]
+    fn test_explain() {
+        let mut events = Vec::new();
+        assert_eq!(Ok(17), fold_once(EXAMPLE, &mut events));
+        assert_eq!(
+            vec!["fold: axis=y line=7 dots=17"],
+            events.iter().map(Event::text).collect::<Vec<String>>()
+        );
+
+        let mut events = Vec::new();
+        assert_eq!(Ok(SQUARE.to_string()), fold_all(EXAMPLE, &mut events));
+        assert_eq!(
+            vec![
+                "fold: axis=y line=7 dots=17",
+                "fold: axis=x line=5 dots=16",
+                "row: y=0 dots=#####",
+                "row: y=1 dots=#...#",
+                "row: y=2 dots=#...#",
+                "row: y=3 dots=#...#",
+                "row: y=4 dots=#####",
+            ],
+            events.iter().map(Event::text).collect::<Vec<String>>()
+        );
+    }
+
+    #[test]
+    fn test_frames() {
+        let frames = Origami::new(EXAMPLE).unwrap().frames(Solver::PartTwo);
+
+        assert_eq!(3, frames.len());
+        assert_eq!("18 dots before folding", frames[0].caption);
+        assert_eq!("fold along y=7: 17 dots", frames[1].caption);
+        assert_eq!("#####\n#...#\n#...#\n#...#\n#####\n", frames[2].picture);
+        assert_eq!(
+            2,
+            Origami::new(EXAMPLE).unwrap().frames(Solver::PartOne).len()
+        );
+    }
+    #[test]
+    fn test_simulation() {
+        let mut origami = Origami::new(EXAMPLE).unwrap();
+        assert!(origami.step());
+
+        assert_eq!(vec![("folds", 1), ("dots", 17)], origami.measures());
+        assert_eq!(Some(String::from("dot at 0,0")), origami.inspect(0, 0));
+        assert_eq!(Some(String::from("no dot at 1,0")), origami.inspect(1, 0));
+
+        assert!(origami.step());
+        assert!(!origami.step());
+        assert_eq!(vec![("folds", 2), ("dots", 16)], origami.measures());
+    }
+    #[test]
+    fn test_display() {
+        let (dots, instructions) = parse(EXAMPLE).unwrap();
+        let mut paper = Paper::new(dots);
+        let mut rendered = format!("before folding:\n{}", paper);
+
+        for instruction in &instructions {
+            paper.fold(std::slice::from_ref(instruction), Solver::PartOne, &mut ());
+            rendered.push_str(&format!(
+                "\nfold along {}={}:\n{}",
+                instruction.0, instruction.1, paper
+            ));
+        }
+
+        snapshot::check("day_13_paper", &rendered);
+    }
+}
@@ -0,0 +1,349 @@
+use crate::error::ParseError;
+use crate::rng::Rng;
+use crate::validate::{bad_chars, ensure_valid, fields};
+use regex::Regex;
+use std::collections::HashMap;
+
+/// problems with one side of a rule, which needs `len` uppercase letters
+fn check_elements(side: &str, len: usize, idx: usize, col_idx: usize) -> Vec<ParseError> {
+    let mut errors = bad_chars(side, idx, |ch| ch.is_ascii_uppercase(), "an element");
+
+    for error in errors.iter_mut() {
+        error.column = error.column.map(|column| column + col_idx);
+    }
+
+    if side.chars().count() != len {
+        errors.push(ParseError::at(
+            idx,
+            col_idx,
+            format!("expected {} element(s), found {:?}", len, side),
+        ));
+    }
+
+    errors
+}
+
+/// an uppercase template, a blank line, then rules like `CH -> B`; every pair of elements that
+/// could ever show up in the polymer needs a rule
+pub fn validate(input: &str) -> Vec<ParseError> {
+    let mut errors = Vec::new();
+    let mut lines = input.lines().enumerate();
+
+    let template = lines.next().map(|(_, line)| line).unwrap_or_default();
+    if template.is_empty() {
+        errors.push(ParseError::new(0, "expected a template like `NNCB`"));
+    }
+    errors.extend(bad_chars(
+        template,
+        0,
+        |ch| ch.is_ascii_uppercase(),
+        "an element",
+    ));
+
+    if !matches!(lines.next(), Some((_, ""))) {
+        errors.push(ParseError::new(
+            1,
+            "expected a blank line after the template",
+        ));
+    }
+
+    let mut rules = Vec::new();
+    for (idx, line) in lines {
+        let sides = fields(line, " -> ");
+        if sides.len() != 2 {
+            errors.push(ParseError::at(idx, 0, "expected a rule like `CH -> B`"));
+            continue;
+        }
+
+        let malformed = check_elements(sides[0].1, 2, idx, sides[0].0)
+            .into_iter()
+            .chain(check_elements(sides[1].1, 1, idx, sides[1].0))
+            .collect::<Vec<ParseError>>();
+
+        if malformed.is_empty() {
+            rules.push((sides[0].1, sides[1].1));
+        }
+        errors.extend(malformed);
+    }
+
+    if !errors.is_empty() {
+        // which pairs need rules isn't known until all the elements are
+        return errors;
+    }
+
+    let mut elements = template.chars().collect::<Vec<char>>();
+    elements.extend(rules.iter().flat_map(|(_, insert)| insert.chars()));
+    elements.sort_unstable();
+    elements.dedup();
+
+    for left in &elements {
+        for right in &elements {
+            let pair = format!("{}{}", left, right);
+            if !rules.iter().any(|(key, _)| *key == pair) {
+                errors.push(ParseError::new(
+                    input.lines().count(),
+                    format!("missing a rule for {}", pair),
+                ));
+            }
+        }
+    }
+
+    errors
+}
+
+/// polymer template and the pair insertion rules
+pub(crate) fn parse(input: &str) -> Result<(&str, HashMap<String, String>), ParseError> {
+    ensure_valid(validate(input))?;
+
+    let mut rules = HashMap::new();
+    let re = Regex::new(r"^([A-Z]{2}) -> ([A-Z])$").unwrap();
+
+    let mut lines = input.lines();
+    let template = lines.next().unwrap();
+
+    for line in lines.skip(1) {
+        let parsed = re.captures(line).unwrap();
+
+        let key = parsed[1].to_string();
+        let value = parsed[2].to_string();
+
+        rules.insert(key, value);
+    }
+
+    Ok((template, rules))
+}
+
+/// naive approach: build the entire polymer
+pub(crate) fn insert_elements(input: &str, steps: usize) -> Result<usize, ParseError> {
+    let (template, rules) = parse(input)?;
+
+    let mut polymer = template
+        .chars()
+        .map(|ch| ch.to_string())
+        .collect::<Vec<String>>();
+
+    let mut inserts = vec![];
+
+    for _step in 0..steps {
+        // for each pair of elements, determine what rule applies and track the point in the
+        // polymer it needs to be inserted
+        for (index, pair) in polymer.windows(2).enumerate() {
+            // go from NN to C or similar
+            let rule_key = format!("{}{}", pair[0], pair[1]);
+            let element = rules.get(&rule_key).unwrap();
+
+            // current offset + 1 + number of insertions that have already occurred gives us the
+            // offset at which we place the element into the new polymer
+            let insertion_point = index + inserts.len() + 1;
+            inserts.push((insertion_point, element));
+        }
+
+        // after building the insertions, perform them on the polymer and clear the inserts vec
+        // to be ready for the next step
+        for (idx, ele) in inserts.iter() {
+            polymer.insert(*idx, ele.to_string());
+        }
+        inserts.clear();
+
+        #[cfg(test)]
+        println!("[{}] polymer len: {}", _step, polymer.len());
+    }
+
+    let mut counts = HashMap::new();
+
+    for element in polymer {
+        *counts.entry(element).or_insert(0) += 1;
+    }
+
+    let mut to_sort: Vec<_> = counts.iter().collect();
+    to_sort.sort_by(|a, b| a.1.cmp(b.1));
+
+    let max = to_sort[to_sort.len() - 1];
+    let min = to_sort[0];
+
+    Ok(max.1 - min.1)
+}
+
+pub fn part_one(input: &str) -> Result<usize, ParseError> {
+    insert_elements(input, 10)
+}
+
+/// optimized approach: only track how many of each pair the polymer contains
+pub(crate) fn count_pairs(input: &str, steps: usize) -> Result<usize, ParseError> {
+    let (template, rules) = parse(input)?;
+
+    let polymer_input = template
+        .chars()
+        .map(|ch| ch.to_string())
+        .collect::<Vec<String>>();
+
+    // array to store # of times we've seen an element
+    // each element such as 'A' will be stored mapped to the array by subtracting 65 from the ascii
+    // value, i.e. 'A' - 65 == 0
+    let mut counts = [0_usize; 26];
+
+    let mut polymer = HashMap::new();
+
+    polymer_input.windows(2).for_each(|pair| {
+        // pairs from rules, window slides by 2, making it unsuitable for counting
+        let rule_key = format!("{}{}", pair[0], pair[1]);
+        *polymer.entry(rule_key).or_insert(0) += 1;
+    });
+
+    polymer_input.iter().for_each(|element| {
+        // initial count values
+        counts[element.chars().next().unwrap() as usize - 65] += 1;
+    });
+
+    for _step in 0..steps {
+        // for each pair of elements (polymer's keys), track how many new pairs each step produces
+        // by incrementing the value
+        let mut temp = polymer.clone();
+
+        for (key, value) in polymer.iter() {
+            // go from NN to C or similar
+            if let Some(insert) = rules.get(key) {
+                // when we see a key of BB and an insert of N (BB -> N)
+                // we need to insert BN and NB into the map. Additionally, we need to
+                // increment each one by BB's value (if we had 10 BB's we'll now have 10 BN's
+                // and 10 NB's)
+
+                let mut chars = key.chars();
+                let left_pair = format!("{}{}", chars.next().unwrap(), insert);
+                let right_pair = format!("{}{}", insert, chars.next().unwrap());
+
+                *temp.get_mut(key).unwrap() -= value;
+                *temp.entry(left_pair).or_insert(0) += value;
+                *temp.entry(right_pair).or_insert(0) += value;
+
+                counts[insert.chars().next().unwrap() as usize - 65] += value;
+            }
+        }
+
+        polymer = temp;
+        #[cfg(test)]
+        {
+            println!("[{}] polymer len: {:#?}", _step, polymer);
+            println!("counts: {:?}", counts);
+        }
+    }
+
+    let mut non_zeroes = counts
+        .into_iter()
+        .filter(|n| *n > 0)
+        .collect::<Vec<usize>>();
+
+    non_zeroes.sort_unstable();
+    Ok(non_zeroes[non_zeroes.len() - 1] - non_zeroes[0])
+}
+
+pub fn part_two(input: &str) -> Result<usize, ParseError> {
+    count_pairs(input, 40)
+}
+
+/// elements seen in real inputs
+const ELEMENTS: [char; 10] = ['B', 'C', 'F', 'H', 'K', 'N', 'O', 'P', 'S', 'V'];
+
+/// template followed by one insertion rule for every possible pair of `elements`; `rules[i]` is
+/// the element inserted between `elements[i / len]` and `elements[i % len]`
+fn render(elements: &[char], template: &[usize], rules: &[usize]) -> String {
+    let mut result = template.iter().map(|e| elements[*e]).collect::<String>();
+    result.push('\n');
+
+    for (idx, insert) in rules.iter().enumerate() {
+        let left = elements[idx / elements.len()];
+        let right = elements[idx % elements.len()];
+        result.push_str(&format!("\n{}{} -> {}", left, right, elements[*insert]));
+    }
+
+    result
+}
+
+/// polymer template of `size` elements, with a complete set of rules for the elements it uses
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let count = rng.range(2, ELEMENTS.len() as i64) as usize;
+    let template = (0..size.max(2))
+        .map(|_| rng.below(count))
+        .collect::<Vec<usize>>();
+    let rules = (0..count * count)
+        .map(|_| rng.below(count))
+        .collect::<Vec<usize>>();
+
+    render(&ELEMENTS[..count], &template, &rules)
+}
+
+pub const EXAMPLE: &str = "NNCB
+
+CH -> B
+HH -> N
+CB -> H
+NH -> C
+HB -> C
+HC -> B
+HN -> C
+NN -> C
+BH -> H
+NC -> B
+NB -> B
+BN -> B
+BB -> N
+BC -> B
+CC -> N
+CN -> C";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::property;
+
+    #[test]
+    fn test_one() {
+        assert_eq!(Ok(1588), part_one(EXAMPLE));
+    }
+
+    #[test]
+    fn test_two() {
+        assert_eq!(Ok(2188189693529), part_two(EXAMPLE));
+    }
+
+    #[test]
+    fn test_oracle() {
+        let elements = &ELEMENTS[..4];
+
+        property::check(
+            "counting pairs matches building the polymer",
+            |rng| {
+                let template = (0..rng.range(1, 8))
+                    .map(|_| rng.below(elements.len()))
+                    .collect::<Vec<usize>>();
+                let rules = (0..elements.len() * elements.len())
+                    .map(|_| rng.below(elements.len()))
+                    .collect::<Vec<usize>>();
+                (template, rules, rng.below(8))
+            },
+            |(template, rules, steps)| {
+                if template.is_empty() || rules.is_empty() {
+                    return Ok(());
+                }
+
+                // shrinking may drop rules, so cycle whatever is left to keep the set complete
+                let rules = rules
+                    .iter()
+                    .cycle()
+                    .take(elements.len() * elements.len())
+                    .copied()
+                    .collect::<Vec<usize>>();
+                let input = render(elements, template, &rules);
+
+                let expected = insert_elements(&input, *steps).map_err(|e| e.to_string())?;
+                let actual = count_pairs(&input, *steps).map_err(|e| e.to_string())?;
+
+                if expected == actual {
+                    Ok(())
+                } else {
+                    Err(format!("built {}, counted {}", expected, actual))
+                }
+            },
+        );
+    }
+}
@@ -0,0 +1,516 @@
+use crate::checked::Operation;
+use crate::error::ParseError;
+use crate::image::{ColorMap, Image};
+use crate::rng::Rng;
+use crate::validate::{bad_chars, ensure_valid, rectangular};
+use crate::visualize::{Frame, Visualize};
+use crate::Solver;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashSet};
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+struct State {
+    risk: usize,
+    position: (usize, usize),
+}
+
+// The priority queue depends on `Ord`.
+// Explicitly implement the trait so the queue becomes a min-heap
+// instead of a max-heap.
+impl Ord for State {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Notice that the we flip the ordering on costs.
+        // In case of a tie we compare positions - this step is necessary
+        // to make implementations of `PartialEq` and `Ord` consistent.
+        other
+            .risk
+            .cmp(&self.risk)
+            .then_with(|| self.position.cmp(&other.position))
+    }
+}
+
+// `PartialOrd` needs to be implemented as well.
+impl PartialOrd for State {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct Edge {
+    node: (usize, usize),
+    cost: usize,
+}
+
+impl Edge {
+    // fn get_cost(&self, map_len: usize, solver: Solver) -> usize {
+    //     match solver {
+    //         Solver::PartOne => self.cost,
+    //         Solver::PartTwo => {}
+    //     }
+    // }
+
+    fn neighbors(&self, map: &[Vec<Edge>]) -> Vec<Edge> {
+        let row_max = map.len() - 1;
+        let col_max = map[0].len() - 1;
+
+        let row_start: i32 = if self.node.0 > 0 { -1 } else { 0 };
+        let row_end: i32 = if self.node.0 < row_max { 1 } else { 0 };
+
+        let col_start: i32 = if self.node.1 > 0 { -1 } else { 0 };
+        let col_end: i32 = if self.node.1 < col_max { 1 } else { 0 };
+
+        let mut neighbors = Vec::new();
+
+        for x in row_start..=row_end {
+            for y in col_start..=col_end {
+                let center = x == 0 && y == 0;
+                let up_left = x == -1 && y == -1;
+                let up_right = x == -1 && y == 1;
+                let down_left = x == 1 && y == -1;
+                let down_right = x == 1 && y == 1;
+                if center || up_left || up_right || down_left || down_right {
+                    // skip current node and skip diagonals
+                    continue;
+                }
+                neighbors.push(
+                    map[(self.node.0 as i32 + x) as usize][(self.node.1 as i32 + y) as usize]
+                        .clone(),
+                );
+            }
+        }
+
+        neighbors
+    }
+}
+
+// Dijkstra's shortest path algorithm.
+
+// Start at `start` and use `dist` to track the current shortest distance
+// to each node. This implementation isn't memory-efficient as it may leave duplicate
+// nodes in the queue. It also uses `usize::MAX` as a sentinel value,
+// for a simpler implementation.
+fn shortest_path(
+    adj_list: &[Vec<Edge>],
+    start: (usize, usize),
+    goal: (usize, usize),
+) -> Option<usize> {
+    // dist[node] = current shortest distance from `start` to `node`
+    let mut dist: Vec<_> = (0..adj_list.len())
+        .map(|_| {
+            (0..adj_list[0].len())
+                .map(|_| usize::MAX)
+                .collect::<Vec<usize>>()
+        })
+        .collect();
+
+    let mut heap = BinaryHeap::new();
+
+    // We're at `start`, with a zero cost
+    dist[start.0][start.1] = 0;
+    heap.push(State {
+        risk: 0,
+        position: start,
+    });
+
+    // Examine the frontier with lower cost nodes first (min-heap)
+    while let Some(State { risk, position }) = heap.pop() {
+        // Alternatively we could have continued to find all shortest paths
+        if position == goal {
+            return Some(risk);
+        }
+
+        // Important as we may have already found a better way
+        if risk > dist[position.0][position.1] {
+            continue;
+        }
+
+        // For each node we can reach, see if we can find a way with
+        // a lower cost going through this node
+        for edge in &adj_list[position.0][position.1].neighbors(adj_list) {
+            let next = State {
+                risk: risk + edge.cost,
+                position: edge.node,
+            };
+
+            // If so, add it to the frontier and continue
+            if next.risk < dist[next.position.0][next.position.1] {
+                heap.push(next);
+                // Relaxation, we have now found a better way
+                dist[next.position.0][next.position.1] = next.risk;
+            }
+        }
+    }
+
+    // Goal not reachable
+    None
+}
+
+/// A* search: dijkstra, but always expanding the node that looks closest to the goal first
+///
+/// the manhattan distance to the goal never overestimates the remaining risk, since every step
+/// costs at least 1, so the first time the goal comes off the heap its risk is the lowest
+fn a_star(adj_list: &[Vec<Edge>], start: (usize, usize), goal: (usize, usize)) -> Option<usize> {
+    let estimate = |position: (usize, usize)| (goal.0 - position.0) + (goal.1 - position.1);
+
+    let mut dist: Vec<_> = (0..adj_list.len())
+        .map(|_| vec![usize::MAX; adj_list[0].len()])
+        .collect();
+
+    let mut heap = BinaryHeap::new();
+
+    // State's risk is the estimated total here, dist holds the actual risk so far
+    dist[start.0][start.1] = 0;
+    heap.push(State {
+        risk: estimate(start),
+        position: start,
+    });
+
+    while let Some(State { position, .. }) = heap.pop() {
+        let risk = dist[position.0][position.1];
+
+        if position == goal {
+            return Some(risk);
+        }
+
+        for edge in &adj_list[position.0][position.1].neighbors(adj_list) {
+            let next = risk + edge.cost;
+
+            if next < dist[edge.node.0][edge.node.1] {
+                dist[edge.node.0][edge.node.1] = next;
+                heap.push(State {
+                    risk: next + estimate(edge.node),
+                    position: edge.node,
+                });
+            }
+        }
+    }
+
+    None
+}
+
+/// signature shared by shortest_path and a_star
+type Search = fn(&[Vec<Edge>], (usize, usize), (usize, usize)) -> Option<usize>;
+
+/// lowest total risk from the top left to the bottom right of the map
+fn lowest_total_risk(map: &[Vec<Edge>], search: Search, solver: Solver) -> Result<i32, ParseError> {
+    let risk = search(map, (0, 0), (map.len() - 1, map[0].len() - 1)).unwrap();
+    Operation::new(15, solver, "total risk").narrow(risk)
+}
+
+/// risk levels need to be digits from 1 to 9, laid out in a rectangle
+pub fn validate(input: &str) -> Vec<ParseError> {
+    let mut errors = rectangular(input);
+
+    for (idx, line) in input.lines().enumerate() {
+        errors.extend(bad_chars(
+            line,
+            idx,
+            |ch| matches!(ch, '1'..='9'),
+            "a risk level",
+        ));
+    }
+
+    errors
+}
+
+pub(crate) fn parse(input: &str) -> Result<Vec<Vec<Edge>>, ParseError> {
+    ensure_valid(validate(input))?;
+
+    let map = input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            line.chars()
+                .enumerate()
+                .map(|(ii, ch)| Edge {
+                    node: (i, ii),
+                    cost: ch.to_digit(10).unwrap() as usize,
+                })
+                .collect::<Vec<Edge>>()
+        })
+        .collect::<Vec<_>>();
+
+    Ok(map)
+}
+
+fn parse_two(input: &str) -> String {
+    let mut temp = String::new();
+
+    for i in 0..5 {
+        for line in input.lines() {
+            for ii in 0..5 {
+                for char in line.chars() {
+                    let next = char as u8 + i + ii;
+                    let value = if (0x31..=0x39).contains(&next) {
+                        // '1' => '9'
+                        next as char
+                    } else {
+                        (next - 9) as char
+                    };
+                    temp.push(value);
+                }
+            }
+            temp.push('\n');
+        }
+    }
+    temp
+}
+
+pub fn part_one(input: &str) -> Result<i32, ParseError> {
+    lowest_total_risk(&parse(input)?, shortest_path, Solver::PartOne)
+}
+
+pub fn part_two(input: &str) -> Result<i32, ParseError> {
+    // parse_two assumes nothing but risk levels, so make sure that's all there is first
+    parse(input)?;
+
+    let second = parse_two(input);
+    lowest_total_risk(&parse(&second)?, shortest_path, Solver::PartTwo)
+}
+
+pub fn part_one_a_star(input: &str) -> Result<i32, ParseError> {
+    lowest_total_risk(&parse(input)?, a_star, Solver::PartOne)
+}
+
+pub fn part_two_a_star(input: &str) -> Result<i32, ParseError> {
+    parse(input)?;
+
+    let second = parse_two(input);
+    lowest_total_risk(&parse(&second)?, a_star, Solver::PartTwo)
+}
+
+/// cavern to search, for watching dijkstra's frontier spread out from the top left
+pub(crate) struct Cavern<'a> {
+    input: &'a str,
+}
+
+impl<'a> Cavern<'a> {
+    pub(crate) fn new(input: &'a str) -> Result<Self, ParseError> {
+        parse(input)?;
+        Ok(Self { input })
+    }
+
+    /// part two's map is five times the size each way
+    fn map(&self, solver: Solver) -> Vec<Vec<Edge>> {
+        match solver {
+            Solver::PartOne => parse(self.input),
+            Solver::PartTwo => parse(&parse_two(self.input)),
+        }
+        .expect("checked the input when the cavern was made")
+    }
+
+    /// the risk levels in shades of gray, with the lowest risk path through them in red
+    pub(crate) fn picture(&self, solver: Solver) -> Image {
+        let map = self.map(solver);
+        let trace = trace(&map, |_| {});
+
+        let risks = map
+            .iter()
+            .map(|row| row.iter().map(|edge| edge.cost as u32).collect())
+            .collect::<Vec<Vec<u32>>>();
+        let mut image = Image::render(&risks, ColorMap::Gray);
+
+        for &(row, col) in &trace.path {
+            image.set(col, row, [255, 0, 0]);
+        }
+
+        image
+    }
+}
+
+/// how far dijkstra got: the lowest risk to everywhere it's been so far, which of those are
+/// settled, and once it's done, the path it found
+#[derive(Default)]
+struct Trace {
+    dist: Vec<Vec<usize>>,
+    settled: Vec<Vec<bool>>,
+    settled_count: usize,
+    path: HashSet<(usize, usize)>,
+}
+
+impl Trace {
+    /// `.` for settled positions, `+` for the frontier, `#` for the path and the risk level for
+    /// anywhere the search hasn't got to yet
+    fn picture(&self, map: &[Vec<Edge>]) -> String {
+        let mut result = String::new();
+
+        for (i, row) in map.iter().enumerate() {
+            for (ii, edge) in row.iter().enumerate() {
+                let ch = if self.path.contains(&(i, ii)) {
+                    '#'
+                } else if self.settled[i][ii] {
+                    '.'
+                } else if self.dist[i][ii] != usize::MAX {
+                    '+'
+                } else {
+                    char::from_digit(edge.cost as u32, 10).unwrap()
+                };
+                result.push(ch);
+            }
+            result.push('\n');
+        }
+
+        result
+    }
+}
+
+/// dijkstra's shortest path again, but remembering how it got everywhere so the path can be drawn,
+/// and calling `progress` every time a position is settled
+fn trace(map: &[Vec<Edge>], mut progress: impl FnMut(&Trace)) -> Trace {
+    let (rows, cols) = (map.len(), map[0].len());
+    let goal = (rows - 1, cols - 1);
+
+    let mut trace = Trace {
+        dist: vec![vec![usize::MAX; cols]; rows],
+        settled: vec![vec![false; cols]; rows],
+        ..Default::default()
+    };
+    let mut previous = vec![vec![None; cols]; rows];
+    let mut heap = BinaryHeap::new();
+
+    trace.dist[0][0] = 0;
+    heap.push(State {
+        risk: 0,
+        position: (0, 0),
+    });
+
+    while let Some(State { risk, position }) = heap.pop() {
+        if trace.settled[position.0][position.1] {
+            continue;
+        }
+        trace.settled[position.0][position.1] = true;
+        trace.settled_count += 1;
+
+        if position == goal {
+            break;
+        }
+
+        for edge in &map[position.0][position.1].neighbors(map) {
+            let next = risk + edge.cost;
+
+            if next < trace.dist[edge.node.0][edge.node.1] {
+                trace.dist[edge.node.0][edge.node.1] = next;
+                previous[edge.node.0][edge.node.1] = Some(position);
+                heap.push(State {
+                    risk: next,
+                    position: edge.node,
+                });
+            }
+        }
+
+        progress(&trace);
+    }
+
+    let mut position = goal;
+    trace.path.insert(goal);
+    while let Some(before) = previous[position.0][position.1] {
+        trace.path.insert(before);
+        position = before;
+    }
+
+    trace
+}
+
+impl Visualize for Cavern<'_> {
+    /// the search every so often as it settles positions, ending with the lowest risk path; part
+    /// two searches the full map, five times the size each way
+    fn frames(&mut self, solver: Solver) -> Vec<Frame> {
+        let map = self.map(solver);
+        let size = map.len() * map[0].len();
+        // a few dozen frames, however big the map is
+        let batch = (size / 50).max(1);
+
+        let mut frames = Vec::new();
+        let last = trace(&map, |trace| {
+            if trace.settled_count == 1 {
+                frames.push(Frame::new(
+                    "starting from the top left",
+                    &trace.picture(&map),
+                ));
+            } else if trace.settled_count % batch == 0 {
+                frames.push(Frame::new(
+                    format!("{} of {} positions settled", trace.settled_count, size),
+                    &trace.picture(&map),
+                ));
+            }
+        });
+
+        let (rows, cols) = (map.len(), map[0].len());
+        frames.push(Frame::new(
+            format!("lowest total risk: {}", last.dist[rows - 1][cols - 1]),
+            &last.picture(&map),
+        ));
+
+        frames
+    }
+}
+
+/// `size` x `size` grid of risk levels
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+
+    (0..size)
+        .map(|_| {
+            (0..size)
+                .map(|_| rng.range(1, 9).to_string())
+                .collect::<String>()
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+pub const EXAMPLE: &str = "1163751742
+1381373672
+2136511328
+3694931569
+7463417111
+1319128137
+1359912421
+3125421639
+1293138521
+2311944581";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_one() {
+        assert_eq!(Ok(40), part_one(EXAMPLE));
+    }
+
+    #[test]
+    fn test_a_star() {
+        assert_eq!(Ok(40), part_one_a_star(EXAMPLE));
+        assert_eq!(part_two(EXAMPLE), part_two_a_star(EXAMPLE));
+    }
+
+    #[test]
+    fn test_frames() {
+        let frames = Cavern::new(EXAMPLE).unwrap().frames(Solver::PartOne);
+        let last = frames.last().unwrap();
+
+        // the start is settled, and its neighbours are the frontier
+        assert!(frames[0].picture.starts_with(".+63751742\n+381373672\n"));
+        assert_eq!("lowest total risk: 40", last.caption);
+        // the path from the puzzle text, which only goes right and down
+        assert_eq!(19, last.picture.matches('#').count());
+        assert!(last.picture.starts_with('#'));
+        assert!(last.picture.ends_with("#\n"));
+
+        let frames = Cavern::new(EXAMPLE).unwrap().frames(Solver::PartTwo);
+        assert_eq!(
+            format!("lowest total risk: {}", part_two(EXAMPLE).unwrap()),
+            frames.last().unwrap().caption
+        );
+    }
+
+    #[test]
+    fn test_picture() {
+        let image = Cavern::new(EXAMPLE).unwrap().picture(Solver::PartOne);
+
+        assert_eq!((10, 10), (image.width, image.height));
+        let path = image.pixels.iter().filter(|&&pixel| pixel == [255, 0, 0]);
+        assert_eq!(19, path.count());
+    }
+}
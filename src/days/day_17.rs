@@ -0,0 +1,350 @@
+use crate::error::{parse_value, ParseError};
+use crate::rng::Rng;
+use crate::validate::{ensure_valid, number};
+use crate::visualize::{Canvas, Frame, Visualize};
+use crate::Solver;
+use regex::Regex;
+
+#[derive(Debug, Default)]
+struct Velocity {
+    horizontal: i32,
+    vertical: i32,
+}
+
+impl Velocity {
+    fn new(horizontal: i32, vertical: i32) -> Self {
+        Self {
+            horizontal,
+            vertical,
+        }
+    }
+    fn highest_vertical(&self) -> i32 {
+        self.vertical * (self.vertical + 1) / 2
+    }
+}
+
+#[derive(Debug, Default)]
+struct Position {
+    x: i32,
+    y: i32,
+}
+
+#[derive(Debug, Default)]
+struct Target {
+    lower_x: i32,
+    upper_x: i32,
+    lower_y: i32,
+    upper_y: i32,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct Probe {
+    position: Position,
+    velocity: Velocity,
+    target: Target,
+}
+
+impl Probe {
+    pub(crate) fn new(lower_x: i32, upper_x: i32, lower_y: i32, upper_y: i32) -> Self {
+        Self {
+            target: Target {
+                lower_x,
+                upper_x,
+                lower_y,
+                upper_y,
+            },
+            ..Default::default()
+        }
+    }
+
+    fn reached_target(&self) -> bool {
+        let inside_x =
+            self.target.lower_x <= self.position.x && self.position.x <= self.target.upper_x;
+
+        let inside_y =
+            self.target.lower_y <= self.position.y && self.position.y <= self.target.upper_y;
+
+        inside_x && inside_y
+    }
+
+    fn reaches_target(&mut self) -> bool {
+        self.position = Position::default(); // 0,0
+        loop {
+            self.position.x += self.velocity.horizontal;
+            self.position.y += self.velocity.vertical;
+            self.velocity.horizontal = std::cmp::max(0, self.velocity.horizontal - 1);
+            self.velocity.vertical -= 1;
+
+            if self.position.x > self.target.upper_x || self.position.y < self.target.lower_y {
+                return false;
+            }
+
+            if self.reached_target() {
+                return true;
+            }
+        }
+    }
+
+    /// every position the probe passes through when launched with its velocity, up to where it
+    /// lands in the target area or overshoots it, along with whether it landed
+    fn trajectory(&self) -> (Vec<(i32, i32)>, bool) {
+        let (mut x, mut y) = (0, 0);
+        let (mut horizontal, mut vertical) = (self.velocity.horizontal, self.velocity.vertical);
+        let mut positions = vec![(x, y)];
+
+        loop {
+            x += horizontal;
+            y += vertical;
+            horizontal = std::cmp::max(0, horizontal - 1);
+            vertical -= 1;
+            positions.push((x, y));
+
+            if x > self.target.upper_x || y < self.target.lower_y {
+                return (positions, false);
+            }
+
+            let inside_x = self.target.lower_x <= x && x <= self.target.upper_x;
+            let inside_y = self.target.lower_y <= y && y <= self.target.upper_y;
+            if inside_x && inside_y {
+                return (positions, true);
+            }
+        }
+    }
+
+    fn min_velocity_to_target(&self) -> Velocity {
+        let horizontal = (self.target.lower_x as f32 * 2.0).sqrt() as i32;
+        let vertical = self.target.lower_y;
+        Velocity {
+            horizontal,
+            vertical,
+        }
+    }
+
+    fn max_velocity_to_target(&self) -> Velocity {
+        let horizontal = self.target.upper_x;
+        let vertical = (self.target.lower_y + 1).abs();
+        Velocity {
+            horizontal,
+            vertical,
+        }
+    }
+}
+
+impl Visualize for Probe {
+    /// the arc of every velocity that lands in the target area, in the order part two tries
+    /// them; part one only shows the ones that go higher than any before them
+    fn frames(&mut self, solver: Solver) -> Vec<Frame> {
+        let min_velocity = self.min_velocity_to_target();
+        let max_velocity = self.max_velocity_to_target();
+
+        let mut hits = Vec::new();
+        let mut highest = i32::MIN;
+
+        for h in min_velocity.horizontal..=max_velocity.horizontal {
+            for v in min_velocity.vertical..=max_velocity.vertical {
+                self.velocity = Velocity::new(h, v);
+                let (positions, hit) = self.trajectory();
+                let peak = positions.iter().map(|&(_, y)| y).max().unwrap_or_default();
+
+                if hit && (solver == Solver::PartTwo || peak > highest) {
+                    highest = highest.max(peak);
+                    hits.push((h, v, peak, positions));
+                }
+            }
+        }
+
+        let target = &self.target;
+        let right = hits
+            .iter()
+            .flat_map(|(_, _, _, positions)| positions.iter().map(|&(x, _)| x))
+            .chain([target.upper_x])
+            .max()
+            .unwrap_or_default();
+        let draw = |positions: &[(i32, i32)]| {
+            let mut canvas = Canvas::new(
+                80,
+                30,
+                0..=right as i64,
+                target.lower_y as i64..=highest.max(0) as i64,
+            );
+            canvas.fill(
+                target.lower_x as i64..=target.upper_x as i64,
+                target.lower_y as i64..=target.upper_y as i64,
+                'T',
+            );
+            for &(x, y) in positions {
+                canvas.plot(x as i64, y as i64, '#');
+            }
+            canvas.plot(0, 0, 'S');
+            canvas
+        };
+
+        let mut frames = vec![Frame::new(
+            format!(
+                "target area: x={}..{}, y={}..{}",
+                target.lower_x, target.upper_x, target.lower_y, target.upper_y
+            ),
+            &draw(&[]),
+        )];
+
+        for (idx, (h, v, peak, positions)) in hits.iter().enumerate() {
+            let caption = match solver {
+                Solver::PartOne => format!("velocity {},{} reaches y={}", h, v, peak),
+                Solver::PartTwo => {
+                    format!("velocity {},{}: hit {} of {}", h, v, idx + 1, hits.len())
+                }
+            };
+            frames.push(Frame::new(caption, &draw(positions)));
+        }
+
+        frames
+    }
+}
+
+/// furthest the target area can be from the launcher before the trajectory math overflows
+const MAX_DISTANCE: i32 = 10_000;
+
+fn target_regex() -> Regex {
+    Regex::new(r"^target area: x=(-?\d+)\.\.(-?\d+), y=(-?\d+)\.\.(-?\d+)$").unwrap()
+}
+
+/// a single target area below and to the right of the launcher, bounds in order
+pub fn validate(input: &str) -> Vec<ParseError> {
+    let line = input.trim_end();
+
+    let captures = match target_regex().captures(line) {
+        Some(captures) => captures,
+        None => {
+            return vec![ParseError::at(
+                0,
+                0,
+                "expected a target like `target area: x=20..30, y=-10..-5`",
+            )]
+        }
+    };
+
+    let mut errors = Vec::new();
+    let mut bounds = Vec::new();
+
+    for group in 1..=4 {
+        let bound = captures.get(group).unwrap();
+        match number::<i32>(bound.as_str(), 0, line[..bound.start()].chars().count()) {
+            Ok(value) => bounds.push(value),
+            Err(e) => errors.push(e),
+        }
+    }
+
+    if !errors.is_empty() {
+        return errors;
+    }
+
+    let column = |group: usize| line[..captures.get(group).unwrap().start()].chars().count();
+
+    if !(0 < bounds[0] && bounds[0] <= bounds[1] && bounds[1] <= MAX_DISTANCE) {
+        errors.push(ParseError::at(
+            0,
+            column(1),
+            format!("x needs to be somewhere in 1..={}, in order", MAX_DISTANCE),
+        ));
+    }
+    if !(-MAX_DISTANCE <= bounds[2] && bounds[2] <= bounds[3] && bounds[3] < 0) {
+        errors.push(ParseError::at(
+            0,
+            column(3),
+            format!(
+                "y needs to be somewhere in -{}..=-1, in order",
+                MAX_DISTANCE
+            ),
+        ));
+    }
+
+    errors
+}
+
+/// target area bounds
+pub(crate) fn parse(input: &str) -> Result<(i32, i32, i32, i32), ParseError> {
+    ensure_valid(validate(input))?;
+
+    let captures = target_regex().captures(input.trim_end()).unwrap();
+
+    Ok((
+        parse_value::<i32>(&captures[1], 0)?,
+        parse_value::<i32>(&captures[2], 0)?,
+        parse_value::<i32>(&captures[3], 0)?,
+        parse_value::<i32>(&captures[4], 0)?,
+    ))
+}
+
+pub fn part_one(input: &str) -> Result<i32, ParseError> {
+    let bounds = parse(input)?;
+    let probe = Probe::new(bounds.0, bounds.1, bounds.2, bounds.3);
+    let max_velocity = probe.max_velocity_to_target();
+    Ok(max_velocity.highest_vertical())
+}
+
+pub fn part_two(input: &str) -> Result<i32, ParseError> {
+    let bounds = parse(input)?;
+    let mut probe = Probe::new(bounds.0, bounds.1, bounds.2, bounds.3);
+
+    let min_velocity = probe.min_velocity_to_target();
+    let max_velocity = probe.max_velocity_to_target();
+    let mut valid_velocities = 0;
+
+    (min_velocity.horizontal..=max_velocity.horizontal).for_each(|h| {
+        (min_velocity.vertical..=max_velocity.vertical).for_each(|v| {
+            let velocity = Velocity::new(h, v);
+            probe.velocity = velocity;
+            if probe.reaches_target() {
+                valid_velocities += 1;
+            }
+        })
+    });
+
+    Ok(valid_velocities)
+}
+
+/// target area below and to the right of the launcher, further away as `size` grows
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1) as i64;
+
+    let lower_x = rng.range(10, 10 + 10 * size);
+    let upper_x = lower_x + rng.range(5, 40);
+    let upper_y = -rng.range(5, 5 + 5 * size);
+    let lower_y = upper_y - rng.range(5, 40);
+
+    format!(
+        "target area: x={}..{}, y={}..{}",
+        lower_x, upper_x, lower_y, upper_y
+    )
+}
+
+pub const EXAMPLE: &str = "target area: x=20..30, y=-10..-5";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_one() {
+        assert_eq!(Ok(45), part_one(EXAMPLE));
+    }
+
+    #[test]
+    fn test_two() {
+        assert_eq!(Ok(112), part_two(EXAMPLE));
+    }
+
+    #[test]
+    fn test_frames() {
+        let frames = Probe::new(20, 30, -10, -5).frames(Solver::PartOne);
+        let last = frames.last().unwrap();
+
+        assert_eq!("target area: x=20..30, y=-10..-5", frames[0].caption);
+        assert_eq!("velocity 6,9 reaches y=45", last.caption);
+        assert!(last.picture.contains('S'));
+        assert!(last.picture.contains('T'));
+
+        let frames = Probe::new(20, 30, -10, -5).frames(Solver::PartTwo);
+        assert_eq!(113, frames.len());
+        assert!(frames[112].caption.ends_with("hit 112 of 112"));
+    }
+}
@@ -0,0 +1,192 @@
+use crate::checked::Operation;
+use crate::error::{parse_value, ParseError};
+use crate::rng::Rng;
+use crate::stream::solve_lines;
+use crate::validate::{ensure_valid, number};
+use crate::Solver;
+use std::io::BufRead;
+
+/// problems with a single line's command
+fn check(idx: usize, line: &str) -> Vec<ParseError> {
+    let (direction, value) = match line.split_once(' ') {
+        Some(command) => command,
+        None => return vec![ParseError::at(idx, 0, "expected `direction value`")],
+    };
+
+    let mut errors = Vec::new();
+
+    if !matches!(direction, "forward" | "up" | "down") {
+        errors.push(ParseError::at(
+            idx,
+            0,
+            format!("unknown direction {:?}", direction),
+        ));
+    }
+
+    let col_idx = direction.chars().count() + 1;
+    errors.extend(number::<i32>(value, idx, col_idx).err());
+
+    errors
+}
+
+/// every command needs to be `forward`, `up` or `down`, followed by a space and a number
+pub fn validate(input: &str) -> Vec<ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .flat_map(|(idx, line)| check(idx, line))
+        .collect()
+}
+
+/// a line `check` has passed, so it's a known direction and a value
+fn parse_command(idx: usize, line: &str) -> Result<(&str, i32), ParseError> {
+    let (direction, value) = line.split_once(' ').unwrap_or_default();
+
+    Ok((direction, parse_value(value, idx)?))
+}
+
+pub(crate) fn parse(input: &str) -> Result<Vec<(&str, i32)>, ParseError> {
+    ensure_valid(validate(input))?;
+
+    input
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| parse_command(idx, line))
+        .collect()
+}
+
+/// horizontal position times depth after following the commands, with up and down changing the
+/// aim rather than the depth when `aim` is set
+fn navigate<D: AsRef<str>>(
+    commands: impl Iterator<Item = (D, i32)>,
+    aim: bool,
+) -> Result<i32, ParseError> {
+    let solver = if aim {
+        Solver::PartTwo
+    } else {
+        Solver::PartOne
+    };
+    let movement = Operation::new(2, solver, "movement");
+
+    let mut h_pos = 0;
+    let mut depth = 0;
+    let mut current_aim = 0;
+
+    for (direction, i_value) in commands {
+        let vertical = if aim { &mut current_aim } else { &mut depth };
+
+        match direction.as_ref() {
+            "forward" => {
+                h_pos = movement.add(h_pos, i_value)?;
+                depth = movement.add(depth, movement.mul(current_aim, i_value)?)?;
+            }
+            "up" => *vertical = movement.sub(*vertical, i_value)?,
+            "down" => *vertical = movement.add(*vertical, i_value)?,
+            // parsing only lets these three directions through
+            _ => unreachable!(),
+        }
+    }
+
+    Operation::new(2, solver, "position * depth").mul(h_pos, depth)
+}
+
+pub fn part_one(input: &str) -> Result<i32, ParseError> {
+    navigate(parse(input)?.into_iter(), false)
+}
+
+pub fn part_two(input: &str) -> Result<i32, ParseError> {
+    navigate(parse(input)?.into_iter(), true)
+}
+
+/// a line's command, owning its direction so the line can go
+fn owned_command(idx: usize, line: &str) -> Result<(String, i32), ParseError> {
+    ensure_valid(check(idx, line))?;
+
+    parse_command(idx, line).map(|(direction, value)| (direction.to_string(), value))
+}
+
+/// part_one, a line at a time
+pub fn stream_part_one(reader: &mut dyn BufRead) -> Result<i32, ParseError> {
+    solve_lines(reader, owned_command, |commands| navigate(commands, false))?
+}
+
+/// part_two, a line at a time
+pub fn stream_part_two(reader: &mut dyn BufRead) -> Result<i32, ParseError> {
+    solve_lines(reader, owned_command, |commands| navigate(commands, true))?
+}
+
+/// `size` commands that never send the submarine above the surface
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut depth = 0;
+
+    (0..size.max(1))
+        .map(|_| {
+            let value = rng.range(1, 9);
+            let direction = match rng.below(3) {
+                0 => "forward",
+                1 if depth >= value => {
+                    depth -= value;
+                    "up"
+                }
+                _ => {
+                    depth += value;
+                    "down"
+                }
+            };
+            format!("{} {}", direction, value)
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::property;
+
+    #[test]
+    fn test_stream() {
+        property::check(
+            "streaming the input gets the same answers as reading it all",
+            |rng| (rng.below(1000) as u64, rng.below(50)),
+            |(seed, size)| {
+                let input = generate(&mut Rng::new(*seed), *size);
+
+                let expected = (part_one(&input), part_two(&input));
+                let actual = (
+                    stream_part_one(&mut input.as_bytes()),
+                    stream_part_two(&mut input.as_bytes()),
+                );
+
+                if expected == actual {
+                    Ok(())
+                } else {
+                    Err(format!("read {:?}, streamed {:?}", expected, actual))
+                }
+            },
+        );
+    }
+    #[test]
+    fn test_stream_errors() {
+        let input = "forward 5\nsideways 2\ndown x";
+
+        assert_eq!(
+            Err(validate(input).remove(0)),
+            stream_part_one(&mut input.as_bytes())
+        );
+    }
+
+    #[cfg(feature = "checked")]
+    #[test]
+    fn test_overflow() {
+        let input = "down 6\ndown 2147483647\nforward 1";
+
+        assert_eq!(
+            Err(ParseError::overall(
+                "day 2 part 1: movement overflowed an i32 (6 + 2147483647)"
+            )),
+            part_one(input)
+        );
+        assert_eq!(part_one(input), stream_part_one(&mut input.as_bytes()));
+    }
+}
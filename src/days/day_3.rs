@@ -0,0 +1,233 @@
+use crate::checked::Operation;
+use crate::error::ParseError;
+use crate::rng::Rng;
+use crate::validate::{bad_chars, ensure_valid, rectangular};
+use crate::Solver;
+
+#[derive(Copy, Clone, Debug)]
+pub enum Rating {
+    Oxygen,
+    CO2,
+}
+
+/// numbers need to be made of bits, all the same width, and no wider than count_bits can handle
+pub fn validate(input: &str) -> Vec<ParseError> {
+    let mut errors = rectangular(input);
+
+    for (idx, line) in input.lines().enumerate() {
+        errors.extend(bad_chars(line, idx, |ch| matches!(ch, '0' | '1'), "a bit"));
+
+        if line.chars().count() > 12 {
+            errors.push(ParseError::at(
+                idx,
+                12,
+                "numbers can be at most 12 bits wide",
+            ));
+        }
+    }
+
+    errors
+}
+
+pub(crate) fn count_bits(input: &str) -> Result<(Vec<Vec<char>>, [i32; 12]), ParseError> {
+    ensure_valid(validate(input))?;
+
+    let mut counter = [0; 12];
+
+    // [
+    //   ['1', '0', '1', '1', '1', '1', '1', '0', '1', '0', '0', '0'],
+    //   ['1', '1', '0', '1', '1', '0', '0', '1', '1', '1', '0', '0'],
+    //   ...
+    // ]
+    let two_d_vec = input
+        .lines()
+        .map(|line| line.chars().collect::<Vec<char>>())
+        .collect::<Vec<Vec<char>>>();
+
+    // most common bit will be determined by positive/negative
+    two_d_vec.iter().for_each(|row| {
+        row.iter().enumerate().for_each(|(idx, ch)| match ch {
+            '0' => counter[idx] -= 1,
+            '1' => counter[idx] += 1,
+            _ => {}
+        })
+    });
+
+    Ok((two_d_vec, counter))
+}
+
+pub fn part_one(input: &str) -> Result<i32, ParseError> {
+    // gamma rate can be determined by finding the most common bit in the corresponding position
+    // of all numbers in the diagnostic report
+    let (_, gamma_ctr) = count_bits(input)?;
+
+    // string builder to arrive at something like
+    // gamma: 000100011100
+    // epsilon: 111011100011
+    let (gamma, epsilon) =
+        gamma_ctr
+            .iter()
+            .fold((String::new(), String::new()), |(gam, eps), ele| {
+                if *ele > 0 {
+                    (format!("{}{}", gam, "1"), format!("{}{}", eps, "0"))
+                } else {
+                    (format!("{}{}", gam, "0"), format!("{}{}", eps, "1"))
+                }
+            });
+
+    let gamma = i32::from_str_radix(&gamma, 2).unwrap();
+    let epsilon = i32::from_str_radix(&epsilon, 2).unwrap();
+    let power = Operation::new(3, Solver::PartOne, "gamma * epsilon").mul(gamma, epsilon)?;
+
+    Ok(power)
+}
+
+pub fn popularity_contest(index: usize, numbers: &[Vec<char>], rating: Rating) -> char {
+    let mut counter = 0;
+
+    for number in numbers {
+        match number[index] {
+            '0' => counter -= 1,
+            '1' => counter += 1,
+            _ => {}
+        }
+    }
+
+    match (counter, rating) {
+        // most common is 0 when counter is negative, 1 when positive
+        (i32::MIN..=-1, Rating::Oxygen) => '0',
+        (i32::MIN..=-1, Rating::CO2) => '1',
+        (0, Rating::Oxygen) => '1',
+        (0, Rating::CO2) => '0',
+        (1..=i32::MAX, Rating::Oxygen) => '1',
+        (1..=i32::MAX, Rating::CO2) => '0',
+    }
+}
+
+fn find_life_support_component_rating(
+    diagnostics: &[Vec<char>],
+    rating: Rating,
+) -> Result<i32, ParseError> {
+    let mut filterable = diagnostics.to_owned();
+
+    for col_idx in 0..diagnostics[0].len() {
+        // iterate for given number of columns, getting the most common bit for each index
+        let polularest = popularity_contest(col_idx, &filterable, rating);
+        let filterable_len = filterable.len(); // can't call .len from within retain
+
+        // filter out those that don't match, unless there's only one value in the vector
+        filterable.retain(|value| filterable_len == 1 || value[col_idx] == polularest);
+
+        // every number sharing the bit leaves none with the least common one
+        if filterable.is_empty() {
+            return Err(ParseError::overall(format!(
+                "no numbers have a {} in bit {} for the {:?} rating",
+                polularest,
+                col_idx + 1,
+                rating
+            )));
+        }
+    }
+
+    // convert from Vec<char> -> binary String -> i32
+    Ok(i32::from_str_radix(&filterable[0].iter().collect::<String>(), 2).unwrap())
+}
+
+pub fn part_two(input: &str) -> Result<i32, ParseError> {
+    let (two_d_vec, _) = count_bits(input)?;
+
+    let oxygen_rating = find_life_support_component_rating(&two_d_vec, Rating::Oxygen)?;
+    let co2_rating = find_life_support_component_rating(&two_d_vec, Rating::CO2)?;
+
+    let life_support =
+        Operation::new(3, Solver::PartTwo, "oxygen * co2").mul(oxygen_rating, co2_rating)?;
+
+    Ok(life_support)
+}
+
+/// whether filtering for the co2 rating ever leaves two or more numbers that all agree on the next
+/// bit, in which case the least common bit matches nothing and the rating doesn't exist
+fn co2_rating_exists(numbers: &[Vec<char>]) -> bool {
+    let mut filterable = numbers.to_owned();
+
+    for col_idx in 0..numbers[0].len() {
+        if filterable.len() == 1 {
+            break;
+        }
+
+        if filterable
+            .iter()
+            .all(|value| value[col_idx] == filterable[0][col_idx])
+        {
+            return false;
+        }
+
+        let polularest = popularity_contest(col_idx, &filterable, Rating::CO2);
+        filterable.retain(|value| value[col_idx] == polularest);
+    }
+
+    true
+}
+
+/// `size` distinct binary numbers, all the same width, no wider than `count_bits` can handle
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.clamp(1, 1 << 12);
+
+    // leave some room to pick from, but stay inside the 12 bits count_bits can handle
+    let narrowest = (usize::BITS - size.leading_zeros() + 1).clamp(5, 12) as i64;
+    let width = rng.range(narrowest, 12) as usize;
+
+    loop {
+        let mut values = (0..1 << width).collect::<Vec<usize>>();
+        rng.shuffle(&mut values);
+
+        let numbers = values[..size]
+            .iter()
+            .map(|value| {
+                format!("{:0width$b}", value, width = width)
+                    .chars()
+                    .collect()
+            })
+            .collect::<Vec<Vec<char>>>();
+
+        if co2_rating_exists(&numbers) {
+            return numbers
+                .iter()
+                .map(|number| number.iter().collect::<String>())
+                .collect::<Vec<String>>()
+                .join("\n");
+        }
+    }
+}
+
+pub const EXAMPLE: &str = "00100
+11110
+10110
+10111
+10101
+01111
+00111
+11100
+10000
+11001
+00010
+01010";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_two() {
+        assert_eq!(Ok(230), part_two(EXAMPLE));
+    }
+
+    #[test]
+    fn test_shared_bit() {
+        assert_eq!(
+            Err(ParseError::overall(
+                "no numbers have a 1 in bit 1 for the CO2 rating"
+            )),
+            part_two("010\n001")
+        );
+    }
+}
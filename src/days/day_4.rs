@@ -0,0 +1,529 @@
+use crate::checked::Operation;
+use crate::error::{parse_value, ParseError};
+use crate::explain::{Event, Sink};
+use crate::rng::Rng;
+use crate::simulation::Simulation;
+use crate::validate::{ensure_valid, fields, number, words};
+use crate::visualize::{tile, Frame, Visualize};
+use crate::Solver;
+use std::fmt::{Display, Formatter};
+
+#[derive(Clone, Debug, Default)]
+struct Number {
+    value: i32,
+    called: bool,
+}
+
+#[derive(Debug, Default, Clone)]
+pub(crate) struct Board {
+    rows: Vec<Vec<Number>>,
+    index: usize,
+}
+
+impl Display for Board {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut result = String::new();
+
+        for row in &self.rows {
+            for num in row {
+                let repr = if num.called {
+                    String::from("XX ")
+                } else {
+                    format!("{:02} ", num.value)
+                };
+                result.push_str(&repr);
+            }
+            result.push('\n');
+        }
+        write!(f, "{}", result)
+    }
+}
+
+impl Board {
+    fn check_vertical(&self) -> bool {
+        for i in 0..5 {
+            if self.rows[0][i].called
+                && self.rows[1][i].called
+                && self.rows[2][i].called
+                && self.rows[3][i].called
+                && self.rows[4][i].called
+            {
+                return true;
+            }
+        }
+
+        false
+    }
+
+    fn check_horizontal(&self) -> bool {
+        self.rows.iter().any(|row| row.iter().all(|num| num.called))
+    }
+
+    fn check(&self) -> bool {
+        self.check_vertical() || self.check_horizontal()
+    }
+
+    fn mark(&mut self, called_number: i32) {
+        for row in self.rows.iter_mut() {
+            for num in row {
+                if num.value == called_number {
+                    num.called = true;
+                }
+            }
+        }
+    }
+
+    fn unmarked(&self, sum: &Operation) -> Result<i32, ParseError> {
+        self.rows
+            .iter()
+            .flatten()
+            .filter(|num| !num.called)
+            .try_fold(0, |acc, num| sum.add(acc, num.value))
+    }
+}
+
+/// called numbers on the first line, a blank line, then 5x5 boards separated by blank lines
+pub fn validate(input: &str) -> Vec<ParseError> {
+    let mut errors = Vec::new();
+    let mut lines = input.lines().enumerate();
+
+    match lines.next() {
+        Some((_, line)) => errors.extend(
+            fields(line, ",")
+                .into_iter()
+                .filter_map(|(col_idx, field)| number::<i32>(field, 0, col_idx).err()),
+        ),
+        None => errors.push(ParseError::new(0, "missing called numbers")),
+    }
+
+    if !matches!(lines.next(), Some((_, ""))) {
+        errors.push(ParseError::new(
+            1,
+            "expected a blank line after the called numbers",
+        ));
+    }
+
+    // (zero based) line the current board starts on, and how many rows it has so far
+    let (mut start, mut rows) = (2, 0);
+    let check_rows = |start: usize, rows: usize, errors: &mut Vec<ParseError>| {
+        if rows != 5 {
+            errors.push(ParseError::new(
+                start,
+                format!("boards need to be 5 rows, found {}", rows),
+            ));
+        }
+    };
+
+    for (idx, line) in lines {
+        if line.is_empty() {
+            check_rows(start, rows, &mut errors);
+            (start, rows) = (idx + 1, 0);
+            continue;
+        }
+
+        let row = words(line);
+        if row.len() != 5 {
+            errors.push(ParseError::at(
+                idx,
+                0,
+                format!("board rows need to be 5 numbers, found {}", row.len()),
+            ));
+        }
+        errors.extend(
+            row.into_iter()
+                .filter_map(|(col_idx, word)| number::<i32>(word, idx, col_idx).err()),
+        );
+        rows += 1;
+    }
+
+    check_rows(start, rows, &mut errors);
+
+    errors
+}
+
+pub(crate) fn build_boards(input: &str) -> Result<(Vec<i32>, Vec<Board>), ParseError> {
+    ensure_valid(validate(input))?;
+
+    // validate has made sure of the layout: the called numbers, a blank line, then 5x5 boards
+    // (which check_vertical relies on) separated by blank lines
+    let mut lines = input.lines().enumerate();
+    let (_, called) = lines.next().unwrap_or_default();
+    let called_nums = called
+        .split(',')
+        .map(|n| parse_value::<i32>(n, 0))
+        .collect::<Result<Vec<i32>, ParseError>>()?;
+
+    let mut boards = vec![];
+
+    lines.next(); // discard first blank line
+
+    let mut board = Board::default();
+
+    for (idx, line) in lines {
+        if line.is_empty() {
+            // start of new board
+            boards.push(board);
+            board = Board::default();
+            continue;
+        }
+
+        let row = line
+            .split_whitespace()
+            .map(|n| {
+                Ok(Number {
+                    value: parse_value(n, idx)?,
+                    called: false,
+                })
+            })
+            .collect::<Result<Vec<Number>, ParseError>>()?;
+
+        board.index = boards.len();
+        board.rows.push(row);
+    }
+
+    boards.push(board); // push last board
+
+    Ok((called_nums, boards))
+}
+
+pub fn part_one(input: &str) -> Result<i32, ParseError> {
+    first_winner(input, &mut ())
+}
+
+pub fn part_two(input: &str) -> Result<i32, ParseError> {
+    last_winner(input, &mut ())
+}
+
+/// the score a board wins with, explaining the win
+fn win(
+    board: &Board,
+    call: usize,
+    called_number: i32,
+    solver: Solver,
+    sink: &mut dyn Sink,
+) -> Result<i32, ParseError> {
+    let unmarked = board.unmarked(&Operation::new(4, solver, "unmarked sum"))?;
+    let score = Operation::new(4, solver, "score").mul(unmarked, called_number)?;
+
+    sink.emit(
+        Event::new("win")
+            .with("board", board.index + 1)
+            .with("call", call)
+            .with("number", called_number)
+            .with("unmarked", unmarked)
+            .with("score", score),
+    );
+
+    Ok(score)
+}
+
+/// score of the first board to win, explaining which board it was and on which call
+pub(crate) fn first_winner(input: &str, sink: &mut dyn Sink) -> Result<i32, ParseError> {
+    let (called_numbers, mut boards) = build_boards(input)?;
+
+    for (idx, called_number) in called_numbers.into_iter().enumerate() {
+        for board in boards.iter_mut() {
+            board.mark(called_number);
+            if board.check() {
+                return win(board, idx + 1, called_number, Solver::PartOne, sink);
+            }
+        }
+    }
+    Ok(0)
+}
+
+/// score of the last board to win, explaining every board's win along the way
+pub(crate) fn last_winner(input: &str, sink: &mut dyn Sink) -> Result<i32, ParseError> {
+    let (called_numbers, mut boards) = build_boards(input)?;
+
+    let mut last_score = 0;
+    let mut done: Vec<usize> = vec![];
+
+    for (idx, called_number) in called_numbers.into_iter().enumerate() {
+        for board in boards.iter_mut() {
+            board.mark(called_number);
+            if board.check() {
+                if !done.contains(&board.index) {
+                    last_score = win(board, idx + 1, called_number, Solver::PartTwo, sink)?;
+                    done.push(board.index);
+                }
+                continue;
+            }
+        }
+    }
+
+    Ok(last_score)
+}
+
+/// numbers to call along with every board, for watching the game play out
+pub(crate) struct Bingo {
+    called: Vec<i32>,
+    boards: Vec<Board>,
+    /// how many numbers have been called so far
+    calls: usize,
+    /// boards that have won, in the order they did
+    winners: Vec<usize>,
+}
+
+impl Bingo {
+    pub(crate) fn new(input: &str) -> Result<Self, ParseError> {
+        let (called, boards) = build_boards(input)?;
+        Ok(Self {
+            called,
+            boards,
+            calls: 0,
+            winners: Vec::new(),
+        })
+    }
+
+    fn picture(&self) -> String {
+        let boards = self
+            .boards
+            .iter()
+            .map(Board::to_string)
+            .collect::<Vec<String>>();
+        tile(&boards, 5)
+    }
+
+    /// call the next number, returning the boards it made win; None once every number's called
+    fn call(&mut self) -> Option<Vec<usize>> {
+        let called = *self.called.get(self.calls)?;
+        self.calls += 1;
+
+        let mut winners = Vec::new();
+        for board in self.boards.iter_mut() {
+            board.mark(called);
+            if board.check() && !self.winners.contains(&board.index) {
+                winners.push(board.index);
+            }
+        }
+        self.winners.extend(&winners);
+
+        Some(winners)
+    }
+}
+
+impl Visualize for Bingo {
+    /// a frame per number called, until the first board wins for part one or the last one does
+    /// for part two
+    fn frames(&mut self, solver: Solver) -> Vec<Frame> {
+        let mut frames = vec![Frame::new(
+            format!("{} boards, nothing called yet", self.boards.len()),
+            &self.picture(),
+        )];
+
+        while let Some(winners) = self.call() {
+            let mut caption = format!("call {}: {}", self.calls, self.called[self.calls - 1]);
+            for winner in winners {
+                caption.push_str(&format!(", board {} wins", winner + 1));
+            }
+            frames.push(Frame::new(caption, &self.picture()));
+
+            let finished = match solver {
+                Solver::PartOne => !self.winners.is_empty(),
+                Solver::PartTwo => self.winners.len() == self.boards.len(),
+            };
+            if finished {
+                break;
+            }
+        }
+
+        frames
+    }
+}
+
+impl Simulation for Bingo {
+    /// calls the next number
+    fn step(&mut self) -> bool {
+        self.call().is_some()
+    }
+
+    fn show(&self) -> String {
+        self.picture()
+    }
+
+    /// row `y` of board `x`, both counted from 1 like the boards are in captions
+    fn inspect(&self, x: usize, y: usize) -> Option<String> {
+        let board = self.boards.get(x.checked_sub(1)?)?;
+        let row = board.rows.get(y.checked_sub(1)?)?;
+
+        let numbers = row
+            .iter()
+            .map(|num| match num.called {
+                true => format!("({})", num.value),
+                false => num.value.to_string(),
+            })
+            .collect::<Vec<String>>();
+
+        Some(format!("board {}, row {}: {}", x, y, numbers.join(" ")))
+    }
+
+    fn measures(&self) -> Vec<(&'static str, i64)> {
+        let last = match self.calls {
+            0 => -1,
+            calls => self.called[calls - 1] as i64,
+        };
+
+        vec![
+            ("calls", self.calls as i64),
+            ("last", last),
+            ("winners", self.winners.len() as i64),
+            ("boards", self.boards.len() as i64),
+        ]
+    }
+}
+
+/// every number from 0 to 99 called in random order, followed by `size` 5x5 boards
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut called = (0..100).collect::<Vec<i32>>();
+    rng.shuffle(&mut called);
+
+    let mut result = called
+        .iter()
+        .map(|n| n.to_string())
+        .collect::<Vec<String>>()
+        .join(",");
+
+    for _ in 0..size.max(1) {
+        let mut pool = (0..100).collect::<Vec<i32>>();
+        rng.shuffle(&mut pool);
+
+        result.push('\n');
+
+        for row in pool[..25].chunks(5) {
+            let row = row
+                .iter()
+                .map(|n| format!("{:>2}", n))
+                .collect::<Vec<String>>()
+                .join(" ");
+            result.push('\n');
+            result.push_str(&row);
+        }
+    }
+
+    result
+}
+
+pub const EXAMPLE: &str = "7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1
+
+22 13 17 11  0
+ 8  2 23  4 24
+21  9 14 16  7
+ 6 10  3 18  5
+ 1 12 20 15 19
+
+ 3 15  0  2 22
+ 9 18 13 17  5
+19  8  7 25 23
+20 11 10 24  4
+14 21 16 12  6
+
+14 21 17 24  4
+10 16 15  9 19
+18  8 23 26 20
+22 11 13  6  5
+ 2  0 12  3  7";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snapshot;
+
+    #[test]
+    fn test_one() {
+        assert_eq!(Ok(4512), part_one(EXAMPLE));
+    }
+
+    #[test]
+    fn test_two() {
+        assert_eq!(Ok(1924), part_two(EXAMPLE));
+    }
+
+    #[test]
+    fn test_explain() {
+        let mut events = Vec::new();
+        assert_eq!(Ok(4512), first_winner(EXAMPLE, &mut events));
+        assert_eq!(
+            vec!["win: board=3 call=12 number=24 unmarked=188 score=4512"],
+            events.iter().map(Event::text).collect::<Vec<String>>()
+        );
+
+        let mut events = Vec::new();
+        assert_eq!(Ok(1924), last_winner(EXAMPLE, &mut events));
+        assert_eq!(3, events.len());
+        assert_eq!(
+            "win: board=2 call=15 number=13 unmarked=148 score=1924",
+            events[2].text()
+        );
+    }
+
+    #[test]
+    fn test_frames() {
+        let mut bingo = Bingo::new(EXAMPLE).unwrap();
+        let frames = bingo.frames(Solver::PartOne);
+
+        // the third board wins on the 12th number, 24
+        assert_eq!(13, frames.len());
+        assert_eq!("call 12: 24, board 3 wins", frames[12].caption);
+        assert!(frames[12].picture.contains("XX XX XX XX XX"));
+
+        let mut bingo = Bingo::new(EXAMPLE).unwrap();
+        let last = bingo.frames(Solver::PartTwo).pop().unwrap();
+        assert_eq!("call 15: 13, board 2 wins", last.caption);
+    }
+
+    #[test]
+    fn test_simulation() {
+        let mut bingo = Bingo::new(EXAMPLE).unwrap();
+        for _ in 0..12 {
+            assert!(bingo.step());
+        }
+
+        assert_eq!(
+            vec![("calls", 12), ("last", 24), ("winners", 1), ("boards", 3)],
+            bingo.measures()
+        );
+        assert_eq!(
+            Some(String::from("board 3, row 1: (14) (21) (17) (24) (4)")),
+            bingo.inspect(3, 1)
+        );
+        assert_eq!(None, bingo.inspect(4, 1));
+        assert_eq!(None, bingo.inspect(0, 1));
+
+        while bingo.step() {}
+        assert_eq!(27, bingo.measures()[0].1);
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(validate(EXAMPLE).is_empty());
+
+        let errors = validate("7,4,x\n\n1 2 3 4 5\n1 2 3 4\n1 2 3 4 5");
+        assert_eq!(
+            vec![
+                "line 1, column 5: couldn't parse \"x\": invalid digit found in string",
+                "line 4, column 1: board rows need to be 5 numbers, found 4",
+                "line 3: boards need to be 5 rows, found 3",
+            ],
+            errors
+                .iter()
+                .map(|e| e.to_string())
+                .collect::<Vec<String>>()
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        let (called, mut boards) = build_boards(EXAMPLE).unwrap();
+        for number in &called[..12] {
+            boards.iter_mut().for_each(|board| board.mark(*number));
+        }
+
+        let rendered = boards
+            .iter()
+            .map(Board::to_string)
+            .collect::<Vec<String>>()
+            .join("\n");
+        snapshot::check("day_4_boards", &rendered);
+    }
+}
@@ -0,0 +1,371 @@
+use crate::checked::Operation;
+use crate::error::{parse_value, ParseError};
+use crate::image::{ColorMap, Image};
+use crate::rng::Rng;
+use crate::validate::{digits, ensure_valid, fields};
+use crate::Solver;
+use regex::Regex;
+use std::cmp;
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+
+#[derive(Copy, Clone, Debug, Default, Ord, PartialOrd, Eq, PartialEq)]
+struct Point {
+    x: i32,
+    y: i32,
+    covered_by: i32,
+}
+
+impl Point {
+    fn new(x: i32, y: i32) -> Self {
+        Self {
+            x,
+            y,
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Line {
+    slope: i32,
+    points: (Point, Point),
+    x_range: RangeInclusive<i32>,
+    y_range: RangeInclusive<i32>,
+}
+
+impl Line {
+    fn new(p1: Point, p2: Point) -> Self {
+        let left_x = cmp::min(p1.x, p2.x);
+        let right_x = cmp::max(p1.x, p2.x);
+        let left_y = cmp::min(p1.y, p2.y);
+        let right_y = cmp::max(p1.y, p2.y);
+        let slope = (p1.y - p2.y).checked_div(p1.x - p2.x).unwrap_or(0);
+
+        Self {
+            points: (p1, p2),
+            x_range: (left_x..=right_x),
+            y_range: (left_y..=right_y),
+            slope,
+        }
+    }
+}
+
+impl Line {
+    fn contains(&self, point: Point, solver: Solver) -> bool {
+        if self.is_vertical() && self.points.0.x == point.x {
+            // need to check y values
+            self.y_range.contains(&point.y)
+        } else if self.is_horizontal() && self.points.0.y == point.y {
+            self.x_range.contains(&point.x)
+        } else if self.is_diagonal() && solver == Solver::PartTwo {
+            self.slope * (point.x - self.points.0.x) == point.y - self.points.0.y
+                && self.x_range.contains(&point.x)
+                && self.y_range.contains(&point.y)
+        } else {
+            false
+        }
+    }
+
+    fn is_vertical(&self) -> bool {
+        self.points.0.x == self.points.1.x
+    }
+
+    fn is_horizontal(&self) -> bool {
+        self.points.0.y == self.points.1.y
+    }
+
+    fn is_diagonal(&self) -> bool {
+        let lhs =
+            cmp::max(self.points.0.x, self.points.1.x) - cmp::min(self.points.1.x, self.points.0.x);
+        let rhs =
+            cmp::max(self.points.0.y, self.points.1.y) - cmp::min(self.points.1.y, self.points.0.y);
+        lhs == rhs
+    }
+}
+
+/// the vents are on a 1000 by 1000 grid, which part_one scans every point of
+const MAX_COORDINATE: i32 = 999;
+
+/// every line needs to be a segment like `0,9 -> 5,9`, on the grid
+pub fn validate(input: &str) -> Vec<ParseError> {
+    let mut errors = Vec::new();
+
+    for (idx, line) in input.lines().enumerate() {
+        let ends = fields(line, " -> ");
+        if ends.len() != 2 {
+            errors.push(ParseError::at(idx, 0, "expected `x1,y1 -> x2,y2`"));
+            continue;
+        }
+
+        for (end_idx, end) in ends {
+            let coordinates = fields(end, ",");
+            if coordinates.len() != 2 {
+                errors.push(ParseError::at(idx, end_idx, "expected a point like `x,y`"));
+                continue;
+            }
+
+            errors.extend(coordinates.into_iter().filter_map(|(col_idx, value)| {
+                match digits::<i32>(value, idx, end_idx + col_idx) {
+                    Ok(coordinate) if coordinate > MAX_COORDINATE => Some(ParseError::at(
+                        idx,
+                        end_idx + col_idx,
+                        format!("coordinates can be at most {}", MAX_COORDINATE),
+                    )),
+                    Ok(_) => None,
+                    Err(e) => Some(e),
+                }
+            }));
+        }
+    }
+
+    errors
+}
+
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Graph {
+    lines: Vec<Line>,
+    x_boundary: i32,
+    y_boundary: i32,
+    covered_points: Vec<Point>,
+}
+
+impl Graph {
+    pub(crate) fn new(input: &str) -> Result<Self, ParseError> {
+        ensure_valid(validate(input))?;
+
+        let mut line_segments = vec![];
+        let re = Regex::new(r"^(\d+),(\d+) -> (\d+),(\d+)$").unwrap();
+
+        // track edges of the diagram, i.e. largest x and y we see while parsing
+        let mut largest_x = 0;
+        let mut largest_y = 0;
+
+        for (idx, line) in input.lines().enumerate() {
+            let parsed = re.captures(line).unwrap();
+
+            let x1 = parse_value::<i32>(&parsed[1], idx)?;
+            let y1 = parse_value::<i32>(&parsed[2], idx)?;
+            let x2 = parse_value::<i32>(&parsed[3], idx)?;
+            let y2 = parse_value::<i32>(&parsed[4], idx)?;
+
+            // grab the largest of the three current numbers for both x and y
+            largest_x = cmp::max(largest_x, cmp::max(x1, x2));
+            largest_y = cmp::max(largest_y, cmp::max(y1, y2));
+
+            let p1 = Point::new(x1, y1);
+            let p2 = Point::new(x2, y2);
+
+            line_segments.push(Line::new(p1, p2))
+        }
+
+        Ok(Self {
+            lines: line_segments,
+            x_boundary: largest_x,
+            y_boundary: largest_y,
+            covered_points: Vec::<Point>::new(),
+        })
+    }
+}
+
+pub fn part_one(input: &str, solver: Solver) -> Result<i32, ParseError> {
+    let mut graph = Graph::new(input)?;
+
+    // iterating up to and including the largest known values of x and y, since we saw them while
+    // parsing, meaning they're valid points
+    for row in 0..=graph.x_boundary {
+        for column in 0..=graph.y_boundary {
+            // get the current point on the graph
+            let mut current = Point::new(row, column);
+
+            for line in &graph.lines {
+                // each line is a line segment from the given input, check if any of the lines
+                // cover the current point
+                if line.contains(current, solver) {
+                    current.covered_by += 1;
+                }
+            }
+
+            if current.covered_by > 1 && !graph.covered_points.contains(&current) {
+                // for any point that's covered by at least 2 lines, add it to the vec of ones we
+                // care about
+                graph.covered_points.push(current);
+            }
+        }
+    }
+
+    Operation::new(5, solver, "overlap count").narrow(graph.covered_points.len())
+}
+
+pub fn part_two(input: &str, solver: Solver) -> Result<i32, ParseError> {
+    part_one(input, solver)
+}
+
+/// how many lines pass through each point, walking along every line the part cares about
+fn draw(graph: &Graph, solver: Solver) -> HashMap<(i32, i32), i32> {
+    let mut drawn = HashMap::new();
+
+    for line in &graph.lines {
+        let straight = line.is_vertical() || line.is_horizontal();
+        let diagonal = line.is_diagonal() && solver == Solver::PartTwo;
+        if !(straight || diagonal) {
+            // part one ignores diagonals, and nothing else is drawn at all
+            continue;
+        }
+
+        let (start, end) = line.points;
+        let (dx, dy) = ((end.x - start.x).signum(), (end.y - start.y).signum());
+        let mut current = (start.x, start.y);
+
+        loop {
+            *drawn.entry(current).or_insert(0) += 1;
+
+            if current == (end.x, end.y) {
+                break;
+            }
+            current = (current.0 + dx, current.1 + dy);
+        }
+    }
+
+    drawn
+}
+
+/// rasterizing approach: walk along every line instead of scanning the whole grid, counting how
+/// many lines pass through each point
+pub fn rasterize(input: &str, solver: Solver) -> Result<i32, ParseError> {
+    let graph = Graph::new(input)?;
+    let drawn = draw(&graph, solver);
+
+    Operation::new(5, solver, "overlap count")
+        .narrow(drawn.values().filter(|count| **count > 1).count())
+}
+
+/// the vents as a heatmap, brighter where more lines overlap
+pub(crate) fn heatmap(input: &str, solver: Solver) -> Result<Image, ParseError> {
+    let graph = Graph::new(input)?;
+    let drawn = draw(&graph, solver);
+
+    let counts = (0..=graph.y_boundary)
+        .map(|y| {
+            (0..=graph.x_boundary)
+                .map(|x| drawn.get(&(x, y)).copied().unwrap_or_default() as u32)
+                .collect()
+        })
+        .collect::<Vec<Vec<u32>>>();
+
+    Ok(Image::render(&counts, ColorMap::Heat))
+}
+
+/// `size` horizontal, vertical or 45 degree lines on a grid that grows with `size`
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let extent = ((size as i64).max(10) - 1).min(MAX_COORDINATE as i64);
+
+    (0..size.max(1))
+        .map(|_| {
+            let (x1, y1) = (rng.range(0, extent), rng.range(0, extent));
+            let (dx, dy) = (*rng.choose(&[-1, 1]), *rng.choose(&[-1, 1]));
+
+            // longest diagonal that stays on the grid in the chosen direction
+            let room_x = if dx > 0 { extent - x1 } else { x1 };
+            let room_y = if dy > 0 { extent - y1 } else { y1 };
+            let room = room_x.min(room_y);
+
+            let (x2, y2) = match rng.below(3) {
+                0 if room > 0 => {
+                    let length = rng.range(1, room);
+                    (x1 + dx * length, y1 + dy * length)
+                }
+                1 => (x1, (y1 + rng.range(1, extent)) % (extent + 1)),
+                _ => ((x1 + rng.range(1, extent)) % (extent + 1), y1),
+            };
+
+            format!("{},{} -> {},{}", x1, y1, x2, y2)
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+pub const EXAMPLE: &str = "0,9 -> 5,9
+8,0 -> 0,8
+9,4 -> 3,4
+2,2 -> 2,1
+7,0 -> 7,4
+6,4 -> 2,0
+0,9 -> 2,9
+3,4 -> 1,4
+0,0 -> 8,8
+5,5 -> 8,2";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_contains_part_one() {
+        let line = Line::new(Point::new(7, 0), Point::new(7, 4));
+
+        for i in 0..=4 {
+            assert!(line.contains(Point::new(7, i), Solver::PartOne));
+        }
+
+        let line = Line::new(Point::new(7, 0), Point::new(3, 0));
+        for i in 3..=7 {
+            assert!(line.contains(Point::new(i, 0), Solver::PartOne));
+        }
+    }
+
+    #[test]
+    fn test_contains_part_two() {
+        let line = Line::new(Point::new(1, 1), Point::new(3, 3));
+        assert!(line.contains(Point::new(1, 1), Solver::PartTwo));
+        assert!(line.contains(Point::new(2, 2), Solver::PartTwo));
+        assert!(line.contains(Point::new(3, 3), Solver::PartTwo));
+
+        let line = Line::new(Point::new(9, 7), Point::new(7, 9));
+        assert!(line.contains(Point::new(9, 7), Solver::PartTwo));
+        assert!(line.contains(Point::new(8, 8), Solver::PartTwo));
+        assert!(line.contains(Point::new(7, 9), Solver::PartTwo));
+
+        assert!(!Line::new(Point::new(8, 0), Point::new(0, 8))
+            .contains(Point::new(0, 0), Solver::PartTwo));
+    }
+
+    #[test]
+    fn test_one() {
+        assert_eq!(Ok(5), part_one(EXAMPLE, Solver::PartOne));
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(validate(EXAMPLE).is_empty());
+        assert_eq!(
+            vec![ParseError::at(0, 9, "coordinates can be at most 999")],
+            validate("8,6 -> 8,2147483647")
+        );
+    }
+
+    #[test]
+    fn test_heatmap() {
+        let image = heatmap(EXAMPLE, Solver::PartTwo).unwrap();
+        assert_eq!((10, 10), (image.width, image.height));
+
+        // the puzzle's diagram has 3 lines crossing at 4,4 and 6,4 and nowhere else
+        let hottest = ColorMap::Heat.color(3, 3);
+        assert_eq!(
+            vec![44, 46],
+            (0..100)
+                .filter(|&idx| image.pixels[idx] == hottest)
+                .collect::<Vec<usize>>()
+        );
+    }
+
+    #[test]
+    fn test_two() {
+        assert_eq!(Ok(12), part_two(EXAMPLE, Solver::PartTwo));
+    }
+
+    #[test]
+    fn test_rasterize() {
+        assert_eq!(Ok(5), rasterize(EXAMPLE, Solver::PartOne));
+        assert_eq!(Ok(12), rasterize(EXAMPLE, Solver::PartTwo));
+    }
+}
@@ -0,0 +1,232 @@
+use crate::checked::Operation;
+use crate::error::{parse_value, ParseError};
+use crate::rng::Rng;
+use crate::simulation::Simulation;
+use crate::validate::{ensure_valid, fields, number};
+use crate::Solver;
+
+#[derive(Clone, Debug, Default)]
+struct Model {
+    population: Vec<Fish>,
+}
+
+impl Model {
+    fn new() -> Self {
+        Model {
+            population: Vec::new(),
+        }
+    }
+
+    fn tick(&mut self) {
+        let mut new_fish_counter = 0;
+
+        for fish in self.population.iter_mut() {
+            match fish.timer {
+                0 => {
+                    //create new fish & reset timer
+                    fish.timer = 6;
+                    new_fish_counter += 1;
+                }
+                1..=8 => {
+                    fish.timer -= 1;
+                }
+                _ => {}
+            }
+        }
+        (0..new_fish_counter).for_each(|_| self.population.push(Fish::new(8)));
+    }
+}
+
+#[derive(Clone, Debug, Default, Copy)]
+struct Fish {
+    timer: i32,
+}
+
+impl Fish {
+    fn new(timer: i32) -> Self {
+        Fish { timer }
+    }
+}
+
+fn progress_model(model: &mut Model, days: i32) {
+    (0..days).for_each(|_| model.tick());
+}
+
+/// naive approach: track every single fish
+pub(crate) fn simulate_fish(timers: &[usize], days: i32) -> usize {
+    let mut model = Model::new();
+    timers
+        .iter()
+        .for_each(|timer| model.population.push(Fish::new(*timer as i32)));
+
+    progress_model(&mut model, days);
+    model.population.len()
+}
+
+/// every timer needs to be a number from 0 to 8
+pub fn validate(input: &str) -> Vec<ParseError> {
+    let mut errors = Vec::new();
+
+    for (col_idx, field) in fields(input, ",") {
+        match number::<usize>(field, 0, col_idx) {
+            Ok(timer) if timer > 8 => errors.push(ParseError::at(
+                0,
+                col_idx,
+                format!("timer {} is above 8", timer),
+            )),
+            Ok(_) => {}
+            Err(e) => errors.push(e),
+        }
+    }
+
+    errors
+}
+
+/// comma separated timers, none of which can be above 8
+pub(crate) fn parse(input: &str) -> Result<Vec<usize>, ParseError> {
+    ensure_valid(validate(input))?;
+
+    input
+        .split(',')
+        .map(|n| parse_value::<usize>(n, 0))
+        .collect()
+}
+
+pub fn part_one(input: &str) -> Result<i32, ParseError> {
+    Operation::new(6, Solver::PartOne, "fish count").narrow(simulate_fish(&parse(input)?, 80))
+}
+
+/// how many fish share each timer value, rather than every single fish
+#[derive(Clone, Debug, Default)]
+pub(crate) struct School {
+    counts: [usize; 9],
+    days: usize,
+}
+
+impl School {
+    pub(crate) fn new(timers: &[usize]) -> Self {
+        let mut counts = [0; 9];
+        timers.iter().for_each(|fish_on_day| {
+            counts[*fish_on_day] += 1;
+        });
+
+        School { counts, days: 0 }
+    }
+
+    fn tick(&mut self) {
+        // rotate left by 1 is equivalent to decrementing each fish's timer by 1
+        self.counts.rotate_left(1);
+
+        // need to update the 'old' fish vs. 'new' fish
+        // old fish will go to day 6 and new fish go to day 8
+        // the day 8 move happens naturally with the rotate left, but we'll need to increment
+        // day 6 by the same value as index 8
+        self.counts[6] += self.counts[8];
+        self.days += 1;
+    }
+
+    fn len(&self) -> usize {
+        self.counts.iter().sum()
+    }
+}
+
+impl Simulation for School {
+    /// simulates a day; fish never stop, so neither does this
+    fn step(&mut self) -> bool {
+        self.tick();
+        true
+    }
+
+    fn show(&self) -> String {
+        (0..9)
+            .map(|timer| format!("timer {}: {}\n", timer, self.counts[timer]))
+            .collect()
+    }
+
+    /// how many fish have timer `x`; `y` doesn't mean anything here
+    fn inspect(&self, x: usize, _: usize) -> Option<String> {
+        let count = self.counts.get(x)?;
+        Some(format!("{} fish with timer {}", count, x))
+    }
+
+    fn measures(&self) -> Vec<(&'static str, i64)> {
+        vec![("days", self.days as i64), ("fish", self.len() as i64)]
+    }
+}
+
+/// optimized approach: track how many fish share each timer value
+pub(crate) fn simulate_days(timers: &[usize], days: usize) -> usize {
+    // model days instead of fish
+    let mut school = School::new(timers);
+    (0..days).for_each(|_| school.tick());
+
+    school.len()
+}
+
+pub fn part_two(input: &str) -> Result<usize, ParseError> {
+    Ok(simulate_days(&parse(input)?, 256))
+}
+
+/// comma separated timers, like the real input, each fish starting somewhere in its cycle
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| rng.range(1, 5).to_string())
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+pub const EXAMPLE: &str = "3,4,3,1,2";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::property;
+
+    #[test]
+    fn test_one() {
+        assert_eq!(Ok(5934), part_one(EXAMPLE));
+    }
+
+    #[test]
+    fn test_two() {
+        assert_eq!(Ok(26984457539), part_two(EXAMPLE));
+    }
+
+    #[test]
+    fn test_oracle() {
+        property::check(
+            "counting fish per timer matches simulating every fish",
+            |rng| {
+                let fish = (0..rng.range(1, 20))
+                    .map(|_| rng.range(0, 8) as usize)
+                    .collect::<Vec<usize>>();
+                (fish, rng.below(40))
+            },
+            |(fish, days)| {
+                let expected = simulate_fish(fish, *days as i32);
+                let actual = simulate_days(fish, *days);
+
+                if expected == actual {
+                    Ok(())
+                } else {
+                    Err(format!("simulated {}, counted {}", expected, actual))
+                }
+            },
+        );
+    }
+
+    #[test]
+    fn test_simulation() {
+        let mut school = School::new(&parse(EXAMPLE).unwrap());
+        for _ in 0..18 {
+            assert!(school.step());
+        }
+
+        assert_eq!(vec![("days", 18), ("fish", 26)], school.measures());
+        assert_eq!(
+            Some(String::from("5 fish with timer 6")),
+            school.inspect(6, 0)
+        );
+        assert_eq!(None, school.inspect(9, 0));
+    }
+}
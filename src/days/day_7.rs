@@ -0,0 +1,182 @@
+use crate::checked::Operation;
+use crate::error::{parse_value, ParseError};
+use crate::rng::Rng;
+use crate::validate::{digits, ensure_valid, fields};
+use crate::Solver;
+
+/// fuel to move a crab between two positions; a unit a step for part one, and for part two each
+/// step costs one more than the last, which adds up to the distance's triangular number
+fn movement_cost(left: i32, right: i32, fuel: &Operation) -> Result<i32, ParseError> {
+    let distance = u64::from(left.abs_diff(right));
+
+    match fuel.solver {
+        Solver::PartOne => fuel.narrow(distance),
+        Solver::PartTwo => fuel.narrow(distance * (distance + 1) / 2),
+    }
+}
+
+/// furthest along a crab can be; every position up to the furthest crab is tried, so this keeps
+/// that to something that finishes
+const MAX_POSITION: i32 = 100_000;
+
+/// every crab's position needs to be a number, between 0 and MAX_POSITION
+pub fn validate(input: &str) -> Vec<ParseError> {
+    fields(input, ",")
+        .into_iter()
+        .filter_map(|(col_idx, field)| match digits::<i32>(field, 0, col_idx) {
+            Ok(position) if position > MAX_POSITION => Some(ParseError::at(
+                0,
+                col_idx,
+                format!("positions can be at most {}", MAX_POSITION),
+            )),
+            Ok(_) => None,
+            Err(e) => Some(e),
+        })
+        .collect()
+}
+
+pub(crate) fn parse(input: &str) -> Result<Vec<i32>, ParseError> {
+    ensure_valid(validate(input))?;
+
+    input.split(',').map(|n| parse_value(n, 0)).collect()
+}
+
+pub fn part_one(input: &str, solver: Solver) -> Result<i32, ParseError> {
+    let mut crabs = parse(input)?;
+
+    crabs.sort_unstable(); // get upper bounds as last item in array
+    let mut least_cost = i32::MAX;
+    let fuel = Operation::new(7, solver, "fuel cost");
+
+    for pos in 0..=crabs[crabs.len() - 1] {
+        // 0 to the maximum crab's position, i.e. 16 in the testcase
+        let mut fuel_cost = 0;
+        for crab in &crabs {
+            // add up the distance between the current position from outer loop and each crab
+            fuel_cost = fuel.add(fuel_cost, movement_cost(pos, *crab, &fuel)?)?;
+        }
+
+        least_cost = least_cost.min(fuel_cost);
+    }
+
+    Ok(least_cost)
+}
+
+pub fn part_two(input: &str) -> Result<i32, ParseError> {
+    part_one(input, Solver::PartTwo)
+}
+
+/// comma separated crab positions, clustered towards the low end like the real input
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    (0..size)
+        .map(|_| {
+            let spread = rng.range(0, 2 * size as i64);
+            rng.range(0, spread).to_string()
+        })
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+pub const EXAMPLE: &str = "16,1,2,0,4,2,7,1,2,14";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::property;
+
+    #[test]
+    fn test_one() {
+        assert_eq!(Ok(37), part_one(EXAMPLE, Solver::PartOne));
+    }
+
+    #[test]
+    fn test_two() {
+        assert_eq!(Ok(168), part_two(EXAMPLE));
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(validate(EXAMPLE).is_empty());
+        assert_eq!(
+            vec![
+                ParseError::at(0, 2, "'-' isn't a digit"),
+                ParseError::at(0, 5, "positions can be at most 100000"),
+            ],
+            validate("1,-5,100001")
+        );
+    }
+
+    #[cfg(feature = "checked")]
+    #[test]
+    fn test_overflow() {
+        // the cheapest positions fit an i32, but getting there doesn't: moving the crab at 70000 to
+        // 0 costs more fuel than an i32 holds, as do the three at 50000 between them
+        let overflows = [
+            ("0,70000", "2450035000 is too big"),
+            ("0,50000,50000,50000", "1250025000 + 1250025000"),
+        ];
+
+        for (input, detail) in overflows {
+            assert_eq!(
+                Err(ParseError::overall(format!(
+                    "day 7 part 2: fuel cost overflowed an i32 ({})",
+                    detail
+                ))),
+                part_two(input)
+            );
+        }
+    }
+
+    #[test]
+    fn test_oracle() {
+        property::check(
+            "cheapest position matches trying every position between the crabs",
+            |rng| {
+                let crabs = (0..rng.range(1, 10))
+                    .map(|_| rng.range(0, 50) as i32)
+                    .collect::<Vec<i32>>();
+                (crabs, rng.below(2))
+            },
+            |(crabs, part)| {
+                if crabs.is_empty() {
+                    return Ok(());
+                }
+
+                let solver = [Solver::PartOne, Solver::PartTwo][*part % 2];
+                let input = crabs
+                    .iter()
+                    .map(|crab| crab.to_string())
+                    .collect::<Vec<String>>()
+                    .join(",");
+
+                let (lowest, highest) = (crabs.iter().min().unwrap(), crabs.iter().max().unwrap());
+                let expected = (*lowest..=*highest)
+                    .map(|pos| {
+                        crabs
+                            .iter()
+                            .map(|crab| {
+                                let distance = (pos - crab).abs();
+                                match solver {
+                                    Solver::PartOne => distance,
+                                    Solver::PartTwo => distance * (distance + 1) / 2,
+                                }
+                            })
+                            .sum::<i32>()
+                    })
+                    .min()
+                    .unwrap();
+                let actual = part_one(&input, solver).map_err(|e| e.to_string())?;
+
+                if expected == actual {
+                    Ok(())
+                } else {
+                    Err(format!(
+                        "{:?}: brute force {}, solver {}",
+                        solver, expected, actual
+                    ))
+                }
+            },
+        );
+    }
+}
@@ -0,0 +1,471 @@
+use crate::checked::Operation;
+use crate::error::ParseError;
+use crate::explain::{Event, Sink};
+use crate::rng::Rng;
+use crate::validate::{ensure_valid, fields, words};
+use crate::Solver;
+use std::collections::HashMap;
+
+fn sort_signals(signal: &str) -> String {
+    let mut chars = signal.chars().collect::<Vec<char>>();
+    chars.sort_unstable();
+    String::from_iter(chars)
+}
+
+fn contains_all(left: &str, right: &str) -> bool {
+    left.chars().all(|c| right.contains(c))
+}
+
+fn filter_all(left: &str, right: &str) -> String {
+    left.chars()
+        .filter(|c| !right.contains(*c))
+        .collect::<String>()
+}
+
+fn add_signal_to_map(
+    signal: &str,
+    name: &str,
+    capacity: usize,
+    known: &mut HashMap<String, String>,
+) {
+    let sorted = sort_signals(signal);
+    known.insert(sorted.clone(), String::with_capacity(capacity));
+    known.insert(String::from(name), sorted);
+}
+
+/// returns i32 for part one, while part two relies on manipulating the `known` hashmap; either
+/// way along with the reason it's that digit, for explaining
+fn determine_digit(
+    signal: &str,
+    known: &mut HashMap<String, String>,
+    solver: Solver,
+) -> (i32, &'static str) {
+    match (signal.len(), solver) {
+        // easy, used by both parts one and two
+        (2, _) => {
+            add_signal_to_map(signal, "one", 1, known);
+            (1, "only 1 lights 2 segments")
+        }
+        (3, _) => {
+            add_signal_to_map(signal, "seven", 7, known);
+            (7, "only 7 lights 3 segments")
+        }
+        (4, _) => {
+            add_signal_to_map(signal, "four", 4, known);
+            (4, "only 4 lights 4 segments")
+        }
+        (7, _) => {
+            add_signal_to_map(signal, "eight", 8, known);
+            (8, "only 8 lights all 7 segments")
+        }
+        // less easy, but due to sorting the initial signals array, we know that one and four are
+        // always set before processing anything below
+        (5, Solver::PartTwo) => {
+            // possible: 2, 3, 5
+            // sort strings for proper membership comparison
+
+            let five_identifier = filter_all(known.get("four").unwrap(), known.get("one").unwrap());
+
+            if contains_all(known.get("one").unwrap(), signal) {
+                add_signal_to_map(signal, "three", 3, known);
+                (3, "5 segments, including both of 1's")
+            } else if contains_all(&five_identifier, signal) {
+                add_signal_to_map(signal, "five", 5, known);
+                (5, "5 segments, including the two 4 has that 1 doesn't")
+            } else {
+                add_signal_to_map(signal, "two", 2, known);
+                (2, "5 segments, and neither 3 nor 5")
+            }
+        }
+        (6, Solver::PartTwo) => {
+            // 0, 6, 9
+            let two_identifier = filter_all(known.get("two").unwrap(), known.get("three").unwrap());
+
+            if contains_all(known.get("one").unwrap(), signal)
+                && contains_all(known.get("five").unwrap(), signal)
+            {
+                add_signal_to_map(signal, "nine", 9, known);
+                (9, "6 segments, including all of 1's and 5's")
+            } else if contains_all(known.get("five").unwrap(), signal)
+                && contains_all(&two_identifier, signal)
+            {
+                add_signal_to_map(signal, "six", 6, known);
+                (
+                    6,
+                    "6 segments, including all of 5's and the one 2 has that 3 doesn't",
+                )
+            } else {
+                add_signal_to_map(signal, "zero", 0, known);
+                (0, "6 segments, and neither 9 nor 6")
+            }
+        }
+        _ => (-1, "could be more than one digit"),
+    }
+}
+
+/// segments as bits, a being the lowest
+fn mask(pattern: &str) -> u8 {
+    pattern
+        .chars()
+        .fold(0, |acc, ch| acc | 1 << (ch as u8 - b'a'))
+}
+
+/// whether some wiring of the segments turns DIGITS into exactly these patterns, which part two
+/// relies on to pick the digits apart
+///
+/// every segment is lit in a different number of digits, except a/c (8) and d/g (7); c is part of
+/// 1 and a isn't, d is part of 4 and g isn't, which pins down the only wiring worth checking
+fn wiring_exists(patterns: &[&str]) -> bool {
+    let find = |len: usize| patterns.iter().find(|pattern| pattern.len() == len);
+    let (one, four) = match (find(2), find(4)) {
+        (Some(one), Some(four)) => (mask(one), mask(four)),
+        _ => return false,
+    };
+
+    // wiring[wire] is the segment the wire is actually hooked up to
+    let mut wiring = [0_u8; 7];
+    for (wire, segment) in wiring.iter_mut().enumerate() {
+        let bit = 1 << wire;
+        let lit = patterns
+            .iter()
+            .filter(|pattern| mask(pattern) & bit != 0)
+            .count();
+
+        *segment = match (lit, one & bit != 0, four & bit != 0) {
+            (8, false, _) => b'a',
+            (6, _, _) => b'b',
+            (8, true, _) => b'c',
+            (7, _, true) => b'd',
+            (4, _, _) => b'e',
+            (9, _, _) => b'f',
+            (7, _, false) => b'g',
+            _ => return false,
+        };
+    }
+
+    let mut expected = DIGITS.iter().map(|digit| mask(digit)).collect::<Vec<u8>>();
+    let mut found = patterns
+        .iter()
+        .map(|pattern| {
+            let rewired = pattern
+                .chars()
+                .map(|ch| wiring[(ch as u8 - b'a') as usize] as char)
+                .collect::<String>();
+            mask(&rewired)
+        })
+        .collect::<Vec<u8>>();
+
+    expected.sort_unstable();
+    found.sort_unstable();
+    expected == found
+}
+
+/// problems with the whitespace separated digits in one half of a line, which starts at the
+/// (zero based) column `start`
+fn check_digits(
+    digits: &[(usize, &str)],
+    expected: usize,
+    idx: usize,
+    start: usize,
+) -> Vec<ParseError> {
+    let mut errors = Vec::new();
+
+    if digits.len() != expected {
+        errors.push(ParseError::at(
+            idx,
+            start,
+            format!("expected {} digits, found {}", expected, digits.len()),
+        ));
+    }
+
+    for (col_idx, digit) in digits {
+        for (offset, ch) in digit.chars().enumerate() {
+            if !matches!(ch, 'a'..='g') {
+                errors.push(ParseError::at(
+                    idx,
+                    start + col_idx + offset,
+                    format!("{:?} isn't a segment", ch),
+                ));
+            } else if digit.chars().take(offset).any(|other| other == ch) {
+                errors.push(ParseError::at(
+                    idx,
+                    start + col_idx + offset,
+                    format!("segment {:?} is lit twice", ch),
+                ));
+            }
+        }
+    }
+
+    errors
+}
+
+/// every line needs ten signal patterns that make up the digits 0 through 9, a `|`, then four
+/// output digits that show up in the patterns
+pub fn validate(input: &str) -> Vec<ParseError> {
+    let mut errors = Vec::new();
+
+    for (idx, line) in input.lines().enumerate() {
+        let halves = fields(line, "|");
+        if halves.len() != 2 {
+            errors.push(ParseError::at(idx, 0, "expected `patterns | output value`"));
+            continue;
+        }
+
+        let (patterns, output) = (words(halves[0].1), words(halves[1].1));
+
+        let malformed = check_digits(&patterns, 10, idx, halves[0].0)
+            .into_iter()
+            .chain(check_digits(&output, 4, idx, halves[1].0))
+            .collect::<Vec<ParseError>>();
+
+        if !malformed.is_empty() {
+            errors.extend(malformed);
+            continue;
+        }
+
+        let patterns = patterns
+            .iter()
+            .map(|(_, pattern)| *pattern)
+            .collect::<Vec<&str>>();
+
+        if !wiring_exists(&patterns) {
+            errors.push(ParseError::at(
+                idx,
+                0,
+                "patterns aren't the digits 0 through 9 under any wiring",
+            ));
+            continue;
+        }
+
+        for (col_idx, digit) in output {
+            if !patterns.iter().any(|pattern| mask(pattern) == mask(digit)) {
+                errors.push(ParseError::at(
+                    idx,
+                    halves[1].0 + col_idx,
+                    format!("{:?} isn't one of the patterns", digit),
+                ));
+            }
+        }
+    }
+
+    errors
+}
+
+/// signal patterns and output value for each line
+pub(crate) fn parse(input: &str) -> Result<Vec<(&str, &str)>, ParseError> {
+    ensure_valid(validate(input))?;
+
+    Ok(input
+        .lines()
+        .map(|line| line.split_once('|').unwrap())
+        .collect())
+}
+
+pub fn part_one(input: &str) -> Result<i32, ParseError> {
+    count_unique(input, &mut ())
+}
+
+pub fn part_two(input: &str) -> Result<i32, ParseError> {
+    decode(input, &mut ())
+}
+
+/// how many output digits light a number of segments no other digit does, explaining each
+pub(crate) fn count_unique(input: &str, sink: &mut dyn Sink) -> Result<i32, ParseError> {
+    let mut known = HashMap::new(); // unused for part 1
+
+    // discard signal patterns
+    let count = parse(input)?
+        .iter()
+        .enumerate()
+        .fold(0, |mut acc, (idx, (_, output_value))| {
+            acc += output_value
+                .split_whitespace()
+                .filter(|ov| {
+                    let (digit, why) = determine_digit(ov, &mut known, Solver::PartOne);
+                    if digit >= 0 {
+                        sink.emit(
+                            Event::new("unique")
+                                .with("line", idx + 1)
+                                .with("pattern", *ov)
+                                .with("digit", digit)
+                                .with("why", why),
+                        );
+                    }
+                    digit > 0
+                })
+                .count();
+            acc
+        });
+
+    Operation::new(8, Solver::PartOne, "unique digit count").narrow(count)
+}
+
+/// sum of the output values, explaining which digit each pattern decoded to and why
+pub(crate) fn decode(input: &str, sink: &mut dyn Sink) -> Result<i32, ParseError> {
+    let sum = Operation::new(8, Solver::PartTwo, "output value sum");
+    let mut result = 0;
+
+    for (idx, (signal_patterns, output_value)) in parse(input)?.into_iter().enumerate() {
+        let mut known = HashMap::new(); // reset known for each new input
+
+        let mut signal_patterns = signal_patterns.split_whitespace().collect::<Vec<&str>>();
+
+        // sort the signals so we process 1 first (len 2), 7 second (len 3), etc...
+        signal_patterns.sort_by_key(|left| left.len());
+
+        for sp in signal_patterns {
+            let (digit, why) = determine_digit(sp, &mut known, Solver::PartTwo);
+            sink.emit(
+                Event::new("decode")
+                    .with("line", idx + 1)
+                    .with("pattern", sp)
+                    .with("digit", digit)
+                    .with("why", why),
+            );
+        }
+
+        // go from "fgae fg ..." -> 41..
+        let current = output_value
+            .split_whitespace()
+            .fold(String::new(), |mut acc, ov| {
+                let sorted = sort_signals(ov);
+                acc.push_str(&format!("{}", known.get(&sorted).unwrap().capacity()));
+                acc
+            })
+            .parse::<i32>()
+            .unwrap();
+
+        sink.emit(
+            Event::new("display")
+                .with("line", idx + 1)
+                .with("output", output_value.trim())
+                .with("value", current),
+        );
+        result = sum.add(result, current)?;
+    }
+
+    Ok(result)
+}
+
+/// segments lit for each digit, 0 through 9, when the display is wired correctly
+const DIGITS: [&str; 10] = [
+    "abcefg", "cf", "acdeg", "acdfg", "bcdf", "abdfg", "abdefg", "acf", "abcdefg", "abcdfg",
+];
+
+/// `size` displays, each with its own random wiring, all ten patterns and four output digits
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| {
+            let mut wiring = ('a'..='g').collect::<Vec<char>>();
+            rng.shuffle(&mut wiring);
+
+            let scramble = |rng: &mut Rng, digit: usize| {
+                let mut segments = DIGITS[digit]
+                    .chars()
+                    .map(|ch| wiring[ch as usize - 'a' as usize])
+                    .collect::<Vec<char>>();
+                rng.shuffle(&mut segments);
+                String::from_iter(segments)
+            };
+
+            let mut digits = (0..10).collect::<Vec<usize>>();
+            rng.shuffle(&mut digits);
+
+            let patterns = digits
+                .iter()
+                .map(|digit| scramble(rng, *digit))
+                .collect::<Vec<String>>();
+            let output = (0..4)
+                .map(|_| {
+                    let digit = rng.below(10);
+                    scramble(rng, digit)
+                })
+                .collect::<Vec<String>>();
+
+            format!("{} | {}", patterns.join(" "), output.join(" "))
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+pub const EXAMPLE: &str =
+    "be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb | fdgacbe cefdb cefbgd gcbe
+edbfga begcd cbg gc gcadebf fbgde acbgfd abcde gfcbed gfec | fcgedb cgb dgebacf gc
+fgaebd cg bdaec gdafb agbcfd gdcbef bgcad gfac gcb cdgabef | cg cg fdcagb cbg
+fbegcd cbd adcefb dageb afcb bc aefdc ecdab fgdeca fcdbega | efabcd cedba gadfec cb
+aecbfdg fbg gf bafeg dbefa fcge gcbea fcaegb dgceab fcbdga | gecf egdcabf bgf bfgea
+fgeab ca afcebg bdacfeg cfaedg gcfdb baec bfadeg bafgc acf | gebdcfa ecba ca fadegcb
+dbcfg fgd bdegcaf fgec aegbdf ecdfab fbedc dacgb gdcebf gf | cefg dcbef fcge gbcadfe
+bdfegc cbegaf gecbf dfcage bdacg ed bedf ced adcbefg gebcd | ed bcgafe cdgba cbgef
+egadfb cdbfeg cegd fecab cgb gbdefca cg fgcdab egfdb bfceg | gbdfcae bgc cg cgb
+gcafb gcf dcaebfg ecagb gf abcdeg gaef cafbge fdbac fegbdc | fgae cfgab fg bagce";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_one() {
+        assert_eq!(Ok(26), part_one(EXAMPLE));
+    }
+
+    #[test]
+    fn test_two() {
+        assert_eq!(Ok(61229), part_two(EXAMPLE));
+    }
+
+    #[test]
+    fn test_explain() {
+        let mut events = Vec::new();
+        assert_eq!(Ok(26), count_unique(EXAMPLE, &mut events));
+        assert_eq!(26, events.len());
+        assert_eq!(
+            "unique: line=1 pattern=fdgacbe digit=8 why=\"only 8 lights all 7 segments\"",
+            events[0].text()
+        );
+
+        // every pattern of every line decoded, then the line's output value
+        let mut events = Vec::new();
+        assert_eq!(Ok(61229), decode(EXAMPLE, &mut events));
+        assert_eq!(110, events.len());
+        assert_eq!(
+            "decode: line=1 pattern=be digit=1 why=\"only 1 lights 2 segments\"",
+            events[0].text()
+        );
+        assert_eq!(
+            "display: line=1 output=\"fdgacbe cefdb cefbgd gcbe\" value=8394",
+            events[10].text()
+        );
+        assert!(events
+            .iter()
+            .any(|event| event.text().contains(" digit=0 ")));
+        assert!(!events
+            .iter()
+            .any(|event| event.text().contains(" digit=-1 ")));
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(validate(EXAMPLE).is_empty());
+
+        // two patterns swapped between halves, so 1 is missing from the patterns
+        let line = "be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd fdgacbe | edb cefdb cefbgd gcbe";
+        assert_eq!(
+            vec![ParseError::at(
+                0,
+                0,
+                "patterns aren't the digits 0 through 9 under any wiring"
+            )],
+            validate(line)
+        );
+
+        let line =
+            "be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb | fdgacbe cefdx cefbgd";
+        assert_eq!(
+            vec![
+                ParseError::at(0, 60, "expected 4 digits, found 3"),
+                ParseError::at(0, 73, "'x' isn't a segment"),
+            ],
+            validate(line)
+        );
+    }
+}
@@ -0,0 +1,362 @@
+use crate::checked::Operation;
+use crate::error::ParseError;
+use crate::image::{ColorMap, Image};
+use crate::rng::Rng;
+use crate::validate::{bad_chars, ensure_valid, rectangular};
+use crate::Solver;
+use std::collections::{HashSet, VecDeque};
+
+/// heights need to be digits, laid out in a rectangle
+pub fn validate(input: &str) -> Vec<ParseError> {
+    let mut errors = rectangular(input);
+
+    for (idx, line) in input.lines().enumerate() {
+        errors.extend(bad_chars(line, idx, |ch| ch.is_ascii_digit(), "a height"));
+    }
+
+    errors
+}
+
+pub(crate) fn get_heightmap(input: &str) -> Result<Vec<Vec<i32>>, ParseError> {
+    ensure_valid(validate(input))?;
+
+    let mut heightmap = Vec::new();
+
+    for (idx, line) in input.lines().enumerate() {
+        heightmap.push(Vec::new());
+        for ch in line.chars() {
+            heightmap[idx].push(ch.to_digit(10).unwrap() as i32)
+        }
+    }
+
+    Ok(heightmap)
+}
+
+fn is_lowpoint(row: usize, col: usize, map: &[Vec<i32>]) -> bool {
+    // we'll assume access to row/col is valid
+    let current = map[row][col];
+
+    // access to adjacent points can fail, but we can reuse row and col; even so we can underflow
+    // the usize for row/col, so that needs to be handled as well as the actual map access
+    if let Some(right) = map[row].get(col + 1) {
+        if *right < current {
+            return false;
+        }
+    }
+
+    if let Some(checked) = col.checked_sub(1) {
+        if let Some(left) = map[row].get(checked) {
+            if *left < current {
+                return false;
+            }
+        }
+    }
+
+    if let Some(checked) = row.checked_sub(1) {
+        if let Some(up) = map.get(checked) {
+            if up[col] < current {
+                return false;
+            }
+        }
+    }
+
+    if let Some(down) = map.get(row + 1) {
+        if down[col] < current {
+            return false;
+        }
+    }
+
+    true
+}
+
+fn get_basin(
+    map: &[Vec<i32>],
+    visited: &mut HashSet<(usize, usize)>,
+    to_visit: &mut Vec<(usize, usize)>,
+) {
+    // similar to lowpoint, need to handle OOB array access as well as underflows
+    while let Some((row, col)) = to_visit.pop() {
+        // as long as we have places to go, keep looping; grab the next place to check, and mark
+        // it as visited
+        visited.insert((row, col));
+
+        if let Some(checked_row) = row.checked_sub(1) {
+            // row isn't 0
+            if let Some(up) = map.get(checked_row) {
+                // can go up a row
+                if up[col] != 9 && !visited.contains(&(checked_row, col)) {
+                    // value above isn't 9, add it to to_visit list
+                    to_visit.push((checked_row, col));
+                }
+            }
+        }
+
+        if let Some(down) = map.get(row + 1) {
+            // can go down a row
+            if down[col] != 9 && !visited.contains(&(row + 1, col)) {
+                to_visit.push((row + 1, col));
+            }
+        }
+
+        if let Some(checked_col) = col.checked_sub(1) {
+            // col isn't zero
+            if let Some(left) = map[row].get(checked_col) {
+                // can go left a column
+                if *left != 9 && !visited.contains(&(row, checked_col)) {
+                    to_visit.push((row, checked_col));
+                }
+            }
+        }
+
+        if let Some(right) = map[row].get(col + 1) {
+            // can go right a column
+            if *right != 9 && !visited.contains(&(row, col + 1)) {
+                to_visit.push((row, col + 1));
+            }
+        }
+    }
+}
+
+pub fn part_one(input: &str) -> Result<i32, ParseError> {
+    let heightmap = get_heightmap(input)?;
+    let mut risk_levels = Vec::new();
+
+    for i in 0..heightmap.len() {
+        for j in 0..heightmap[0].len() {
+            if is_lowpoint(i, j, &heightmap) {
+                risk_levels.push(heightmap[i][j] + 1); // store height + 1 for risk level
+            }
+        }
+    }
+
+    let sum = Operation::new(9, Solver::PartOne, "risk level sum");
+    risk_levels
+        .iter()
+        .try_fold(0, |acc, risk| sum.add(acc, *risk))
+}
+
+pub fn part_two(input: &str) -> Result<i32, ParseError> {
+    let heightmap = get_heightmap(input)?;
+    let mut to_visit = Vec::new();
+    let mut visited = HashSet::new();
+    let mut basins = Vec::new();
+
+    for i in 0..heightmap.len() {
+        for j in 0..heightmap[0].len() {
+            if is_lowpoint(i, j, &heightmap) {
+                to_visit.push((i, j)); // add lowpoint as a place of interest
+
+                // get_basin only operates on a single lowpoint at a time
+                get_basin(&heightmap, &mut visited, &mut to_visit);
+
+                basins.push(visited.len());
+
+                // to_visit is cleared when get_basin finishes, no need to clean it up as well
+                visited.clear();
+            }
+        }
+    }
+
+    if basins.len() < 3 {
+        return Err(ParseError::overall(format!(
+            "expected at least three basins, found {}",
+            basins.len()
+        )));
+    }
+
+    basins.sort_unstable();
+    basins.reverse(); // big ones up front
+
+    let product = Operation::new(9, Solver::PartTwo, "basin size product");
+    basins[..3]
+        .iter()
+        .try_fold(1, |acc, basin| product.mul(acc, product.narrow(*basin)?))
+}
+
+/// part one's heightmap in shades of gray with the low points in red, or every basin in its own
+/// color for part two
+pub(crate) fn picture(input: &str, solver: Solver) -> Result<Image, ParseError> {
+    let heightmap = get_heightmap(input)?;
+    let (rows, cols) = (heightmap.len(), heightmap[0].len());
+
+    if solver == Solver::PartOne {
+        let heights = heightmap
+            .iter()
+            .map(|row| row.iter().map(|&height| height as u32).collect())
+            .collect::<Vec<Vec<u32>>>();
+        let mut image = Image::render(&heights, ColorMap::Gray);
+
+        for i in 0..rows {
+            for j in 0..cols {
+                if is_lowpoint(i, j, &heightmap) {
+                    image.set(j, i, [255, 0, 0]);
+                }
+            }
+        }
+
+        return Ok(image);
+    }
+
+    // 0 for the 9s between basins, then a number per basin
+    let mut basins = vec![vec![0; cols]; rows];
+    let mut visited = HashSet::new();
+    let mut count = 0;
+
+    for i in 0..rows {
+        for j in 0..cols {
+            if is_lowpoint(i, j, &heightmap) {
+                count += 1;
+                get_basin(&heightmap, &mut visited, &mut vec![(i, j)]);
+
+                for (row, col) in visited.drain() {
+                    basins[row][col] = count;
+                }
+            }
+        }
+    }
+
+    Ok(Image::render(&basins, ColorMap::Categories))
+}
+
+/// attempt at a `rows` x `cols` heightmap where every basin is walled off by 9s and flows down to
+/// exactly one low point; None when fewer than the three basins part two needs survive
+fn try_generate(rng: &mut Rng, rows: usize, cols: usize) -> Option<String> {
+    let neighbors = |(row, col): (usize, usize)| {
+        let mut result = Vec::new();
+        if row > 0 {
+            result.push((row - 1, col));
+        }
+        if row + 1 < rows {
+            result.push((row + 1, col));
+        }
+        if col > 0 {
+            result.push((row, col - 1));
+        }
+        if col + 1 < cols {
+            result.push((row, col + 1));
+        }
+        result
+    };
+
+    // grow every basin outwards from its low point at the same rate, claiming unowned locations
+    let mut owner = vec![vec![None; cols]; rows];
+    let mut to_visit = VecDeque::new();
+    let mut seeds = Vec::new();
+
+    for _ in 0..(rows * cols / 12).max(3) {
+        let seed = (rng.below(rows), rng.below(cols));
+        if owner[seed.0][seed.1].is_none() {
+            owner[seed.0][seed.1] = Some(seeds.len());
+            seeds.push(seed);
+            to_visit.push_back(seed);
+        }
+    }
+
+    while let Some(current) = to_visit.pop_front() {
+        for (row, col) in neighbors(current) {
+            if owner[row][col].is_none() {
+                owner[row][col] = owner[current.0][current.1];
+                to_visit.push_back((row, col));
+            }
+        }
+    }
+
+    // wall off locations that touch another basin
+    let walls = (0..rows)
+        .map(|row| {
+            (0..cols)
+                .map(|col| {
+                    neighbors((row, col))
+                        .iter()
+                        .any(|(r, c)| owner[*r][*c] != owner[row][col])
+                })
+                .collect::<Vec<bool>>()
+        })
+        .collect::<Vec<Vec<bool>>>();
+
+    // height is the distance from the low point, anything too far away becomes part of the wall
+    let mut heights = vec![vec![9; cols]; rows];
+    let mut basins = 0;
+
+    for seed in seeds.iter().filter(|(row, col)| !walls[*row][*col]) {
+        basins += 1;
+        heights[seed.0][seed.1] = 0;
+        to_visit.push_back(*seed);
+
+        while let Some(current) = to_visit.pop_front() {
+            let height = heights[current.0][current.1];
+            if height == 8 {
+                continue;
+            }
+
+            for (row, col) in neighbors(current) {
+                if !walls[row][col] && heights[row][col] == 9 && (row, col) != *seed {
+                    heights[row][col] = height + 1;
+                    to_visit.push_back((row, col));
+                }
+            }
+        }
+    }
+
+    if basins < 3 {
+        return None;
+    }
+
+    Some(
+        heights
+            .iter()
+            .map(|row| row.iter().map(|h| h.to_string()).collect::<String>())
+            .collect::<Vec<String>>()
+            .join("\n"),
+    )
+}
+
+/// `size` x `size` heightmap (at least 5x5), with at least three basins
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(5);
+
+    loop {
+        if let Some(heightmap) = try_generate(rng, size, size) {
+            return heightmap;
+        }
+    }
+}
+
+pub const EXAMPLE: &str = "2199943210
+3987894921
+9856789892
+8767896789
+9899965678";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_one() {
+        assert_eq!(Ok(15), part_one(EXAMPLE));
+    }
+
+    #[test]
+    fn test_two() {
+        assert_eq!(Ok(1134), part_two(EXAMPLE));
+        assert_eq!(
+            Err(ParseError::overall(
+                "expected at least three basins, found 2"
+            )),
+            part_two("9112")
+        );
+    }
+
+    #[test]
+    fn test_picture() {
+        let image = picture(EXAMPLE, Solver::PartOne).unwrap();
+        let red = image.pixels.iter().filter(|&&pixel| pixel == [255, 0, 0]);
+        assert_eq!(4, red.count());
+
+        // the four basins, and the 9s between them
+        let image = picture(EXAMPLE, Solver::PartTwo).unwrap();
+        let colors = image.pixels.iter().collect::<HashSet<_>>();
+        assert_eq!(5, colors.len());
+        assert_eq!([0, 0, 0], image.pixels[2]);
+    }
+}
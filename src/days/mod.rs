@@ -0,0 +1,741 @@
+use crate::error::ParseError;
+use crate::explain::Sink;
+use crate::image::Image;
+use crate::rng::Rng;
+use crate::simulation::Simulation;
+use crate::visualize::{Frame, Visualize};
+use crate::{Answer, Solver};
+use std::io::BufRead;
+
+pub mod day_1;
+pub mod day_10;
+pub mod day_11;
+pub mod day_12;
+pub mod day_13;
+pub mod day_14;
+pub mod day_15;
+pub mod day_17;
+pub mod day_2;
+pub mod day_3;
+pub mod day_4;
+pub mod day_5;
+pub mod day_6;
+pub mod day_7;
+pub mod day_8;
+pub mod day_9;
+
+/// signature shared by every part of every day, once wrapped up for the registry
+pub type Solution = fn(&str) -> Result<Answer, ParseError>;
+
+/// a day's input parser, minus whatever it parsed into; only good for checking an input
+pub type InputParser = fn(&str) -> Result<(), ParseError>;
+
+/// checks the structure of a day's input up front, reporting every problem it finds (with line
+/// and column) rather than stopping at the first; anything it passes, the day's parser accepts
+pub type Validator = fn(&str) -> Vec<ParseError>;
+
+/// produces a random, but valid, input for a day; `size` loosely scales the input (lines, grid
+/// width, etc)
+pub type Generator = fn(&mut Rng, usize) -> String;
+
+/// frames of a day's simulation solving the given part, see `--visualize`
+pub type Visualizer = fn(&str, Solver) -> Result<Vec<Frame>, ParseError>;
+
+/// picture of a day's puzzle for the given part, i.e. day 5's vents as a heatmap, see `aoc image`
+pub type Painter = fn(&str, Solver) -> Result<Image, ParseError>;
+
+/// a day's simulation, fresh from the input and ready to step through, see `aoc repl`
+pub type Simulator = fn(&str) -> Result<Box<dyn Simulation + '_>, ParseError>;
+
+/// solver that reads its input a line at a time instead of all at once, see `aoc run --stream`
+pub type StreamSolution = fn(&mut dyn BufRead) -> Result<Answer, ParseError>;
+
+/// solver that narrates each step to the sink as it goes, see `--explain`
+pub type Explainer = fn(&str, Solver, &mut dyn Sink) -> Result<Answer, ParseError>;
+
+/// a day's streaming solvers, for days that only need a single pass over their input
+#[derive(Copy, Clone, Debug)]
+pub struct Streaming {
+    pub part_one: StreamSolution,
+    pub part_two: StreamSolution,
+}
+
+impl Streaming {
+    /// streaming solver for the given part
+    pub fn solution(&self, solver: Solver) -> StreamSolution {
+        match solver {
+            Solver::PartOne => self.part_one,
+            Solver::PartTwo => self.part_two,
+        }
+    }
+}
+
+/// puzzle example along with the answers the puzzle text gives for it
+#[derive(Copy, Clone, Debug)]
+pub struct Example {
+    pub input: &'static str,
+    pub part_one: Option<&'static str>,
+    pub part_two: Option<&'static str>,
+}
+
+impl Example {
+    /// expected answer for the given part, if the example has one
+    pub fn expected(&self, solver: Solver) -> Option<&'static str> {
+        match solver {
+            Solver::PartOne => self.part_one,
+            Solver::PartTwo => self.part_two,
+        }
+    }
+}
+
+/// name of the implementation in a day's part_one/part_two
+pub const DEFAULT_IMPL: &str = "default";
+
+/// another way of solving one of a day's parts, kept around to compare against the default
+#[derive(Copy, Clone, Debug)]
+pub struct Implementation {
+    pub name: &'static str,
+    pub solver: Solver,
+    pub solve: Solution,
+}
+
+/// a solved (or at least attempted) day
+#[derive(Copy, Clone, Debug)]
+pub struct Day {
+    pub number: u32,
+    pub parse: InputParser,
+    pub validate: Validator,
+    pub part_one: Solution,
+    pub part_two: Solution,
+    pub examples: &'static [Example],
+    pub generate: Generator,
+    pub alternatives: &'static [Implementation],
+    pub visualize: Option<Visualizer>,
+    pub picture: Option<Painter>,
+    pub simulate: Option<Simulator>,
+    pub streaming: Option<Streaming>,
+    pub explain: Option<Explainer>,
+}
+
+impl Day {
+    /// solver for the given part
+    pub fn solution(&self, solver: Solver) -> Solution {
+        match solver {
+            Solver::PartOne => self.part_one,
+            Solver::PartTwo => self.part_two,
+        }
+    }
+
+    /// every implementation of the given part, default first
+    pub fn implementations(&self, solver: Solver) -> Vec<Implementation> {
+        let default = Implementation {
+            name: DEFAULT_IMPL,
+            solver,
+            solve: self.solution(solver),
+        };
+
+        std::iter::once(default)
+            .chain(
+                self.alternatives
+                    .iter()
+                    .filter(|alternative| alternative.solver == solver)
+                    .copied(),
+            )
+            .collect()
+    }
+
+    /// the named implementation of the given part
+    pub fn implementation(&self, solver: Solver, name: &str) -> Option<Solution> {
+        self.implementations(solver)
+            .into_iter()
+            .find(|implementation| implementation.name == name)
+            .map(|implementation| implementation.solve)
+    }
+
+    /// whether any example checks the given part
+    pub fn has_example(&self, solver: Solver) -> bool {
+        self.examples
+            .iter()
+            .any(|example| example.expected(solver).is_some())
+    }
+}
+
+/// every day with a solution, in order
+pub static DAYS: &[Day] = &[
+    Day {
+        number: 1,
+        parse: |input| day_1::parse(input).map(drop),
+        validate: day_1::validate,
+        part_one: |input| day_1::part_one(input).map(Answer::from),
+        part_two: |input| day_1::part_two(input).map(Answer::from),
+        generate: day_1::generate,
+        examples: &[],
+        alternatives: &[],
+        visualize: None,
+        picture: None,
+        simulate: None,
+        streaming: Some(Streaming {
+            part_one: |reader| day_1::stream_part_one(reader).map(Answer::from),
+            part_two: |reader| day_1::stream_part_two(reader).map(Answer::from),
+        }),
+        explain: None,
+    },
+    Day {
+        number: 2,
+        parse: |input| day_2::parse(input).map(drop),
+        validate: day_2::validate,
+        part_one: |input| day_2::part_one(input).map(Answer::from),
+        part_two: |input| day_2::part_two(input).map(Answer::from),
+        generate: day_2::generate,
+        examples: &[],
+        alternatives: &[],
+        visualize: None,
+        picture: None,
+        simulate: None,
+        streaming: Some(Streaming {
+            part_one: |reader| day_2::stream_part_one(reader).map(Answer::from),
+            part_two: |reader| day_2::stream_part_two(reader).map(Answer::from),
+        }),
+        explain: None,
+    },
+    Day {
+        number: 3,
+        parse: |input| day_3::count_bits(input).map(drop),
+        validate: day_3::validate,
+        part_one: |input| day_3::part_one(input).map(Answer::from),
+        part_two: |input| day_3::part_two(input).map(Answer::from),
+        generate: day_3::generate,
+        examples: &[Example {
+            input: day_3::EXAMPLE,
+            part_one: None,
+            part_two: Some("230"),
+        }],
+        alternatives: &[],
+        visualize: None,
+        picture: None,
+        simulate: None,
+        streaming: None,
+        explain: None,
+    },
+    Day {
+        number: 4,
+        parse: |input| day_4::build_boards(input).map(drop),
+        validate: day_4::validate,
+        part_one: |input| day_4::part_one(input).map(Answer::from),
+        part_two: |input| day_4::part_two(input).map(Answer::from),
+        generate: day_4::generate,
+        examples: &[Example {
+            input: day_4::EXAMPLE,
+            part_one: Some("4512"),
+            part_two: Some("1924"),
+        }],
+        alternatives: &[],
+        visualize: Some(|input, solver| {
+            day_4::Bingo::new(input).map(|mut bingo| bingo.frames(solver))
+        }),
+        picture: None,
+        simulate: Some(|input| {
+            day_4::Bingo::new(input).map(|bingo| Box::new(bingo) as Box<dyn Simulation>)
+        }),
+        streaming: None,
+        explain: Some(|input, solver, sink| match solver {
+            Solver::PartOne => day_4::first_winner(input, sink).map(Answer::from),
+            Solver::PartTwo => day_4::last_winner(input, sink).map(Answer::from),
+        }),
+    },
+    Day {
+        number: 5,
+        parse: |input| day_5::Graph::new(input).map(drop),
+        validate: day_5::validate,
+        part_one: |input| day_5::part_one(input, Solver::PartOne).map(Answer::from),
+        part_two: |input| day_5::part_two(input, Solver::PartTwo).map(Answer::from),
+        generate: day_5::generate,
+        examples: &[Example {
+            input: day_5::EXAMPLE,
+            part_one: Some("5"),
+            part_two: Some("12"),
+        }],
+        alternatives: &[
+            Implementation {
+                name: "rasterize",
+                solver: Solver::PartOne,
+                solve: |input| day_5::rasterize(input, Solver::PartOne).map(Answer::from),
+            },
+            Implementation {
+                name: "rasterize",
+                solver: Solver::PartTwo,
+                solve: |input| day_5::rasterize(input, Solver::PartTwo).map(Answer::from),
+            },
+        ],
+        visualize: None,
+        picture: Some(day_5::heatmap),
+        simulate: None,
+        streaming: None,
+        explain: None,
+    },
+    Day {
+        number: 6,
+        parse: |input| day_6::parse(input).map(drop),
+        validate: day_6::validate,
+        part_one: |input| day_6::part_one(input).map(Answer::from),
+        part_two: |input| day_6::part_two(input).map(Answer::from),
+        generate: day_6::generate,
+        examples: &[Example {
+            input: day_6::EXAMPLE,
+            part_one: Some("5934"),
+            part_two: Some("26984457539"),
+        }],
+        // tracking every fish is hopeless for part two's 256 days
+        alternatives: &[Implementation {
+            name: "by-timer",
+            solver: Solver::PartOne,
+            solve: |input| {
+                day_6::parse(input).map(|timers| day_6::simulate_days(&timers, 80).into())
+            },
+        }],
+        visualize: None,
+        picture: None,
+        simulate: Some(|input| {
+            day_6::parse(input)
+                .map(|timers| Box::new(day_6::School::new(&timers)) as Box<dyn Simulation>)
+        }),
+        streaming: None,
+        explain: None,
+    },
+    Day {
+        number: 7,
+        parse: |input| day_7::parse(input).map(drop),
+        validate: day_7::validate,
+        part_one: |input| day_7::part_one(input, Solver::PartOne).map(Answer::from),
+        part_two: |input| day_7::part_two(input).map(Answer::from),
+        generate: day_7::generate,
+        examples: &[Example {
+            input: day_7::EXAMPLE,
+            part_one: Some("37"),
+            part_two: Some("168"),
+        }],
+        alternatives: &[],
+        visualize: None,
+        picture: None,
+        simulate: None,
+        streaming: None,
+        explain: None,
+    },
+    Day {
+        number: 8,
+        parse: |input| day_8::parse(input).map(drop),
+        validate: day_8::validate,
+        part_one: |input| day_8::part_one(input).map(Answer::from),
+        part_two: |input| day_8::part_two(input).map(Answer::from),
+        generate: day_8::generate,
+        examples: &[Example {
+            input: day_8::EXAMPLE,
+            part_one: Some("26"),
+            part_two: Some("61229"),
+        }],
+        alternatives: &[],
+        visualize: None,
+        picture: None,
+        simulate: None,
+        streaming: None,
+        explain: Some(|input, solver, sink| match solver {
+            Solver::PartOne => day_8::count_unique(input, sink).map(Answer::from),
+            Solver::PartTwo => day_8::decode(input, sink).map(Answer::from),
+        }),
+    },
+    Day {
+        number: 9,
+        parse: |input| day_9::get_heightmap(input).map(drop),
+        validate: day_9::validate,
+        part_one: |input| day_9::part_one(input).map(Answer::from),
+        part_two: |input| day_9::part_two(input).map(Answer::from),
+        generate: day_9::generate,
+        examples: &[Example {
+            input: day_9::EXAMPLE,
+            part_one: Some("15"),
+            part_two: Some("1134"),
+        }],
+        alternatives: &[],
+        visualize: None,
+        picture: Some(day_9::picture),
+        simulate: None,
+        streaming: None,
+        explain: None,
+    },
+    Day {
+        number: 10,
+        parse: |input| day_10::parse(input).map(drop),
+        validate: day_10::validate,
+        part_one: |input| day_10::part_one(input).map(Answer::from),
+        part_two: |input| day_10::part_two(input).map(Answer::from),
+        generate: day_10::generate,
+        examples: &[Example {
+            input: day_10::EXAMPLE,
+            part_one: Some("26397"),
+            part_two: Some("288957"),
+        }],
+        alternatives: &[],
+        visualize: None,
+        picture: None,
+        simulate: None,
+        streaming: None,
+        explain: Some(|input, solver, sink| match solver {
+            Solver::PartOne => day_10::syntax_score(input, sink).map(Answer::from),
+            Solver::PartTwo => day_10::completion_score(input, sink).map(Answer::from),
+        }),
+    },
+    Day {
+        number: 11,
+        parse: |input| day_11::Cave::new(input).map(drop),
+        validate: day_11::validate,
+        part_one: |input| day_11::part_one(input).map(Answer::from),
+        part_two: |input| day_11::part_two(input).map(Answer::from),
+        generate: day_11::generate,
+        examples: &[Example {
+            input: day_11::EXAMPLE,
+            part_one: Some("1656"),
+            part_two: Some("195"),
+        }],
+        alternatives: &[],
+        visualize: Some(|input, solver| {
+            day_11::Cave::new(input).map(|mut cave| cave.frames(solver))
+        }),
+        picture: None,
+        simulate: Some(|input| {
+            day_11::Cave::new(input).map(|cave| Box::new(cave) as Box<dyn Simulation>)
+        }),
+        streaming: None,
+        explain: None,
+    },
+    Day {
+        number: 12,
+        parse: |input| input.parse::<day_12::Caves>().map(drop),
+        validate: day_12::validate,
+        part_one: |input| day_12::part_one(input).map(Answer::from),
+        part_two: |input| day_12::part_two(input).map(Answer::from),
+        generate: day_12::generate,
+        // part two's example assertions are still commented out
+        examples: &[
+            Example {
+                input: day_12::EXAMPLE,
+                part_one: Some("10"),
+                part_two: None,
+            },
+            Example {
+                input: day_12::EXAMPLE_1,
+                part_one: Some("19"),
+                part_two: None,
+            },
+            Example {
+                input: day_12::EXAMPLE_2,
+                part_one: Some("226"),
+                part_two: None,
+            },
+        ],
+        alternatives: &[],
+        visualize: None,
+        picture: None,
+        simulate: None,
+        streaming: None,
+        explain: None,
+    },
+    Day {
+        number: 13,
+        parse: |input| day_13::parse(input).map(drop),
+        validate: day_13::validate,
+        part_one: |input| day_13::part_one(input).map(Answer::from),
+        part_two: |input| day_13::part_two(input).map(Answer::from),
+        generate: day_13::generate,
+        examples: &[Example {
+            input: day_13::EXAMPLE,
+            part_one: Some("17"),
+            part_two: Some(day_13::SQUARE),
+        }],
+        alternatives: &[],
+        visualize: Some(|input, solver| {
+            day_13::Origami::new(input).map(|mut origami| origami.frames(solver))
+        }),
+        picture: None,
+        simulate: Some(|input| {
+            day_13::Origami::new(input).map(|origami| Box::new(origami) as Box<dyn Simulation>)
+        }),
+        streaming: None,
+        explain: Some(|input, solver, sink| match solver {
+            Solver::PartOne => day_13::fold_once(input, sink).map(Answer::from),
+            Solver::PartTwo => day_13::fold_all(input, sink).map(Answer::from),
+        }),
+    },
+    Day {
+        number: 14,
+        parse: |input| day_14::parse(input).map(drop),
+        validate: day_14::validate,
+        part_one: |input| day_14::part_one(input).map(Answer::from),
+        part_two: |input| day_14::part_two(input).map(Answer::from),
+        generate: day_14::generate,
+        examples: &[Example {
+            input: day_14::EXAMPLE,
+            part_one: Some("1588"),
+            part_two: Some("2188189693529"),
+        }],
+        // building the whole polymer is hopeless for part two's 40 steps
+        alternatives: &[Implementation {
+            name: "count-pairs",
+            solver: Solver::PartOne,
+            solve: |input| day_14::count_pairs(input, 10).map(Answer::from),
+        }],
+        visualize: None,
+        picture: None,
+        simulate: None,
+        streaming: None,
+        explain: None,
+    },
+    Day {
+        number: 15,
+        parse: |input| day_15::parse(input).map(drop),
+        validate: day_15::validate,
+        part_one: |input| day_15::part_one(input).map(Answer::from),
+        part_two: |input| day_15::part_two(input).map(Answer::from),
+        generate: day_15::generate,
+        examples: &[Example {
+            input: day_15::EXAMPLE,
+            part_one: Some("40"),
+            part_two: None,
+        }],
+        alternatives: &[
+            Implementation {
+                name: "a-star",
+                solver: Solver::PartOne,
+                solve: |input| day_15::part_one_a_star(input).map(Answer::from),
+            },
+            Implementation {
+                name: "a-star",
+                solver: Solver::PartTwo,
+                solve: |input| day_15::part_two_a_star(input).map(Answer::from),
+            },
+        ],
+        visualize: Some(|input, solver| {
+            day_15::Cavern::new(input).map(|mut cavern| cavern.frames(solver))
+        }),
+        picture: Some(|input, solver| {
+            day_15::Cavern::new(input).map(|cavern| cavern.picture(solver))
+        }),
+        simulate: None,
+        streaming: None,
+        explain: None,
+    },
+    Day {
+        number: 17,
+        parse: |input| day_17::parse(input).map(drop),
+        validate: day_17::validate,
+        part_one: |input| day_17::part_one(input).map(Answer::from),
+        part_two: |input| day_17::part_two(input).map(Answer::from),
+        generate: day_17::generate,
+        examples: &[Example {
+            input: day_17::EXAMPLE,
+            part_one: Some("45"),
+            part_two: Some("112"),
+        }],
+        alternatives: &[],
+        visualize: Some(|input, solver| {
+            day_17::parse(input).map(|(lower_x, upper_x, lower_y, upper_y)| {
+                day_17::Probe::new(lower_x, upper_x, lower_y, upper_y).frames(solver)
+            })
+        }),
+        picture: None,
+        simulate: None,
+        streaming: None,
+        explain: None,
+    },
+];
+
+/// look up a day in the registry
+pub fn get(number: u32) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{fuzz, normalize};
+    use std::panic::{catch_unwind, resume_unwind, AssertUnwindSafe};
+
+    #[test]
+    fn test_examples() {
+        for day in DAYS {
+            for example in day.examples {
+                for solver in [Solver::PartOne, Solver::PartTwo] {
+                    if let Some(expected) = example.expected(solver) {
+                        let answer = (day.solution(solver))(example.input).unwrap();
+                        assert_eq!(expected, answer.to_string(), "day {}", day.number);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_windows_inputs() {
+        let mut rng = Rng::new(2021);
+
+        for day in DAYS {
+            let mut inputs = day
+                .examples
+                .iter()
+                .map(|example| example.input.to_string())
+                .collect::<Vec<String>>();
+            inputs.push((day.generate)(&mut rng, 3));
+
+            for input in &inputs {
+                // as saved by an editor that adds a byte order mark, crlf and stray whitespace
+                let edited = format!("\u{feff}{} \r\n\r\n", input.replace('\n', "\t\r\n"));
+
+                for solver in [Solver::PartOne, Solver::PartTwo] {
+                    assert_eq!(
+                        (day.solution(solver))(input),
+                        (day.solution(solver))(&normalize::normalize(&edited)),
+                        "day {}",
+                        day.number
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_explain() {
+        let mut rng = Rng::new(2021);
+
+        for day in DAYS {
+            let explain = match day.explain {
+                Some(explain) => explain,
+                None => continue,
+            };
+
+            // explaining shouldn't change the answer, and there's always something to explain
+            for input in [
+                day.examples[0].input.to_string(),
+                (day.generate)(&mut rng, 4),
+            ] {
+                for solver in [Solver::PartOne, Solver::PartTwo] {
+                    let mut events = Vec::new();
+                    assert_eq!(
+                        (day.solution(solver))(&input),
+                        explain(&input, solver, &mut events),
+                        "day {}",
+                        day.number
+                    );
+                    assert!(!events.is_empty(), "day {} explained nothing", day.number);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_generated_inputs() {
+        let mut rng = Rng::new(2021);
+
+        for day in DAYS {
+            for size in 1..=6 {
+                let input = (day.generate)(&mut rng, size);
+                let context = format!("day {} rejected its own input:\n{}", day.number, input);
+                (day.parse)(&input).expect(&context);
+                (day.part_one)(&input).expect(&context);
+                (day.part_two)(&input).expect(&context);
+            }
+        }
+    }
+
+    #[test]
+    fn test_alternatives() {
+        let mut rng = Rng::new(2021);
+
+        for day in DAYS.iter().filter(|day| !day.alternatives.is_empty()) {
+            let mut inputs = day
+                .examples
+                .iter()
+                .map(|example| example.input.to_string())
+                .collect::<Vec<String>>();
+            inputs.extend((1..=6).map(|size| (day.generate)(&mut rng, size)));
+
+            for input in &inputs {
+                for alternative in day.alternatives {
+                    let expected = (day.solution(alternative.solver))(input);
+                    let actual = (alternative.solve)(input);
+                    assert_eq!(
+                        expected, actual,
+                        "day {} {:?} {} disagrees with the default on:\n{}",
+                        day.number, alternative.solver, alternative.name, input
+                    );
+                }
+            }
+        }
+    }
+
+    /// longest input fuzz_solvers hands to a solver, in bytes
+    const FUZZ_SOLVER_LIMIT: usize = 2048;
+
+    /// inputs the day accepts, so mutations of them get past the first line or two
+    fn corpus(day: &Day, rng: &mut Rng) -> Vec<String> {
+        let mut corpus = day
+            .examples
+            .iter()
+            .map(|example| example.input.to_string())
+            .collect::<Vec<String>>();
+        corpus.extend((1..=4).map(|size| (day.generate)(rng, size)));
+        corpus
+    }
+
+    #[test]
+    fn test_fuzz_parsers() {
+        let mut rng = Rng::new(2021);
+
+        for day in DAYS {
+            let corpus = corpus(day, &mut rng);
+
+            fuzz::fuzz(&format!("day {} parser", day.number), &corpus, day.parse);
+
+            // validate needs to hold up to the same abuse, and agree with the parser about what's
+            // acceptable
+            fuzz::fuzz(&format!("day {} validator", day.number), &corpus, |input| {
+                let errors = (day.validate)(input);
+                match (errors.is_empty(), (day.parse)(input)) {
+                    (true, Err(e)) => panic!("validate passed, but parse failed with {}", e),
+                    _ => Ok::<(), ParseError>(()),
+                }
+            });
+        }
+    }
+
+    #[test]
+    fn test_fuzz_solvers() {
+        let mut rng = Rng::new(2021);
+
+        for day in DAYS {
+            let corpus = corpus(day, &mut rng);
+
+            for solver in [Solver::PartOne, Solver::PartTwo] {
+                let name = format!("day {} {:?}", day.number, solver);
+                fuzz::fuzz(&name, &corpus, |input| {
+                    // solving takes longer than parsing, so big mutations aren't worth the wait
+                    if input.len() > FUZZ_SOLVER_LIMIT {
+                        return Ok(());
+                    }
+                    let solve = AssertUnwindSafe(|| (day.solution(solver))(input).map(drop));
+                    if cfg!(feature = "checked") {
+                        return solve();
+                    }
+
+                    // without the feature an answer that overflows is meant to panic, so only
+                    // other panics count
+                    match catch_unwind(solve) {
+                        Ok(result) => result,
+                        Err(payload) => match payload.downcast_ref::<&str>() {
+                            Some(message) if message.contains("overflow") => Ok(()),
+                            _ => resume_unwind(payload),
+                        },
+                    }
+                });
+            }
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// problem with a day's input, along with where it was found
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseError {
+    /// one based, or zero when the problem isn't with any one line
+    pub line: usize,
+    pub column: Option<usize>,
+    pub message: String,
+}
+
+impl ParseError {
+    /// error on the given (zero based) line of input
+    pub fn new(idx: usize, message: impl Into<String>) -> Self {
+        Self {
+            line: idx + 1,
+            column: None,
+            message: message.into(),
+        }
+    }
+
+    /// error at the given (zero based) line and column of input
+    pub fn at(idx: usize, col_idx: usize, message: impl Into<String>) -> Self {
+        Self {
+            column: Some(col_idx + 1),
+            ..Self::new(idx, message)
+        }
+    }
+
+    /// error with the input as a whole, i.e. its answer being too big for an i32
+    pub fn overall(message: impl Into<String>) -> Self {
+        Self {
+            line: 0,
+            column: None,
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.column {
+            _ if self.line == 0 => write!(f, "{}", self.message),
+            Some(column) => write!(f, "line {}, column {}: {}", self.line, column, self.message),
+            None => write!(f, "line {}: {}", self.line, self.message),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// parse a single value found on the given (zero based) line of input
+pub fn parse_value<T>(value: &str, idx: usize) -> Result<T, ParseError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    value
+        .parse::<T>()
+        .map_err(|e| ParseError::new(idx, format!("couldn't parse {:?}: {}", value, e)))
+}
@@ -0,0 +1,69 @@
+use crate::days::{self, Day};
+
+/// year commands use when none is given
+pub const DEFAULT_YEAR: u32 = 2021;
+
+/// a year's advent of code, along with every day solved for it
+///
+/// a new year gets a module of its own laid out like `days` (with a registry of its days), an
+/// entry in `EVENTS`, and its inputs and answers under src/inputs/<year>/ and src/answers/<year>/
+#[derive(Copy, Clone, Debug)]
+pub struct Event {
+    pub year: u32,
+    pub days: &'static [Day],
+}
+
+impl Event {
+    /// look up one of the event's days
+    pub fn day(&self, number: u32) -> Option<&'static Day> {
+        self.days.iter().find(|day| day.number == number)
+    }
+}
+
+/// every event with solutions, in order
+pub static EVENTS: &[Event] = &[Event {
+    year: 2021,
+    days: days::DAYS,
+}];
+
+/// look up an event
+pub fn get(year: u32) -> Option<&'static Event> {
+    EVENTS.iter().find(|event| event.year == year)
+}
+
+/// look up an event, with an error listing the ones there are
+pub fn event(year: u32) -> Result<&'static Event, String> {
+    get(year).ok_or_else(|| {
+        let years = EVENTS
+            .iter()
+            .map(|event| event.year.to_string())
+            .collect::<Vec<String>>();
+        format!("no solutions for {} (try {})", year, years.join(" or "))
+    })
+}
+
+/// look up a day of an event, with an error saying which of the two is missing
+pub fn day(year: u32, number: u32) -> Result<&'static Day, String> {
+    event(year)?
+        .day(number)
+        .ok_or(format!("day {} of {} isn't implemented", number, year))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_day() {
+        assert_eq!(4, day(2021, 4).unwrap().number);
+        assert_eq!(
+            Err(String::from("day 16 of 2021 isn't implemented")),
+            day(2021, 16).map(|day| day.number)
+        );
+        assert_eq!(
+            Err(String::from("no solutions for 2015 (try 2021)")),
+            day(2015, 1).map(|day| day.number)
+        );
+        assert!(get(DEFAULT_YEAR).is_some());
+    }
+}
@@ -0,0 +1,139 @@
+use crate::config::Format;
+use crate::json::quote;
+use crate::Answer;
+
+/// one step of a solution, i.e. a bingo board winning, with the values that explain it
+///
+/// values are answers since they're the same thing, a number or some text
+#[derive(Clone, Debug, PartialEq)]
+pub struct Event {
+    pub step: &'static str,
+    pub fields: Vec<(&'static str, Answer)>,
+}
+
+impl Event {
+    pub fn new(step: &'static str) -> Self {
+        Self {
+            step,
+            fields: Vec::new(),
+        }
+    }
+
+    /// the event with another field, for building one up a field at a time
+    pub fn with(mut self, name: &'static str, value: impl Into<Answer>) -> Self {
+        self.fields.push((name, value.into()));
+        self
+    }
+
+    /// i.e. `win: board=3 call=24 score=4512`, with text quoted when it has spaces in it
+    pub fn text(&self) -> String {
+        let fields = self.fields.iter().map(|(name, value)| match value {
+            Answer::Text(text) if text.is_empty() || text.contains(char::is_whitespace) => {
+                format!(" {}={:?}", name, text)
+            }
+            value => format!(" {}={}", name, value),
+        });
+
+        format!("{}:{}", self.step, fields.collect::<String>())
+    }
+
+    /// i.e. `{"step":"win","board":3,"call":24,"score":4512}`
+    pub fn json(&self) -> String {
+        let fields = self
+            .fields
+            .iter()
+            .map(|(name, value)| format!(",{}:{}", quote(name), json_value(value)));
+
+        format!(
+            "{{\"step\":{}{}}}",
+            quote(self.step),
+            fields.collect::<String>()
+        )
+    }
+}
+
+/// where a solver narrates what it's doing, for `--explain`
+pub trait Sink {
+    fn emit(&mut self, event: Event);
+}
+
+/// solving without explaining anything
+impl Sink for () {
+    fn emit(&mut self, _: Event) {}
+}
+
+impl Sink for Vec<Event> {
+    fn emit(&mut self, event: Event) {
+        self.push(event);
+    }
+}
+
+/// a number as is, text as a string
+fn json_value(answer: &Answer) -> String {
+    match answer {
+        Answer::Number(number) => number.to_string(),
+        Answer::Text(text) => quote(text),
+    }
+}
+
+/// the events as a json array, an event per line
+pub fn json(events: &[Event]) -> String {
+    let events = events
+        .iter()
+        .map(|event| format!("  {}", event.json()))
+        .collect::<Vec<String>>();
+
+    match events.is_empty() {
+        true => String::from("[]"),
+        false => format!("[\n{}\n]", events.join(",\n")),
+    }
+}
+
+/// what `--explain` prints: the events a line each then the answer, or in json an object of both
+pub fn render(answer: &Answer, events: &[Event], format: Format) -> String {
+    match format {
+        Format::Text => events
+            .iter()
+            .map(|event| format!("{}\n", event.text()))
+            .chain(std::iter::once(format!("{}\n", answer)))
+            .collect(),
+        Format::Json => format!(
+            "{{\"answer\":{},\"events\":{}}}\n",
+            json_value(answer),
+            json(events)
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let event = Event::new("decode")
+            .with("pattern", "cdfbe")
+            .with("digit", 5)
+            .with("why", "has the two segments 4 has that 1 doesn't");
+
+        assert_eq!(
+            "decode: pattern=cdfbe digit=5 why=\"has the two segments 4 has that 1 doesn't\"",
+            event.text()
+        );
+        assert_eq!(
+            "[\n  {\"step\":\"decode\",\"pattern\":\"cdfbe\",\"digit\":5,\"why\":\"has the two segments 4 has that 1 doesn't\"},\n  {\"step\":\"done\"}\n]",
+            json(&[event, Event::new("done")])
+        );
+        assert_eq!("[]", json(&[]));
+
+        let events = [Event::new("fold").with("axis", "y").with("dots", 17)];
+        assert_eq!(
+            "fold: axis=y dots=17\n17\n",
+            render(&Answer::from(17), &events, Format::Text)
+        );
+        assert_eq!(
+            "{\"answer\":\"ABC\",\"events\":[\n  {\"step\":\"fold\",\"axis\":\"y\",\"dots\":17}\n]}\n",
+            render(&Answer::from("ABC"), &events, Format::Json)
+        );
+    }
+}
@@ -0,0 +1,396 @@
+use crate::events::{self, DEFAULT_YEAR};
+use crate::normalize::normalize;
+use crate::Solver;
+use std::ffi::c_char;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::slice;
+
+/// the answer was written to the output buffer
+pub const AOC_OK: i32 = 0;
+/// the day isn't in the year's registry
+pub const AOC_UNKNOWN_DAY: i32 = 1;
+/// the part wasn't 1 or 2
+pub const AOC_BAD_PART: i32 = 2;
+/// a pointer that's needed was null
+pub const AOC_NULL_POINTER: i32 = 3;
+/// the input isn't utf-8
+pub const AOC_BAD_UTF8: i32 = 4;
+/// the input couldn't be parsed; the output buffer holds as much of the error as fits instead of
+/// an answer
+pub const AOC_PARSE_ERROR: i32 = 5;
+/// the output buffer is too small; `out_len` is set to the size that's needed
+pub const AOC_BUFFER_TOO_SMALL: i32 = 6;
+/// the solver panicked
+pub const AOC_PANIC: i32 = 7;
+/// there are no solutions for the year
+pub const AOC_UNKNOWN_YEAR: i32 = 8;
+
+/// every error code with its name and a nul terminated description, in order
+const CODES: &[(i32, &str, &str)] = &[
+    (AOC_OK, "AOC_OK", "ok\0"),
+    (
+        AOC_UNKNOWN_DAY,
+        "AOC_UNKNOWN_DAY",
+        "day isn't implemented\0",
+    ),
+    (AOC_BAD_PART, "AOC_BAD_PART", "part needs to be 1 or 2\0"),
+    (AOC_NULL_POINTER, "AOC_NULL_POINTER", "null pointer\0"),
+    (AOC_BAD_UTF8, "AOC_BAD_UTF8", "input isn't utf-8\0"),
+    (
+        AOC_PARSE_ERROR,
+        "AOC_PARSE_ERROR",
+        "couldn't parse the input\0",
+    ),
+    (
+        AOC_BUFFER_TOO_SMALL,
+        "AOC_BUFFER_TOO_SMALL",
+        "output buffer is too small\0",
+    ),
+    (AOC_PANIC, "AOC_PANIC", "the solver panicked\0"),
+    (
+        AOC_UNKNOWN_YEAR,
+        "AOC_UNKNOWN_YEAR",
+        "year has no solutions\0",
+    ),
+];
+
+/// copy `text` into the buffer followed by a nul, setting `out_len` to its length without the nul
+///
+/// # Safety
+///
+/// see `aoc_solve`
+unsafe fn write_out(text: &str, out_buf: *mut u8, out_len: *mut usize, code: i32) -> i32 {
+    let capacity = *out_len;
+    *out_len = text.len() + 1;
+
+    if capacity <= text.len() {
+        return AOC_BUFFER_TOO_SMALL;
+    }
+
+    let out = slice::from_raw_parts_mut(out_buf, capacity);
+    out[..text.len()].copy_from_slice(text.as_bytes());
+    out[text.len()] = 0;
+    *out_len = text.len();
+
+    code
+}
+
+/// solve a part of a day of 2021, writing the answer into `out_buf` as a nul terminated string
+///
+/// `out_len` starts out as the size of `out_buf`, and ends up as the length of the answer
+/// (without the nul), or when the buffer's too small, the size it would need to be. the input is
+/// normalized like an input file, so crlf line endings or a byte order mark are fine
+///
+/// # Safety
+///
+/// `input_ptr` needs to point to `input_len` readable bytes, `out_len` to a readable and writable
+/// `size_t`, and `out_buf` to at least `*out_len` writable bytes
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve(
+    day: u32,
+    part: u32,
+    input_ptr: *const u8,
+    input_len: usize,
+    out_buf: *mut u8,
+    out_len: *mut usize,
+) -> i32 {
+    aoc_solve_year(
+        DEFAULT_YEAR,
+        day,
+        part,
+        input_ptr,
+        input_len,
+        out_buf,
+        out_len,
+    )
+}
+
+/// `aoc_solve` for a day of another year's event
+///
+/// # Safety
+///
+/// see `aoc_solve`
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve_year(
+    year: u32,
+    day: u32,
+    part: u32,
+    input_ptr: *const u8,
+    input_len: usize,
+    out_buf: *mut u8,
+    out_len: *mut usize,
+) -> i32 {
+    if input_ptr.is_null() || out_buf.is_null() || out_len.is_null() {
+        return AOC_NULL_POINTER;
+    }
+
+    let event = match events::get(year) {
+        Some(event) => event,
+        None => return AOC_UNKNOWN_YEAR,
+    };
+    let day = match event.day(day) {
+        Some(day) => day,
+        None => return AOC_UNKNOWN_DAY,
+    };
+    let solver = match part {
+        1 => Solver::PartOne,
+        2 => Solver::PartTwo,
+        _ => return AOC_BAD_PART,
+    };
+    let input = match std::str::from_utf8(slice::from_raw_parts(input_ptr, input_len)) {
+        Ok(input) => normalize(input),
+        Err(_) => return AOC_BAD_UTF8,
+    };
+
+    // unwinding across the boundary is undefined behaviour, so panics have to stop here
+    match catch_unwind(AssertUnwindSafe(|| day.solution(solver)(&input))) {
+        Ok(Ok(answer)) => write_out(&answer.to_string(), out_buf, out_len, AOC_OK),
+        Ok(Err(e)) => {
+            // the error's only there to be shown to someone, so as much as fits will do
+            let message = e.to_string();
+            let mut end = message.len().min((*out_len).saturating_sub(1));
+            while !message.is_char_boundary(end) {
+                end -= 1;
+            }
+            write_out(&message[..end], out_buf, out_len, AOC_PARSE_ERROR)
+        }
+        Err(_) => AOC_PANIC,
+    }
+}
+
+/// static, nul terminated description of an error code
+#[no_mangle]
+pub extern "C" fn aoc_strerror(code: i32) -> *const c_char {
+    let description = CODES
+        .iter()
+        .find(|(known, _, _)| *known == code)
+        .map_or("unknown error\0", |(_, _, description)| description);
+
+    description.as_ptr() as *const c_char
+}
+
+/// C header declaring everything above, kept in include/aoc.h; see `aoc header`
+pub fn header() -> String {
+    let mut result = String::from(
+        "/* generated by `aoc header`, don't edit by hand */\n\
+         #ifndef AOC_H\n\
+         #define AOC_H\n\
+         \n\
+         #include <stddef.h>\n\
+         #include <stdint.h>\n\
+         \n\
+         #ifdef __cplusplus\n\
+         extern \"C\" {\n\
+         #endif\n\
+         \n",
+    );
+
+    for (code, name, description) in CODES {
+        result.push_str(&format!(
+            "#define {} {} /* {} */\n",
+            name,
+            code,
+            description.trim_end_matches('\0')
+        ));
+    }
+
+    result.push_str(
+        "\n\
+         /*\n \
+         * solve a part of a day of 2021, writing the answer into out_buf as a nul terminated\n \
+         * string\n \
+         *\n \
+         * out_len starts out as the size of out_buf, and ends up as the length of the answer\n \
+         * (without the nul), or when the buffer's too small, the size it would need to be.\n \
+         * on AOC_PARSE_ERROR, out_buf holds as much of the error as fits instead of an answer.\n \
+         * the input is normalized like an input file, so crlf line endings or a byte order mark\n \
+         * are fine\n \
+         */\n\
+         int32_t aoc_solve(uint32_t day, uint32_t part, const uint8_t *input_ptr, size_t input_len,\n\
+         \x20                 uint8_t *out_buf, size_t *out_len);\n\
+         \n\
+         /* aoc_solve for a day of another year's event */\n\
+         int32_t aoc_solve_year(uint32_t year, uint32_t day, uint32_t part, const uint8_t *input_ptr,\n\
+         \x20                      size_t input_len, uint8_t *out_buf, size_t *out_len);\n\
+         \n\
+         /* static, nul terminated description of an error code */\n\
+         const char *aoc_strerror(int32_t code);\n\
+         \n\
+         #ifdef __cplusplus\n\
+         }\n\
+         #endif\n\
+         \n\
+         #endif /* AOC_H */\n",
+    );
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::day_17::EXAMPLE;
+    use std::ffi::CStr;
+    use std::fs::read_to_string;
+
+    // declared the way a C caller sees them, so the calls go through the C ABI
+    extern "C" {
+        #[link_name = "aoc_solve"]
+        fn c_aoc_solve(
+            day: u32,
+            part: u32,
+            input_ptr: *const u8,
+            input_len: usize,
+            out_buf: *mut u8,
+            out_len: *mut usize,
+        ) -> i32;
+
+        #[link_name = "aoc_solve_year"]
+        fn c_aoc_solve_year(
+            year: u32,
+            day: u32,
+            part: u32,
+            input_ptr: *const u8,
+            input_len: usize,
+            out_buf: *mut u8,
+            out_len: *mut usize,
+        ) -> i32;
+
+        #[link_name = "aoc_strerror"]
+        fn c_aoc_strerror(code: i32) -> *const c_char;
+    }
+
+    /// call aoc_solve with a buffer of the given size, returning the code and what it wrote
+    fn solve(day: u32, part: u32, input: &[u8], capacity: usize) -> (i32, usize, String) {
+        let mut out = vec![0xffu8; capacity.max(1)];
+        let mut out_len = capacity;
+
+        let code = unsafe {
+            c_aoc_solve(
+                day,
+                part,
+                input.as_ptr(),
+                input.len(),
+                out.as_mut_ptr(),
+                &mut out_len,
+            )
+        };
+
+        (code, out_len, written(&out))
+    }
+
+    /// call aoc_solve_year the same way
+    fn solve_year(
+        year: u32,
+        day: u32,
+        part: u32,
+        input: &[u8],
+        capacity: usize,
+    ) -> (i32, usize, String) {
+        let mut out = vec![0xffu8; capacity.max(1)];
+        let mut out_len = capacity;
+
+        let code = unsafe {
+            c_aoc_solve_year(
+                year,
+                day,
+                part,
+                input.as_ptr(),
+                input.len(),
+                out.as_mut_ptr(),
+                &mut out_len,
+            )
+        };
+
+        (code, out_len, written(&out))
+    }
+
+    /// the nul terminated text in the output buffer
+    fn written(out: &[u8]) -> String {
+        match out.iter().position(|&byte| byte == 0) {
+            Some(end) => String::from_utf8_lossy(&out[..end]).into_owned(),
+            None => String::new(),
+        }
+    }
+
+    #[test]
+    fn test_solve() {
+        assert_eq!(
+            (AOC_OK, 2, String::from("45")),
+            solve(17, 1, EXAMPLE.as_bytes(), 64)
+        );
+        assert_eq!(
+            (AOC_OK, 3, String::from("112")),
+            solve(17, 2, EXAMPLE.as_bytes(), 4)
+        );
+        assert_eq!(
+            (AOC_OK, 2, String::from("45")),
+            solve_year(2021, 17, 1, EXAMPLE.as_bytes(), 64)
+        );
+    }
+
+    #[test]
+    fn test_normalize() {
+        let input = format!("\u{feff}{}\r\n", EXAMPLE.replace('\n', " \r\n"));
+        assert_eq!(
+            (AOC_OK, 2, String::from("45")),
+            solve(17, 1, input.as_bytes(), 64)
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let input = EXAMPLE.as_bytes();
+
+        assert_eq!(AOC_UNKNOWN_DAY, solve(16, 1, input, 64).0);
+        assert_eq!(AOC_UNKNOWN_YEAR, solve_year(2020, 17, 1, input, 64).0);
+        assert_eq!(AOC_BAD_PART, solve(17, 3, input, 64).0);
+        assert_eq!(AOC_BAD_UTF8, solve(17, 1, b"\xff\xfe", 64).0);
+
+        // room for the answer, but not the nul
+        assert_eq!(
+            (AOC_BUFFER_TOO_SMALL, 4, String::new()),
+            solve(17, 2, input, 3)
+        );
+
+        let (code, _, message) = solve(17, 1, b"target area: x=1..2", 256);
+        assert_eq!(AOC_PARSE_ERROR, code);
+        assert!(message.starts_with("line 1"), "{}", message);
+
+        assert_eq!(
+            (AOC_PARSE_ERROR, 5, String::from("line ")),
+            solve(17, 1, b"target area: x=1..2", 6)
+        );
+
+        let code = unsafe {
+            c_aoc_solve(
+                17,
+                1,
+                input.as_ptr(),
+                input.len(),
+                std::ptr::null_mut(),
+                &mut 0,
+            )
+        };
+        assert_eq!(AOC_NULL_POINTER, code);
+    }
+
+    #[test]
+    fn test_strerror() {
+        for (code, _, description) in CODES {
+            let text = unsafe { CStr::from_ptr(c_aoc_strerror(*code)) };
+            assert_eq!(description.trim_end_matches('\0'), text.to_str().unwrap());
+        }
+        let text = unsafe { CStr::from_ptr(c_aoc_strerror(-1)) };
+        assert_eq!("unknown error", text.to_str().unwrap());
+    }
+
+    #[test]
+    fn test_header_is_current() {
+        assert_eq!(
+            header(),
+            read_to_string("include/aoc.h").unwrap_or_default(),
+            "include/aoc.h is out of date, regenerate it with `cargo run --bin aoc -- header > include/aoc.h`"
+        );
+    }
+}
@@ -0,0 +1,174 @@
+use crate::property::{env_or, evaluate};
+use crate::rng::Rng;
+
+/// number of mutated inputs per target, overridden by AOC_FUZZ_ITERATIONS
+const DEFAULT_ITERATIONS: usize = 1000;
+
+/// seed for the mutator, overridden by AOC_FUZZ_SEED
+const DEFAULT_SEED: u64 = 2021;
+
+/// most mutations stacked onto a single input
+const MAX_MUTATIONS: usize = 8;
+
+/// fragments that tend to trip up parsers: separators the days split on, keywords they look for,
+/// numbers at or past the edges of the integer types, and whitespace/encoding oddities
+const TOKENS: &[&str] = &[
+    "\n",
+    "\n\n",
+    "\r\n",
+    " ",
+    ",",
+    "-",
+    "->",
+    " -> ",
+    "|",
+    " | ",
+    "=",
+    "..",
+    "0",
+    "-1",
+    "9",
+    "10",
+    "2147483647",
+    "2147483648",
+    "-2147483649",
+    "18446744073709551616",
+    "99999999999999999999999999",
+    "start",
+    "end",
+    "fold along x=",
+    "fold along y=",
+    "target area: x=",
+    "forward ",
+    "\u{feff}",
+    "é",
+    "\0",
+];
+
+/// apply a single random edit to `bytes`, possibly splicing in part of another corpus entry
+fn mutate_once(rng: &mut Rng, bytes: &mut Vec<u8>, corpus: &[String]) {
+    let position = |rng: &mut Rng, len: usize| rng.below(len + 1);
+
+    match rng.below(7) {
+        0 if !bytes.is_empty() => {
+            // flip a bit
+            let idx = rng.below(bytes.len());
+            bytes[idx] ^= 1 << rng.below(8);
+        }
+        1 if !bytes.is_empty() => {
+            // replace a byte with something random
+            let idx = rng.below(bytes.len());
+            bytes[idx] = rng.below(256) as u8;
+        }
+        2 if !bytes.is_empty() => {
+            // delete a run of bytes
+            let start = rng.below(bytes.len());
+            let end = usize::min(bytes.len(), start + 1 + rng.below(16));
+            bytes.drain(start..end);
+        }
+        3 if !bytes.is_empty() => {
+            // duplicate a run of bytes somewhere else
+            let start = rng.below(bytes.len());
+            let end = usize::min(bytes.len(), start + 1 + rng.below(32));
+            let chunk = bytes[start..end].to_vec();
+            let at = position(rng, bytes.len());
+            bytes.splice(at..at, chunk);
+        }
+        4 => {
+            // splice in a chunk of another corpus entry
+            let other = rng.choose(corpus).as_bytes();
+            if !other.is_empty() {
+                let start = rng.below(other.len());
+                let end = usize::min(other.len(), start + 1 + rng.below(64));
+                let at = position(rng, bytes.len());
+                bytes.splice(at..at, other[start..end].iter().copied());
+            }
+        }
+        5 if !bytes.is_empty() => {
+            // overwrite a run of bytes with a token
+            let token = rng.choose(TOKENS).as_bytes();
+            let start = rng.below(bytes.len());
+            let end = usize::min(bytes.len(), start + token.len());
+            bytes.splice(start..end, token.iter().copied());
+        }
+        _ => {
+            // insert a token
+            let token = rng.choose(TOKENS).as_bytes();
+            let at = position(rng, bytes.len());
+            bytes.splice(at..at, token.iter().copied());
+        }
+    }
+}
+
+/// a corpus entry with a handful of random edits applied; invalid utf-8 is replaced rather than
+/// discarded, since the days only ever see text
+pub fn mutate(rng: &mut Rng, corpus: &[String]) -> String {
+    let mut bytes = rng.choose(corpus).as_bytes().to_vec();
+
+    for _ in 0..1 + rng.below(MAX_MUTATIONS) {
+        mutate_once(rng, &mut bytes, corpus);
+    }
+
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+/// feed mutated versions of `corpus` to `target`, panicking with the offending input if `target`
+/// ever panics (arithmetic overflow included, since tests run with overflow checks) instead of
+/// returning an error
+///
+/// `corpus` should be made up of valid inputs; the closer the mutations start to something the
+/// target accepts, the deeper into the parser they get
+pub fn fuzz<F, E>(name: &str, corpus: &[String], target: F)
+where
+    F: Fn(&str) -> Result<(), E>,
+{
+    assert!(!corpus.is_empty(), "fuzz target `{}` needs a corpus", name);
+
+    let iterations = env_or("AOC_FUZZ_ITERATIONS", DEFAULT_ITERATIONS);
+    let seed = env_or("AOC_FUZZ_SEED", DEFAULT_SEED);
+    let mut rng = Rng::new(seed);
+
+    for iteration in 0..iterations {
+        let input = mutate(&mut rng, corpus);
+
+        // an error is exactly what bad input should produce; only a panic is a failure
+        if let Err(error) = evaluate(&input, &|input: &String| {
+            let _ = target(input);
+            Ok(())
+        }) {
+            panic!(
+                "fuzz target `{}` failed on iteration {} (AOC_FUZZ_SEED={}): {}\ninput: {:?}",
+                name, iteration, seed, error, input
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mutate_deterministic() {
+        let corpus = vec![String::from("1,2\n3,4"), String::from("a-b")];
+        let (mut first, mut second) = (Rng::new(7), Rng::new(7));
+
+        for _ in 0..100 {
+            assert_eq!(mutate(&mut first, &corpus), mutate(&mut second, &corpus));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "fuzz target `unwrapping parser` failed")]
+    fn test_fuzz_catches_panics() {
+        let corpus = vec![String::from("1\n2\n3")];
+
+        fuzz("unwrapping parser", &corpus, |input| {
+            input
+                .lines()
+                .map(|line| line.parse::<u8>().unwrap())
+                .for_each(drop);
+            Ok::<(), String>(())
+        });
+    }
+}
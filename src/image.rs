@@ -0,0 +1,481 @@
+use crate::visualize::Frame;
+use std::collections::HashMap;
+use std::fs::write;
+use std::path::Path;
+
+/// red, green and blue
+pub type Rgb = [u8; 3];
+
+const BLACK: Rgb = [0, 0, 0];
+
+/// anything that can be drawn a cell to a pixel, i.e. a heightmap or how many lines cross each
+/// point
+pub trait Grid {
+    /// columns and rows
+    fn size(&self) -> (usize, usize);
+
+    /// value of the cell, which the color map turns into a color
+    fn value(&self, x: usize, y: usize) -> u32;
+
+    /// every value, row by row
+    fn values(&self) -> Vec<u32> {
+        let (width, height) = self.size();
+        (0..height)
+            .flat_map(|y| (0..width).map(move |x| self.value(x, y)))
+            .collect()
+    }
+}
+
+/// rows of values
+impl Grid for Vec<Vec<u32>> {
+    fn size(&self) -> (usize, usize) {
+        (self.first().map_or(0, Vec::len), self.len())
+    }
+
+    fn value(&self, x: usize, y: usize) -> u32 {
+        self[y].get(x).copied().unwrap_or_default()
+    }
+}
+
+/// a text frame, one cell per character, for `ColorMap::Text`
+impl Grid for Frame {
+    fn size(&self) -> (usize, usize) {
+        let width = self
+            .picture
+            .lines()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or_default();
+        (width, self.picture.lines().count())
+    }
+
+    fn value(&self, x: usize, y: usize) -> u32 {
+        self.picture
+            .lines()
+            .nth(y)
+            .and_then(|line| line.chars().nth(x))
+            .unwrap_or(' ') as u32
+    }
+
+    /// going a character at a time with `value` would mean scanning the picture for every one
+    fn values(&self) -> Vec<u32> {
+        let (width, _) = self.size();
+        self.picture
+            .lines()
+            .flat_map(|line| {
+                let padding = width - line.chars().count();
+                line.chars().chain(std::iter::repeat_n(' ', padding))
+            })
+            .map(|ch| ch as u32)
+            .collect()
+    }
+}
+
+/// how the values of a grid are turned into colors
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ColorMap {
+    /// black for 0, up to white for the largest value
+    Gray,
+    /// black through red and yellow to white, for counts
+    Heat,
+    /// a different color for every value, i.e. basin numbers, except 0 which is black
+    Categories,
+    /// characters of a text frame: digits shade from dark to light, and the marks the days use
+    /// (`#`, `+`, `T`, `XX` etc) stand out
+    Text,
+}
+
+impl ColorMap {
+    pub fn color(&self, value: u32, max: u32) -> Rgb {
+        let level = |value: u32| (value.min(max) as u64 * 255 / max.max(1) as u64) as u8;
+
+        match self {
+            ColorMap::Gray => [level(value); 3],
+            ColorMap::Heat => {
+                let level = level(value) as u32 * 3;
+                let channel = |offset: u32| level.saturating_sub(offset).min(255) as u8;
+                [channel(0), channel(255), channel(510)]
+            }
+            ColorMap::Categories if value == 0 => BLACK,
+            ColorMap::Categories => {
+                // golden angle steps around the hue circle keep neighbouring values apart
+                hue((value as u64 * 137) % 360)
+            }
+            ColorMap::Text => match char::from_u32(value).unwrap_or(' ') {
+                digit @ '0'..='9' => [40 + (digit as u8 - b'0') * 20; 3],
+                '#' => [255, 215, 0],
+                '+' => [0, 200, 255],
+                'T' => [200, 40, 40],
+                'S' | 'X' => [40, 200, 40],
+                '.' => [30, 30, 30],
+                ' ' => BLACK,
+                _ => [255; 3],
+            },
+        }
+    }
+}
+
+/// fully saturated color at the given angle around the hue circle
+fn hue(degrees: u64) -> Rgb {
+    let x = (255 * (60 - (degrees % 120).abs_diff(60)) / 60) as u8;
+
+    match degrees / 60 {
+        0 => [255, x, 0],
+        1 => [x, 255, 0],
+        2 => [0, 255, x],
+        3 => [0, x, 255],
+        4 => [x, 0, 255],
+        _ => [255, 0, x],
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Image {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<Rgb>,
+}
+
+impl Image {
+    /// a pixel per cell of the grid, colored relative to its largest value
+    pub fn render(grid: &impl Grid, colors: ColorMap) -> Self {
+        let (width, height) = grid.size();
+        let values = grid.values();
+        let max = values.iter().copied().max().unwrap_or_default();
+
+        Self {
+            width,
+            height,
+            pixels: values
+                .iter()
+                .map(|&value| colors.color(value, max))
+                .collect(),
+        }
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, color: Rgb) {
+        self.pixels[y * self.width + x] = color;
+    }
+
+    /// every pixel blown up into a `factor` x `factor` square
+    pub fn scaled(&self, factor: usize) -> Self {
+        let factor = factor.max(1);
+        let width = self.width * factor;
+
+        Self {
+            width,
+            height: self.height * factor,
+            pixels: (0..self.height * factor)
+                .flat_map(|y| {
+                    (0..width).map(move |x| self.pixels[(y / factor) * self.width + x / factor])
+                })
+                .collect(),
+        }
+    }
+
+    /// binary PGM, with each pixel's luminance as its gray level
+    pub fn pgm(&self) -> Vec<u8> {
+        let mut result = format!("P5\n{} {}\n255\n", self.width, self.height).into_bytes();
+        result.extend(
+            self.pixels.iter().map(|&[r, g, b]| {
+                ((r as u32 * 299 + g as u32 * 587 + b as u32 * 114) / 1000) as u8
+            }),
+        );
+        result
+    }
+
+    /// binary PPM
+    pub fn ppm(&self) -> Vec<u8> {
+        let mut result = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        result.extend(self.pixels.iter().flatten());
+        result
+    }
+}
+
+/// up to 256 colors covering every pixel of every image, black first since it's the background;
+/// images with more colors than that are squeezed into a 6x6x6 color cube
+fn palette(images: &[Image]) -> (Vec<Rgb>, HashMap<Rgb, u8>) {
+    let mut colors = vec![BLACK];
+    for pixel in images.iter().flat_map(|image| &image.pixels) {
+        if !colors.contains(pixel) {
+            colors.push(*pixel);
+            if colors.len() > 256 {
+                break;
+            }
+        }
+    }
+
+    if colors.len() <= 256 {
+        let indices = colors
+            .iter()
+            .enumerate()
+            .map(|(idx, color)| (*color, idx as u8))
+            .collect();
+        return (colors, indices);
+    }
+
+    let cube = (0..216u32)
+        .map(|idx| [idx / 36, idx / 6 % 6, idx % 6].map(|level| (level * 51) as u8))
+        .collect::<Vec<Rgb>>();
+    let indices = images
+        .iter()
+        .flat_map(|image| &image.pixels)
+        .map(|pixel| {
+            let [r, g, b] = pixel.map(|channel| ((channel as u32 + 25) / 51) as u8);
+            (*pixel, r * 36 + g * 6 + b)
+        })
+        .collect();
+
+    (cube, indices)
+}
+
+/// LZW data for a GIF image, without actually compressing anything
+///
+/// every index goes out as a literal 9 bit code, with a clear code often enough that the decoder's
+/// table never fills up to the point where codes would need a 10th bit
+fn lzw(indices: &[u8]) -> Vec<u8> {
+    const CLEAR: u32 = 256;
+    const END: u32 = 257;
+
+    let mut bytes = Vec::new();
+    let (mut buffer, mut bits) = (0u32, 0);
+    let mut push = |code: u32| {
+        buffer |= code << bits;
+        bits += 9;
+        while bits >= 8 {
+            bytes.push(buffer as u8);
+            buffer >>= 8;
+            bits -= 8;
+        }
+    };
+
+    for chunk in indices.chunks(254) {
+        push(CLEAR);
+        chunk.iter().for_each(|&idx| push(idx as u32));
+    }
+    push(END);
+    if bits > 0 {
+        bytes.push(buffer as u8);
+    }
+
+    // the data goes out in blocks of up to 255 bytes, each prefixed with its length
+    let mut result = vec![8];
+    for block in bytes.chunks(255) {
+        result.push(block.len() as u8);
+        result.extend(block);
+    }
+    result.push(0);
+
+    result
+}
+
+/// animated GIF looping over the images, each shown for `delay` hundredths of a second; images
+/// smaller than the largest one are drawn from the top left on a black background
+pub fn gif(images: &[Image], delay: u16) -> Vec<u8> {
+    let le = |value: usize| (value as u16).to_le_bytes();
+    let width = images.iter().map(|image| image.width).max().unwrap_or(1);
+    let height = images.iter().map(|image| image.height).max().unwrap_or(1);
+    let (colors, indices) = palette(images);
+
+    let mut result = b"GIF89a".to_vec();
+    result.extend(le(width));
+    result.extend(le(height));
+    // global color table of 256 colors, background is the first of them
+    result.extend([0xf7, 0, 0]);
+    for idx in 0..256 {
+        result.extend(colors.get(idx).unwrap_or(&BLACK));
+    }
+
+    if images.len() > 1 {
+        // loop forever
+        result.extend(b"\x21\xff\x0bNETSCAPE2.0\x03\x01\x00\x00\x00");
+    }
+
+    for image in images {
+        // graphic control extension: clear back to the background after each frame
+        result.extend([0x21, 0xf9, 0x04, 0x08]);
+        result.extend(delay.to_le_bytes());
+        result.extend([0, 0]);
+
+        result.push(0x2c);
+        result.extend([0, 0, 0, 0]);
+        result.extend(le(image.width));
+        result.extend(le(image.height));
+        result.push(0);
+
+        let pixels = image
+            .pixels
+            .iter()
+            .map(|pixel| indices[pixel])
+            .collect::<Vec<u8>>();
+        result.extend(lzw(&pixels));
+    }
+
+    result.push(0x3b);
+    result
+}
+
+/// write the images out, in whichever format the path's extension asks for: .pgm and .ppm take a
+/// single image, .gif any number of them as an animation
+pub fn save(path: &Path, images: &[Image], delay: u16) -> Result<(), String> {
+    let extension = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .unwrap_or_default();
+
+    let bytes = match (extension, images) {
+        (_, []) => return Err(String::from("nothing to draw")),
+        ("gif", _) => gif(images, delay),
+        ("pgm", [image]) => image.pgm(),
+        ("ppm", [image]) => image.ppm(),
+        ("pgm" | "ppm", _) => {
+            return Err(format!(
+                "{} only holds a single image, try .gif for {} frames",
+                path.display(),
+                images.len()
+            ))
+        }
+        _ => {
+            return Err(format!(
+                "don't know how to write {}, try .pgm, .ppm or .gif",
+                path.display()
+            ))
+        }
+    };
+
+    write(path, bytes).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// decodes the output of `lzw`, which only ever has literals and clear codes in it
+    fn unlzw(data: &[u8]) -> Vec<u8> {
+        assert_eq!(8, data[0], "minimum code size");
+
+        let mut bytes: Vec<u8> = Vec::new();
+        let mut rest = &data[1..];
+        while rest[0] != 0 {
+            let length = rest[0] as usize;
+            bytes.extend(&rest[1..=length]);
+            rest = &rest[length + 1..];
+        }
+
+        let (mut buffer, mut bits) = (0u32, 0);
+        let (mut width, mut next) = (9, 258);
+        let mut fresh = true;
+        let mut result = Vec::new();
+
+        for byte in bytes {
+            buffer |= (byte as u32) << bits;
+            bits += 8;
+
+            while bits >= width {
+                let code = buffer & ((1 << width) - 1);
+                buffer >>= width;
+                bits -= width;
+
+                match code {
+                    256 => {
+                        (width, next, fresh) = (9, 258, true);
+                        continue;
+                    }
+                    257 => return result,
+                    literal => {
+                        assert!(literal < 256, "only literals are ever written");
+                        result.push(literal as u8);
+                    }
+                }
+
+                // the decoder's table grows by one for every code after the first
+                if !fresh {
+                    next += 1;
+                    if next == 1 << width {
+                        width += 1;
+                    }
+                }
+                fresh = false;
+            }
+        }
+
+        panic!("missing end code");
+    }
+
+    #[test]
+    fn test_lzw() {
+        let indices = (0..5000)
+            .map(|idx| (idx * 7 % 256) as u8)
+            .collect::<Vec<u8>>();
+        assert_eq!(indices, unlzw(&lzw(&indices)));
+    }
+
+    #[test]
+    fn test_gif() {
+        let frames = [
+            Image::render(&vec![vec![0, 1], vec![2, 3]], ColorMap::Gray),
+            Image::render(&vec![vec![3]], ColorMap::Heat),
+        ];
+        let gif = gif(&frames, 10);
+
+        assert!(gif.starts_with(b"GIF89a\x02\x00\x02\x00\xf7"));
+        assert_eq!(2, gif.windows(3).filter(|w| w == b"\x21\xf9\x04").count());
+        assert_eq!(Some(&0x3b), gif.last());
+    }
+
+    #[test]
+    fn test_palette_overflow() {
+        let image = Image {
+            width: 300,
+            height: 1,
+            pixels: (0..300)
+                .map(|idx| [idx as u8, (idx / 256) as u8, 0])
+                .collect(),
+        };
+        let (colors, indices) = palette(&[image]);
+
+        assert_eq!(216, colors.len());
+        assert_eq!(0, indices[&[0, 0, 0]]);
+        assert_eq!([255, 0, 0], colors[indices[&[255, 0, 0]] as usize]);
+    }
+
+    #[test]
+    fn test_netpbm() {
+        let mut image = Image::render(&vec![vec![0, 4], vec![2, 4]], ColorMap::Gray);
+        image.set(0, 0, [255, 0, 0]);
+
+        assert_eq!(b"P5\n2 2\n255\n\x4c\xff\x7f\xff".to_vec(), image.pgm());
+        assert_eq!(
+            b"P6\n4 4\n255\n\xff\x00\x00\xff\x00\x00\xff\xff\xff\xff\xff\xff".to_vec(),
+            image.scaled(2).ppm()[..23].to_vec()
+        );
+    }
+
+    #[test]
+    fn test_frame() {
+        let frame = Frame::new("", &"#.\n+\n");
+
+        assert_eq!((2, 2), frame.size());
+        assert_eq!(' ' as u32, frame.value(1, 1));
+        assert_eq!(
+            vec!['#', '.', '+', ' '],
+            frame
+                .values()
+                .into_iter()
+                .map(|value| char::from_u32(value).unwrap())
+                .collect::<Vec<char>>()
+        );
+    }
+
+    #[test]
+    fn test_color_maps() {
+        assert_eq!([0, 0, 0], ColorMap::Heat.color(0, 10));
+        assert_eq!([255, 255, 255], ColorMap::Heat.color(10, 10));
+        assert_eq!(BLACK, ColorMap::Categories.color(0, 10));
+        assert_ne!(
+            ColorMap::Categories.color(1, 10),
+            ColorMap::Categories.color(2, 10)
+        );
+        assert_eq!([255, 215, 0], ColorMap::Text.color('#' as u32, 0));
+    }
+}
@@ -0,0 +1,270 @@
+use std::iter::Peekable;
+use std::str::CharIndices;
+
+/// JSON string literal
+pub fn quote(value: &str) -> String {
+    let mut result = String::from("\"");
+
+    for ch in value.chars() {
+        match ch {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            ch if (ch as u32) < 0x20 => result.push_str(&format!("\\u{:04x}", ch as u32)),
+            ch => result.push(ch),
+        }
+    }
+
+    result.push('"');
+    result
+}
+
+/// a parsed JSON document, with objects keeping their keys in order
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Value>),
+    Object(Vec<(String, Value)>),
+}
+
+impl Value {
+    /// the value of an object's key
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Object(entries) => entries
+                .iter()
+                .find(|(name, _)| name == key)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Number(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Value]> {
+        match self {
+            Value::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+}
+
+/// parse a JSON document, with the error saying at which byte it went wrong
+pub fn parse(text: &str) -> Result<Value, String> {
+    let mut parser = Parser {
+        text,
+        chars: text.char_indices().peekable(),
+    };
+
+    let value = parser.value()?;
+    parser.whitespace();
+    match parser.chars.peek() {
+        None => Ok(value),
+        Some(&(idx, _)) => Err(format!("byte {}: unexpected trailing characters", idx)),
+    }
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    chars: Peekable<CharIndices<'a>>,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&mut self, expected: &str) -> String {
+        match self.chars.peek() {
+            Some(&(idx, ch)) => format!("byte {}: expected {}, got {:?}", idx, expected, ch),
+            None => format!("expected {}, got the end of the document", expected),
+        }
+    }
+
+    fn whitespace(&mut self) {
+        while self.chars.next_if(|(_, ch)| ch.is_whitespace()).is_some() {}
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        self.whitespace();
+        match self.chars.next_if(|&(_, ch)| ch == expected) {
+            Some(_) => Ok(()),
+            None => Err(self.error(&format!("{:?}", expected))),
+        }
+    }
+
+    fn literal(&mut self, word: &str, value: Value) -> Result<Value, String> {
+        for expected in word.chars() {
+            if self.chars.next_if(|&(_, ch)| ch == expected).is_none() {
+                return Err(self.error(word));
+            }
+        }
+        Ok(value)
+    }
+
+    fn value(&mut self) -> Result<Value, String> {
+        self.whitespace();
+        match self.chars.peek().map(|&(_, ch)| ch) {
+            Some('n') => self.literal("null", Value::Null),
+            Some('t') => self.literal("true", Value::Bool(true)),
+            Some('f') => self.literal("false", Value::Bool(false)),
+            Some('"') => self.string().map(Value::String),
+            Some('[') => self.array(),
+            Some('{') => self.object(),
+            Some(ch) if ch == '-' || ch.is_ascii_digit() => self.number(),
+            _ => Err(self.error("a value")),
+        }
+    }
+
+    fn number(&mut self) -> Result<Value, String> {
+        let start = self.chars.peek().map_or(self.text.len(), |&(idx, _)| idx);
+        while self
+            .chars
+            .next_if(|(_, ch)| ch.is_ascii_digit() || "+-.eE".contains(*ch))
+            .is_some()
+        {}
+        let end = self.chars.peek().map_or(self.text.len(), |&(idx, _)| idx);
+
+        let number = &self.text[start..end];
+        number
+            .parse()
+            .map(Value::Number)
+            .map_err(|_| format!("byte {}: bad number {:?}", start, number))
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        self.expect('"')?;
+        let mut result = String::new();
+
+        loop {
+            match self.chars.next() {
+                Some((_, '"')) => return Ok(result),
+                Some((idx, '\\')) => match self.chars.next().map(|(_, ch)| ch) {
+                    Some('"') => result.push('"'),
+                    Some('\\') => result.push('\\'),
+                    Some('/') => result.push('/'),
+                    Some('n') => result.push('\n'),
+                    Some('r') => result.push('\r'),
+                    Some('t') => result.push('\t'),
+                    Some('b') => result.push('\u{8}'),
+                    Some('f') => result.push('\u{c}'),
+                    Some('u') => {
+                        let hex = (0..4)
+                            .filter_map(|_| self.chars.next().map(|(_, ch)| ch))
+                            .collect::<String>();
+                        let ch = u32::from_str_radix(&hex, 16)
+                            .ok()
+                            .and_then(char::from_u32)
+                            .ok_or(format!("byte {}: bad escape \\u{}", idx, hex))?;
+                        result.push(ch);
+                    }
+                    _ => return Err(format!("byte {}: bad escape", idx)),
+                },
+                Some((_, ch)) => result.push(ch),
+                None => return Err(String::from("unterminated string")),
+            }
+        }
+    }
+
+    fn array(&mut self) -> Result<Value, String> {
+        self.expect('[')?;
+        let mut values = Vec::new();
+
+        self.whitespace();
+        if self.chars.next_if(|&(_, ch)| ch == ']').is_some() {
+            return Ok(Value::Array(values));
+        }
+
+        loop {
+            values.push(self.value()?);
+            self.whitespace();
+            match self.chars.next_if(|&(_, ch)| ch == ',' || ch == ']') {
+                Some((_, ',')) => continue,
+                Some(_) => return Ok(Value::Array(values)),
+                None => return Err(self.error("',' or ']'")),
+            }
+        }
+    }
+
+    fn object(&mut self) -> Result<Value, String> {
+        self.expect('{')?;
+        let mut entries = Vec::new();
+
+        self.whitespace();
+        if self.chars.next_if(|&(_, ch)| ch == '}').is_some() {
+            return Ok(Value::Object(entries));
+        }
+
+        loop {
+            self.whitespace();
+            let key = self.string()?;
+            self.expect(':')?;
+            entries.push((key, self.value()?));
+            self.whitespace();
+            match self.chars.next_if(|&(_, ch)| ch == ',' || ch == '}') {
+                Some((_, ',')) => continue,
+                Some(_) => return Ok(Value::Object(entries)),
+                None => return Err(self.error("',' or '}'")),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let text = format!(
+            "{{\"day\": 15, \"name\": {}, \"samples\": [1.5, -2e3], \"ok\": true, \"at\": null}}",
+            quote("a \"b\"\n\u{1}")
+        );
+
+        assert_eq!(
+            Ok(Value::Object(vec![
+                (String::from("day"), Value::Number(15.0)),
+                (
+                    String::from("name"),
+                    Value::String(String::from("a \"b\"\n\u{1}"))
+                ),
+                (
+                    String::from("samples"),
+                    Value::Array(vec![Value::Number(1.5), Value::Number(-2000.0)])
+                ),
+                (String::from("ok"), Value::Bool(true)),
+                (String::from("at"), Value::Null),
+            ])),
+            parse(&text)
+        );
+        assert_eq!(Ok(Value::Array(vec![])), parse(" [ ] "));
+
+        assert_eq!(
+            Err(String::from("byte 5: expected ',' or ']', got '}'")),
+            parse("[1, 2}")
+        );
+        assert_eq!(
+            Err(String::from("byte 3: unexpected trailing characters")),
+            parse("{} x")
+        );
+        assert_eq!(
+            Err(String::from(
+                "expected a value, got the end of the document"
+            )),
+            parse("[1,")
+        );
+    }
+}
@@ -0,0 +1,101 @@
+use crate::Solver;
+use std::fs::read_to_string;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// answers accepted by adventofcode.com for a single day
+///
+/// stored in src/answers/<year>/day-N.answers, one `part: answer` pair per line, i.e.
+///
+/// ```text
+/// # comments and blank lines are ignored
+/// 1: 1715
+/// 2: 1739
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Ledger {
+    pub part_one: Option<String>,
+    pub part_two: Option<String>,
+}
+
+impl FromStr for Ledger {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut ledger = Ledger::default();
+
+        for (idx, line) in s.lines().enumerate() {
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (part, answer) = line
+                .split_once(':')
+                .ok_or(format!("line {}: expected `part: answer`", idx + 1))?;
+
+            let answer = Some(answer.trim().to_string());
+
+            match part.trim() {
+                "1" => ledger.part_one = answer,
+                "2" => ledger.part_two = answer,
+                other => return Err(format!("line {}: unknown part {}", idx + 1, other)),
+            }
+        }
+
+        Ok(ledger)
+    }
+}
+
+impl Ledger {
+    /// location of the given day's ledger
+    pub fn path(year: u32, day: u32) -> PathBuf {
+        PathBuf::from(format!("src/answers/{}/day-{}.answers", year, day))
+    }
+
+    /// read the given day's ledger, a missing file is an empty ledger
+    pub fn load(year: u32, day: u32) -> Result<Self, String> {
+        Ledger::read(&Ledger::path(year, day))
+    }
+
+    /// read a ledger from anywhere, i.e. someone else's answers; a missing file is an empty ledger
+    pub fn read(path: &Path) -> Result<Self, String> {
+        if !path.exists() {
+            return Ok(Ledger::default());
+        }
+
+        let contents = read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+
+        Ledger::from_str(&contents).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    /// recorded answer for the given part
+    pub fn get(&self, solver: Solver) -> Option<&str> {
+        match solver {
+            Solver::PartOne => self.part_one.as_deref(),
+            Solver::PartTwo => self.part_two.as_deref(),
+        }
+    }
+
+    /// number of stars earned, i.e. number of recorded answers
+    pub fn stars(&self) -> usize {
+        self.part_one.iter().count() + self.part_two.iter().count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let ledger = Ledger::from_str("# day 1\n1: 1715\n\n2:1739\n").unwrap();
+        assert_eq!(Some("1715"), ledger.get(Solver::PartOne));
+        assert_eq!(Some("1739"), ledger.get(Solver::PartTwo));
+        assert_eq!(2, ledger.stars());
+
+        assert!(Ledger::from_str("3: 12").is_err());
+        assert!(Ledger::from_str("1715").is_err());
+    }
+}
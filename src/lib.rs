@@ -1,13 +1,76 @@
 use clap::Parser;
+use config::{Config, Format};
+use days::{Day, Solution};
 use std::fs::read_to_string;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+pub mod answer;
+pub mod bench;
+pub mod checked;
+pub mod config;
+pub mod days;
+pub mod error;
+pub mod events;
+pub mod explain;
+pub mod ffi;
+pub mod fuzz;
+pub mod image;
+pub mod json;
+pub mod ledger;
+pub mod minimize;
+pub mod normalize;
+pub mod player;
+pub mod property;
+pub mod rng;
+pub mod simulation;
+pub mod snapshot;
+pub mod stream;
+pub mod validate;
+pub mod visualize;
+
+pub use answer::Answer;
+pub use error::ParseError;
 
 /// cli for aoc binaries
 #[derive(Parser)]
 pub struct AoCArgParser {
-    /// which part of the day
+    /// which part of the day: 1, 2 or both
     #[clap(short, long)]
-    pub part: i32,
+    pub part: Parts,
+
+    /// which implementation of the part to run, see `aoc compare`
+    #[clap(long = "impl", default_value = days::DEFAULT_IMPL)]
+    pub implementation: String,
+
+    /// which year's day to run; 2021 unless aoc.toml says otherwise
+    #[clap(long)]
+    pub year: Option<u32>,
+
+    /// whose puzzle input to use, read from <inputs>/<year>/<profile>/
+    #[clap(long)]
+    pub profile: Option<String>,
+
+    /// refuse an input that needs normalizing (crlf, a byte order mark, etc) rather than fixing it
+    #[clap(long)]
+    pub strict: bool,
+
+    /// watch the day's simulation play out in the terminal before the answer is printed
+    #[clap(long)]
+    pub visualize: bool,
+
+    /// check the part against the day's examples instead of solving the input
+    #[clap(long)]
+    pub examples: bool,
+
+    /// print each step of the solution (which board won, what each pattern decoded to, etc)
+    /// before the answer
+    #[clap(long)]
+    pub explain: bool,
+
+    /// how to print an explanation, text or json
+    #[clap(long)]
+    pub format: Option<Format>,
 }
 
 /// parse cli arguments
@@ -15,17 +78,223 @@ pub fn parse_args() -> AoCArgParser {
     AoCArgParser::parse()
 }
 
-/// read input file from src/inputs based on current file's filename
-pub fn get_input(filename: &str) -> String {
+/// day number based on a day binary's filename, i.e. 4 for src/bin/day-4.rs
+pub fn day_from_filename(filename: &str) -> u32 {
     let this_file = Path::new(filename);
     let mut stem = this_file.file_stem().unwrap().to_str().unwrap().split('-');
 
     stem.next().unwrap(); // discard 'day'
-    let current_day = stem.next().unwrap();
+    stem.next().unwrap().parse().unwrap()
+}
+
+/// location of the given day's input file under `inputs`, in the profile's own directory when
+/// one is given
+pub fn input_path(inputs: &Path, year: u32, day: u32, profile: Option<&str>) -> PathBuf {
+    let dir = inputs.join(year.to_string());
+    let dir = match profile {
+        Some(profile) => dir.join(profile),
+        None => dir,
+    };
+    dir.join(format!("input-{}", day))
+}
+
+/// read an input and normalize it, or in strict mode refuse one that would need normalizing
+pub fn read_input(path: &Path, strict: bool) -> Result<String, String> {
+    let input = read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+
+    if strict {
+        if let [first, rest @ ..] = &normalize::problems(&input)[..] {
+            return Err(match rest.len() {
+                0 => format!("{}: {}", path.display(), first),
+                more => format!("{}: {} (and {} more)", path.display(), first, more),
+            });
+        }
+    }
+
+    Ok(normalize::normalize(&input))
+}
+
+/// read the configured input file based on current file's filename
+pub fn get_input(filename: &str, config: &Config) -> Result<String, String> {
+    read_input(
+        &config.input_path(day_from_filename(filename)),
+        config.strict.value,
+    )
+}
+
+/// solve each of the day's examples for the part, printing whether it got the expected answer,
+/// and return how many didn't; `aoc watch` picks out these lines from whatever else the day prints
+fn check_examples(day: &Day, solver: Solver, solution: Solution) -> usize {
+    let examples = day
+        .examples
+        .iter()
+        .filter_map(|example| Some((example.input, example.expected(solver)?)))
+        .collect::<Vec<(&str, &str)>>();
 
-    let input_path = format!("src/inputs/input-{}", current_day);
+    if examples.is_empty() {
+        eprintln!("day {} has no examples for this part", day.number);
+    }
 
-    read_to_string(input_path).expect("couldn't read input file")
+    let mut failed = 0;
+    for (idx, (input, expected)) in examples.into_iter().enumerate() {
+        match solution(input) {
+            Ok(answer) if answer.to_string() == expected => {
+                println!("[+] example {}: {}", idx + 1, answer);
+                continue;
+            }
+            Ok(answer) => println!(
+                "[!] example {}: expected {}, got {}",
+                idx + 1,
+                expected,
+                answer
+            ),
+            Err(e) => println!("[!] example {}: {}", idx + 1, e),
+        }
+        failed += 1;
+    }
+
+    failed
+}
+
+/// what a day binary exits with when it fails, so scripts can tell a mistyped flag from a bad
+/// input from a broken solver
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Exit {
+    /// an unknown day, implementation, etc; the code clap exits with for its own usage errors
+    BadArgs = 2,
+    /// the input couldn't be read or doesn't parse
+    BadInput = 3,
+    /// the input parses but solving it failed (an overflow, say), or an example got the wrong
+    /// answer
+    SolverFailed = 4,
+}
+
+/// solve the day matching the calling binary's filename, using the part given on the cli, and
+/// exit with the matching code when that fails
+pub fn run(filename: &str) {
+    let args = parse_args();
+    let result = Config::load()
+        .map_err(|e| (Exit::BadArgs, e))
+        .and_then(|config| solve(filename, args, config));
+
+    if let Err((exit, message)) = result {
+        eprintln!("{}", message);
+        std::process::exit(exit as i32);
+    }
+}
+
+fn solve(filename: &str, args: AoCArgParser, mut config: Config) -> Result<(), (Exit, String)> {
+    let bad_args = |message: String| (Exit::BadArgs, message);
+
+    config.strict.flag(args.strict.then_some(true));
+    let config = config.with_flags(args.year, args.profile);
+
+    let day = events::day(config.year.value, day_from_filename(filename)).map_err(bad_args)?;
+    let solvers = args.part.solvers();
+
+    let solutions = solvers
+        .iter()
+        .map(|solver| {
+            day.implementation(*solver, &args.implementation)
+                .ok_or_else(|| {
+                    let names = day
+                        .implementations(*solver)
+                        .iter()
+                        .map(|implementation| implementation.name)
+                        .collect::<Vec<&str>>();
+                    bad_args(format!(
+                        "got unexpected value for --impl: {} (try {})",
+                        args.implementation,
+                        names.join(" or ")
+                    ))
+                })
+        })
+        .collect::<Result<Vec<Solution>, (Exit, String)>>()?;
+
+    if args.examples {
+        let mut failed = 0;
+        for (solver, solution) in solvers.iter().zip(&solutions) {
+            failed += check_examples(day, *solver, *solution);
+        }
+        return match failed {
+            0 => Ok(()),
+            failed => Err((
+                Exit::SolverFailed,
+                format!("{} example(s) got the wrong answer", failed),
+            )),
+        };
+    }
+
+    let explain = match (args.explain, day.explain, args.implementation.as_str()) {
+        (false, _, _) => None,
+        (true, Some(explain), days::DEFAULT_IMPL) => Some(explain),
+        (true, Some(_), _) => {
+            return Err(bad_args(format!(
+                "only the {} implementation explains itself",
+                days::DEFAULT_IMPL
+            )))
+        }
+        (true, None, _) => {
+            return Err(bad_args(format!(
+                "day {} doesn't explain itself",
+                day.number
+            )))
+        }
+    };
+    if args.visualize && day.visualize.is_none() {
+        return Err(bad_args(format!(
+            "day {} doesn't have a visualization",
+            day.number
+        )));
+    }
+
+    // read, check and parse the input the once, however many parts are run on it
+    let path = config.input_path(day.number);
+    let input = get_input(filename, &config).map_err(|e| (Exit::BadInput, e))?;
+
+    // report everything wrong with the input at once, rather than whatever the parser trips on
+    // first
+    let errors = (day.validate)(&input)
+        .iter()
+        .map(|error| format!("{}: {}", path.display(), error))
+        .collect::<Vec<String>>();
+    if !errors.is_empty() {
+        return Err((Exit::BadInput, errors.join("\n")));
+    }
+    (day.parse)(&input).map_err(|e| {
+        (
+            Exit::BadInput,
+            format!("couldn't parse {}: {}", path.display(), e),
+        )
+    })?;
+
+    // the input parses, so anything that goes wrong from here on is down to the solver
+    let failed = |e: ParseError| {
+        (
+            Exit::SolverFailed,
+            format!("couldn't solve {}: {}", path.display(), e),
+        )
+    };
+
+    for (solver, solution) in solvers.iter().zip(&solutions) {
+        if let (true, Some(visualize)) = (args.visualize, day.visualize) {
+            player::play(&visualize(&input, *solver).map_err(failed)?);
+        }
+
+        match explain {
+            Some(explain) => {
+                let mut format = config.format.clone();
+                format.flag(args.format);
+
+                let mut events = Vec::new();
+                let answer = explain(&input, *solver, &mut events).map_err(failed)?;
+                print!("{}", explain::render(&answer, &events, format.value));
+            }
+            None => println!("{}", solution(&input).map_err(failed)?),
+        }
+    }
+
+    Ok(())
 }
 
 #[derive(Copy, Clone, Debug, PartialEq)]
@@ -33,3 +302,126 @@ pub enum Solver {
     PartOne,
     PartTwo,
 }
+
+impl Solver {
+    /// the part's number, as `--part` takes it and the puzzle calls it
+    pub fn number(self) -> u32 {
+        match self {
+            Solver::PartOne => 1,
+            Solver::PartTwo => 2,
+        }
+    }
+}
+
+impl FromStr for Solver {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "1" => Ok(Solver::PartOne),
+            "2" => Ok(Solver::PartTwo),
+            _ => Err(format!("unknown part {:?} (try 1 or 2)", value)),
+        }
+    }
+}
+
+/// parts to run, from `--part 1`, `2` or `both`
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Parts {
+    Only(Solver),
+    Both,
+}
+
+impl Parts {
+    pub fn solvers(&self) -> Vec<Solver> {
+        match self {
+            Parts::Only(solver) => vec![*solver],
+            Parts::Both => vec![Solver::PartOne, Solver::PartTwo],
+        }
+    }
+}
+
+impl FromStr for Parts {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "both" => Ok(Parts::Both),
+            _ => value
+                .parse()
+                .map(Parts::Only)
+                .map_err(|_| format!("unknown part {:?} (try 1, 2 or both)", value)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::{create_dir_all, remove_dir_all, write};
+
+    #[test]
+    fn test_parts() {
+        assert_eq!(Ok(Parts::Only(Solver::PartTwo)), "2".parse());
+        assert_eq!(Ok(Parts::Both), "both".parse::<Parts>());
+        assert_eq!(
+            vec![Solver::PartOne, Solver::PartTwo],
+            Parts::Both.solvers()
+        );
+        assert_eq!(
+            Err(String::from("unknown part \"3\" (try 1, 2 or both)")),
+            "3".parse::<Parts>()
+        );
+        assert_eq!(
+            Err(String::from("unknown part \"both\" (try 1 or 2)")),
+            "both".parse::<Solver>()
+        );
+        for solver in Parts::Both.solvers() {
+            assert_eq!(Ok(solver), solver.number().to_string().parse());
+        }
+
+        // clap refuses the part itself, exiting with its usage error code
+        let error = AoCArgParser::try_parse_from(["day-7", "--part", "3"])
+            .err()
+            .unwrap();
+        assert_eq!(clap::ErrorKind::ValueValidation, error.kind);
+    }
+
+    #[test]
+    fn test_exit_codes() {
+        let inputs = std::env::temp_dir().join(format!("aoc-exit-{}", std::process::id()));
+        create_dir_all(inputs.join("2021")).unwrap();
+        let mut config = Config::default();
+        config.inputs.value = inputs.clone();
+
+        let exit = |args: &[&str], input: &str| {
+            write(inputs.join("2021").join("input-7"), input).unwrap();
+            let args = AoCArgParser::try_parse_from([&["day-7"], args].concat()).unwrap();
+            solve("src/bin/day-7.rs", args, config.clone()).map_err(|(exit, _)| exit)
+        };
+
+        assert_eq!(Ok(()), exit(&["--part", "both"], "16,1,2"));
+        assert_eq!(Ok(()), exit(&["--part", "1", "--examples"], ""));
+        assert_eq!(
+            Err(Exit::BadArgs),
+            exit(&["--part", "1", "--impl", "fastest"], "16,1,2")
+        );
+        assert_eq!(
+            Err(Exit::BadArgs),
+            exit(&["--part", "1", "--explain"], "16,1,2")
+        );
+        assert_eq!(Err(Exit::BadInput), exit(&["--part", "both"], "16,x,2"));
+        assert_eq!(
+            Err(Exit::BadInput),
+            exit(&["--part", "1", "--profile", "nobody"], "16,1,2")
+        );
+
+        // parses fine, but the fuel it takes doesn't fit an i32; without the feature, that's an
+        // overflow panic in a debug build
+        if cfg!(feature = "checked") {
+            assert_eq!(Err(Exit::SolverFailed), exit(&["--part", "2"], "0,70000"));
+        }
+
+        remove_dir_all(&inputs).unwrap();
+    }
+}
@@ -0,0 +1,147 @@
+/// delta debugging: the smallest subset of `items` (keeping their order) that `fails` still
+/// reports as failing, found by repeatedly trying to throw away chunks of it
+///
+/// the result is 1-minimal, removing any single remaining item makes the failure go away, but
+/// not necessarily the smallest failing subset overall
+pub fn ddmin<T, F>(items: &[T], mut fails: F) -> Vec<T>
+where
+    T: Clone,
+    F: FnMut(&[T]) -> bool,
+{
+    let mut items = items.to_vec();
+    let mut granularity = 2;
+
+    while items.len() >= 2 {
+        let chunk = items.len().div_ceil(granularity);
+        let starts = (0..items.len()).step_by(chunk).collect::<Vec<usize>>();
+        let mut reduced = false;
+
+        // a single chunk that fails on its own is the biggest win
+        for start in &starts {
+            let subset = &items[*start..usize::min(start + chunk, items.len())];
+            if fails(subset) {
+                items = subset.to_vec();
+                granularity = 2;
+                reduced = true;
+                break;
+            }
+        }
+
+        // otherwise, see if the failure survives without one of the chunks
+        if !reduced {
+            for start in &starts {
+                let end = usize::min(start + chunk, items.len());
+                let mut complement = items[..*start].to_vec();
+                complement.extend_from_slice(&items[end..]);
+
+                if fails(&complement) {
+                    items = complement;
+                    granularity = usize::max(granularity - 1, 2);
+                    reduced = true;
+                    break;
+                }
+            }
+        }
+
+        if !reduced {
+            if granularity >= items.len() {
+                // already tried removing every item on its own
+                break;
+            }
+            granularity = usize::min(granularity * 2, items.len());
+        }
+    }
+
+    if items.len() == 1 && fails(&[]) {
+        items.clear();
+    }
+
+    items
+}
+
+/// input split into sections at blank lines, and each section into lines
+fn split(input: &str) -> Vec<Vec<String>> {
+    let mut sections = vec![Vec::new()];
+
+    for line in input.lines() {
+        if line.is_empty() {
+            sections.push(Vec::new());
+        } else {
+            sections.last_mut().unwrap().push(line.to_string());
+        }
+    }
+
+    sections.retain(|section| !section.is_empty());
+    sections
+}
+
+/// opposite of split, sections that have been emptied out are dropped entirely
+fn render(sections: &[Vec<String>]) -> String {
+    sections
+        .iter()
+        .filter(|section| !section.is_empty())
+        .map(|section| section.join("\n"))
+        .collect::<Vec<String>>()
+        .join("\n\n")
+}
+
+/// smallest input, built from `input`'s sections and lines, that `fails` still reports as failing
+///
+/// whole sections (runs of lines separated by blank lines, like day 4's boards) are thrown away
+/// first, then lines within each remaining section, until neither gets any smaller. nothing is
+/// ever split mid-line, so a day's line formats survive; `fails` should reject candidates the
+/// day can't parse, which keeps section shapes (a board's 5 rows, say) intact as well
+pub fn minimize<F>(input: &str, mut fails: F) -> String
+where
+    F: FnMut(&str) -> bool,
+{
+    let mut sections = split(input);
+
+    loop {
+        let before = render(&sections);
+
+        sections = ddmin(&sections, |candidate| fails(&render(candidate)));
+
+        for idx in 0..sections.len() {
+            let lines = ddmin(&sections[idx], |candidate| {
+                let mut trial = sections.clone();
+                trial[idx] = candidate.to_vec();
+                fails(&render(&trial))
+            });
+            sections[idx] = lines;
+        }
+
+        let after = render(&sections);
+        if after == before {
+            return after;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ddmin() {
+        let items = (0..20).collect::<Vec<usize>>();
+        let minimal = ddmin(&items, |subset| subset.contains(&3) && subset.contains(&17));
+
+        assert_eq!(vec![3, 17], minimal);
+    }
+
+    #[test]
+    fn test_minimize() {
+        let input = "7,4,9\n\na b\nc d\n\ne f\ng h\n\ni j\nk l";
+
+        // draws need to stay first, sections need exactly two rows, and the failure needs `g h`
+        let minimal = minimize(input, |candidate| {
+            let sections = split(candidate);
+            sections.first().is_some_and(|draws| draws == &["7,4,9"])
+                && sections[1..].iter().all(|section| section.len() == 2)
+                && candidate.contains("g h")
+        });
+
+        assert_eq!("7,4,9\n\ne f\ng h", minimal);
+    }
+}
@@ -0,0 +1,137 @@
+use crate::ParseError;
+
+/// byte order mark some editors put at the start of a file
+const BOM: char = '\u{feff}';
+
+/// an input the way the parsers expect it: no byte order mark, \n line endings, no whitespace at
+/// the end of a line and nothing after the last line, not even a newline
+pub fn normalize(input: &str) -> String {
+    let input = input.strip_prefix(BOM).unwrap_or(input);
+
+    input
+        .split('\n')
+        .map(str::trim_end)
+        .collect::<Vec<&str>>()
+        .join("\n")
+        .trim_end_matches('\n')
+        .to_string()
+}
+
+/// where a problem found in `normalize(input)` is in `input` itself; normalizing only removes what
+/// comes after each line's content, so it's just the byte order mark that moves anything
+pub fn raw_position(input: &str, mut error: ParseError) -> ParseError {
+    if error.line == 1 && input.starts_with(BOM) {
+        error.column = error.column.map(|column| column + 1);
+    }
+    error
+}
+
+/// everything normalize would fix, for strict mode to report instead, at its position in `input`
+///
+/// a single newline ending the last line is how text files end rather than a problem, so it's
+/// left out
+pub fn problems(input: &str) -> Vec<ParseError> {
+    let mut problems = Vec::new();
+
+    if input.starts_with(BOM) {
+        problems.push(ParseError::at(0, 0, "byte order mark"));
+    }
+
+    let lines = input
+        .strip_prefix(BOM)
+        .unwrap_or(input)
+        .split('\n')
+        .collect::<Vec<&str>>();
+
+    for (idx, line) in lines.iter().enumerate() {
+        // the byte order mark is still there in the file, ahead of the first line's columns
+        let bom = usize::from(idx == 0 && input.starts_with(BOM));
+
+        let content = match line.strip_suffix('\r') {
+            Some(content) => {
+                let col_idx = bom + content.chars().count();
+                problems.push(ParseError::at(idx, col_idx, "windows line ending"));
+                content
+            }
+            None => line,
+        };
+
+        let trimmed = content.trim_end();
+        if trimmed.len() < content.len() {
+            let col_idx = bom + trimmed.chars().count();
+            problems.push(ParseError::at(idx, col_idx, "trailing whitespace"));
+        }
+    }
+
+    // an empty string after the final newline is where the last line ends, not a blank line
+    let lines = match lines[..] {
+        [ref content @ .., ""] => content,
+        _ => &lines[..],
+    };
+    let blank = lines
+        .iter()
+        .rev()
+        .take_while(|line| line.trim_end().is_empty())
+        .count();
+    if blank > 0 && blank < lines.len() {
+        problems.push(ParseError::new(
+            lines.len() - blank,
+            format!("{} blank line(s) at the end of the input", blank),
+        ));
+    }
+
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!("3,4,3,1,2", normalize("3,4,3,1,2\n"));
+        assert_eq!(
+            "7,4,9\n\n22 13\n 8  2",
+            normalize("\u{feff}7,4,9\r\n\r\n22 13 \r\n 8  2\t\r\n\r\n\r\n")
+        );
+        assert_eq!("", normalize("\u{feff}\r\n"));
+    }
+
+    #[test]
+    fn test_problems() {
+        assert_eq!(Vec::<ParseError>::new(), problems("3,4,3,1,2\n"));
+        assert_eq!(Vec::<ParseError>::new(), problems("6,10\n\nfold along y=7"));
+
+        assert_eq!(
+            vec![
+                ParseError::at(0, 0, "byte order mark"),
+                ParseError::at(0, 6, "windows line ending"),
+                ParseError::at(1, 0, "windows line ending"),
+                ParseError::at(2, 6, "windows line ending"),
+                ParseError::at(2, 5, "trailing whitespace"),
+                ParseError::at(3, 0, "windows line ending"),
+                ParseError::new(3, "2 blank line(s) at the end of the input"),
+            ],
+            problems("\u{feff}7,4,9\r\n\r\n22 13 \r\n\r\n\n")
+        );
+    }
+
+    #[test]
+    fn test_raw_position() {
+        let input = "\u{feff}199 \r\n2x0\r\n";
+        let validated = ParseError::at(0, 0, "couldn't parse");
+        assert_eq!(
+            ParseError::at(0, 1, "couldn't parse"),
+            raw_position(input, validated.clone())
+        );
+        assert_eq!(
+            validated,
+            raw_position(&normalize(input), validated.clone())
+        );
+
+        let validated = ParseError::at(1, 1, "couldn't parse");
+        assert_eq!(validated, raw_position(input, validated.clone()));
+        let validated = ParseError::new(0, "no numbers");
+        assert_eq!(validated, raw_position(input, validated.clone()));
+    }
+}
@@ -0,0 +1,237 @@
+use crate::visualize::Frame;
+use std::io::{stdin, stdout, IsTerminal, Read, Write};
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{channel, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+const HELP: &str = "space play/pause  n/p step  +/- speed  q quit";
+
+/// where playback is at, kept apart from the terminal so the controls can be tested
+#[derive(Debug, PartialEq)]
+struct Player {
+    frame: usize,
+    frames: usize,
+    playing: bool,
+    fps: u32,
+}
+
+impl Player {
+    const MAX_FPS: u32 = 64;
+
+    fn new(frames: usize) -> Self {
+        Self {
+            frame: 0,
+            frames,
+            playing: true,
+            fps: 8,
+        }
+    }
+
+    fn delay(&self) -> Duration {
+        Duration::from_millis(1000 / self.fps as u64)
+    }
+
+    fn last(&self) -> bool {
+        self.frame + 1 >= self.frames
+    }
+
+    /// move on to the next frame if playing, pausing once there are none left
+    fn tick(&mut self) {
+        if self.playing && !self.last() {
+            self.frame += 1;
+        }
+        if self.last() {
+            self.playing = false;
+        }
+    }
+
+    /// react to a key, false when it's time to stop
+    fn press(&mut self, key: char) -> bool {
+        match key {
+            ' ' => self.playing = !self.playing && !self.last(),
+            'n' => {
+                self.playing = false;
+                self.frame = (self.frame + 1).min(self.frames.saturating_sub(1));
+            }
+            'p' => {
+                self.playing = false;
+                self.frame = self.frame.saturating_sub(1);
+            }
+            '+' | '=' => self.fps = (self.fps * 2).min(Self::MAX_FPS),
+            '-' => self.fps = (self.fps / 2).max(1),
+            'q' => return false,
+            _ => {}
+        }
+
+        true
+    }
+
+    fn status(&self) -> String {
+        format!(
+            "frame {}/{}  {} fps  [{}]  {}",
+            self.frame + 1,
+            self.frames,
+            self.fps,
+            if self.playing { "playing" } else { "paused" },
+            HELP
+        )
+    }
+}
+
+/// puts the terminal into non-canonical mode so keys arrive without waiting for enter, putting
+/// it back how it was when dropped
+struct RawMode {
+    saved: String,
+}
+
+impl RawMode {
+    fn stty(args: &[&str]) -> Option<String> {
+        let output = Command::new("stty")
+            .args(args)
+            .stdin(Stdio::inherit())
+            .output()
+            .ok()?;
+
+        output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    fn enable() -> Option<Self> {
+        let saved = Self::stty(&["-g"])?;
+        Self::stty(&["-icanon", "-echo"])?;
+        Some(Self { saved })
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        Self::stty(&[&self.saved]);
+    }
+}
+
+fn draw(frame: &Frame, status: &str) {
+    let mut out = stdout().lock();
+    // clear the screen and go back to the top left before every frame
+    write!(out, "\x1b[2J\x1b[H{}\n{}\n", frame, status).ok();
+    out.flush().ok();
+}
+
+/// play frames back in the terminal, with keys to pause, step and change speed
+///
+/// keys need stdin to be a terminal; without one the frames just play through once, and when
+/// stdout isn't a terminal they're all printed one after the other instead
+pub fn play(frames: &[Frame]) {
+    if frames.is_empty() {
+        return;
+    }
+
+    if !stdout().is_terminal() {
+        for frame in frames {
+            println!("{}", frame);
+        }
+        return;
+    }
+
+    let mut player = Player::new(frames.len());
+
+    let raw = if stdin().is_terminal() {
+        RawMode::enable()
+    } else {
+        None
+    };
+
+    let (sender, keys) = channel();
+    if raw.is_some() {
+        thread::spawn(move || {
+            for byte in stdin().lock().bytes() {
+                match byte {
+                    Ok(byte) if sender.send(byte as char).is_ok() => {}
+                    _ => break,
+                }
+            }
+        });
+    } else {
+        drop(sender);
+    }
+
+    loop {
+        let status = if raw.is_some() {
+            player.status()
+        } else {
+            format!("frame {}/{}", player.frame + 1, player.frames)
+        };
+        draw(&frames[player.frame], &status);
+
+        match keys.recv_timeout(player.delay()) {
+            Ok(key) => {
+                if !player.press(key) {
+                    break;
+                }
+            }
+            Err(RecvTimeoutError::Timeout) => player.tick(),
+            // nobody at the keyboard, so stop once the last frame's been shown
+            Err(RecvTimeoutError::Disconnected) => {
+                if player.last() {
+                    break;
+                }
+                thread::sleep(player.delay());
+                player.tick();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_playback() {
+        let mut player = Player::new(3);
+
+        player.tick();
+        player.tick();
+        assert_eq!(2, player.frame);
+        assert!(!player.playing, "should pause on the last frame");
+
+        player.tick();
+        assert_eq!(2, player.frame);
+
+        assert!(player.press(' '));
+        assert!(!player.playing, "nothing left to play");
+    }
+
+    #[test]
+    fn test_keys() {
+        let mut player = Player::new(3);
+
+        assert!(player.press('n'));
+        assert_eq!((1, false), (player.frame, player.playing));
+
+        player.press('n');
+        player.press('n');
+        assert_eq!(2, player.frame);
+
+        player.press('p');
+        player.press('p');
+        player.press('p');
+        assert_eq!(0, player.frame);
+
+        player.press(' ');
+        assert!(player.playing);
+
+        player.press('-');
+        player.press('-');
+        player.press('-');
+        player.press('-');
+        assert_eq!(1, player.fps);
+
+        (0..10).for_each(|_| assert!(player.press('+')));
+        assert_eq!(Player::MAX_FPS, player.fps);
+
+        assert!(!player.press('q'));
+    }
+}
@@ -0,0 +1,242 @@
+use crate::rng::Rng;
+use std::env;
+use std::fmt::Debug;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+/// number of generated cases per property, overridden by AOC_PROPERTY_CASES
+const DEFAULT_CASES: usize = 2000;
+
+/// seed for the first case, overridden by AOC_PROPERTY_SEED
+const DEFAULT_SEED: u64 = 2021;
+
+/// upper bound on the number of candidates tried while shrinking a failure
+const SHRINK_BUDGET: usize = 10_000;
+
+/// a generated value that knows how to produce simpler versions of itself
+pub trait Shrink: Clone + Debug {
+    /// candidates that are simpler than self, most aggressive first
+    fn shrink(&self) -> Vec<Self>;
+}
+
+macro_rules! shrink_unsigned {
+    ($($t:ty),*) => {$(
+        impl Shrink for $t {
+            fn shrink(&self) -> Vec<Self> {
+                let mut candidates = Vec::new();
+                for candidate in [0, *self / 2, self.saturating_sub(1)] {
+                    if candidate < *self && !candidates.contains(&candidate) {
+                        candidates.push(candidate);
+                    }
+                }
+                candidates
+            }
+        }
+    )*};
+}
+
+macro_rules! shrink_signed {
+    ($($t:ty),*) => {$(
+        impl Shrink for $t {
+            fn shrink(&self) -> Vec<Self> {
+                // move towards 0 from either direction
+                let mut candidates = Vec::new();
+                for candidate in [0, *self / 2, *self - self.signum()] {
+                    if candidate.abs() < self.abs() && !candidates.contains(&candidate) {
+                        candidates.push(candidate);
+                    }
+                }
+                candidates
+            }
+        }
+    )*};
+}
+
+shrink_unsigned!(u8, u32, u64, usize);
+shrink_signed!(i32, i64);
+
+impl<T: Shrink> Shrink for Vec<T> {
+    fn shrink(&self) -> Vec<Self> {
+        let mut candidates = Vec::new();
+
+        // drop chunks, starting with halves and working down to single elements
+        let mut chunk = self.len() / 2;
+        while chunk > 0 {
+            let mut start = 0;
+            while start < self.len() {
+                let end = usize::min(start + chunk, self.len());
+                let mut candidate = self[..start].to_vec();
+                candidate.extend_from_slice(&self[end..]);
+                candidates.push(candidate);
+                start += chunk;
+            }
+            chunk /= 2;
+        }
+
+        if self.len() == 1 {
+            candidates.push(Vec::new());
+        }
+
+        // then simplify the remaining elements one at a time
+        for (idx, element) in self.iter().enumerate() {
+            for simpler in element.shrink() {
+                let mut candidate = self.clone();
+                candidate[idx] = simpler;
+                candidates.push(candidate);
+            }
+        }
+
+        candidates
+    }
+}
+
+impl<A: Shrink, B: Shrink> Shrink for (A, B) {
+    fn shrink(&self) -> Vec<Self> {
+        let mut candidates = Vec::new();
+        candidates.extend(self.0.shrink().into_iter().map(|a| (a, self.1.clone())));
+        candidates.extend(self.1.shrink().into_iter().map(|b| (self.0.clone(), b)));
+        candidates
+    }
+}
+
+impl<A: Shrink, B: Shrink, C: Shrink> Shrink for (A, B, C) {
+    fn shrink(&self) -> Vec<Self> {
+        let mut candidates = Vec::new();
+        candidates.extend(
+            self.0
+                .shrink()
+                .into_iter()
+                .map(|a| (a, self.1.clone(), self.2.clone())),
+        );
+        candidates.extend(
+            self.1
+                .shrink()
+                .into_iter()
+                .map(|b| (self.0.clone(), b, self.2.clone())),
+        );
+        candidates.extend(
+            self.2
+                .shrink()
+                .into_iter()
+                .map(|c| (self.0.clone(), self.1.clone(), c)),
+        );
+        candidates
+    }
+}
+
+/// value of the given environment variable, or `default` when it's unset or unparseable
+pub(crate) fn env_or<T: std::str::FromStr>(name: &str, default: T) -> T {
+    env::var(name)
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(default)
+}
+
+/// run the property, treating a panic the same as a returned error
+pub(crate) fn evaluate<T, P>(value: &T, property: &P) -> Result<(), String>
+where
+    P: Fn(&T) -> Result<(), String>,
+{
+    match catch_unwind(AssertUnwindSafe(|| property(value))) {
+        Ok(result) => result,
+        Err(payload) => {
+            let message = payload
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_default();
+            Err(format!("panicked: {}", message))
+        }
+    }
+}
+
+/// greedily replace a failing value with the first simpler candidate that still fails, until no
+/// candidate does
+pub fn shrink_failure<T, P>(mut value: T, mut error: String, property: P) -> (T, String)
+where
+    T: Shrink,
+    P: Fn(&T) -> Result<(), String>,
+{
+    let mut attempts = 0;
+
+    'outer: loop {
+        for candidate in value.shrink() {
+            attempts += 1;
+            if attempts > SHRINK_BUDGET {
+                break 'outer;
+            }
+
+            if let Err(e) = evaluate(&candidate, &property) {
+                value = candidate;
+                error = e;
+                continue 'outer;
+            }
+        }
+        break;
+    }
+
+    (value, error)
+}
+
+/// check `property` against a few thousand values from `generate`; on failure, panic with the
+/// smallest failing value shrinking could find
+///
+/// a property is usually differential: compute the answer with the optimized solver and a
+/// simple oracle, and return an error describing any disagreement
+pub fn check<T, G, P>(name: &str, generate: G, property: P)
+where
+    T: Shrink,
+    G: Fn(&mut Rng) -> T,
+    P: Fn(&T) -> Result<(), String>,
+{
+    let cases = env_or("AOC_PROPERTY_CASES", DEFAULT_CASES);
+    let seed = env_or("AOC_PROPERTY_SEED", DEFAULT_SEED);
+    let mut rng = Rng::new(seed);
+
+    for case in 0..cases {
+        let value = generate(&mut rng);
+
+        if let Err(error) = evaluate(&value, &property) {
+            let (minimal, error) = shrink_failure(value, error, &property);
+            panic!(
+                "property `{}` failed on case {} (AOC_PROPERTY_SEED={}): {}\nminimal failing value: {:?}",
+                name, case, seed, error, minimal
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shrink_failure() {
+        let property = |values: &Vec<usize>| {
+            if values.iter().any(|v| *v >= 10) {
+                Err(String::from("too big"))
+            } else {
+                Ok(())
+            }
+        };
+
+        let (minimal, error) = shrink_failure(vec![3, 42, 7, 19, 1], String::new(), property);
+
+        assert_eq!(vec![10], minimal);
+        assert_eq!("too big", error);
+    }
+
+    #[test]
+    fn test_check_passes() {
+        check(
+            "sum of two small numbers is small",
+            |rng| (rng.below(100), rng.below(100)),
+            |(a, b)| {
+                if a + b < 200 {
+                    Ok(())
+                } else {
+                    Err(format!("{} + {}", a, b))
+                }
+            },
+        );
+    }
+}
@@ -0,0 +1,83 @@
+/// small, seedable random number generator (splitmix64); good enough for generating puzzle
+/// inputs, not for anything that needs to be unpredictable
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// random number in 0..bound
+    pub fn below(&mut self, bound: usize) -> usize {
+        assert!(bound > 0, "can't pick a number below 0");
+        (self.next_u64() % bound as u64) as usize
+    }
+
+    /// random number in lower..=upper
+    pub fn range(&mut self, lower: i64, upper: i64) -> i64 {
+        assert!(lower <= upper, "empty range {}..={}", lower, upper);
+        let span = upper.wrapping_sub(lower) as u64;
+
+        if span == u64::MAX {
+            return self.next_u64() as i64;
+        }
+
+        lower.wrapping_add((self.next_u64() % (span + 1)) as i64)
+    }
+
+    /// true roughly `percent` percent of the time
+    pub fn chance(&mut self, percent: usize) -> bool {
+        self.below(100) < percent
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    /// fisher-yates shuffle
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.below(i + 1);
+            items.swap(i, j);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deterministic() {
+        let mut first = Rng::new(2021);
+        let mut second = Rng::new(2021);
+
+        for _ in 0..100 {
+            assert_eq!(first.next_u64(), second.next_u64());
+        }
+    }
+
+    #[test]
+    fn test_range() {
+        let mut rng = Rng::new(7);
+
+        for _ in 0..1000 {
+            let value = rng.range(-3, 3);
+            assert!((-3..=3).contains(&value));
+        }
+
+        assert_eq!(5, rng.range(5, 5));
+    }
+}
@@ -0,0 +1,16 @@
+/// a simulation that moves forward a step at a time, for stepping through in `aoc repl`
+pub trait Simulation {
+    /// move forward a single step; false when there's nothing left to do, i.e. no folds left
+    fn step(&mut self) -> bool;
+
+    /// the whole current state
+    fn show(&self) -> String;
+
+    /// what's at a position, whatever position means for the day (a grid's column and row, a
+    /// bingo board and row, etc); None when there's nothing there
+    fn inspect(&self, x: usize, y: usize) -> Option<String>;
+
+    /// named numbers describing the current state, i.e. how many flashes there have been, for
+    /// printing after each step and for run-until conditions to check
+    fn measures(&self) -> Vec<(&'static str, i64)>;
+}
@@ -0,0 +1,155 @@
+use std::env;
+use std::fs::{create_dir_all, read_to_string, write};
+use std::path::PathBuf;
+
+/// where snapshots are kept, one `<name>.snap` per snapshot
+const DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/snapshots");
+
+/// unchanged lines shown around each change in a diff
+const CONTEXT: usize = 2;
+
+fn path(name: &str) -> PathBuf {
+    PathBuf::from(DIR).join(format!("{}.snap", name))
+}
+
+/// whether snapshots are being (re)written rather than checked, set with AOC_BLESS=1
+fn blessing() -> bool {
+    env::var("AOC_BLESS").is_ok_and(|value| value == "1")
+}
+
+/// line diff turning `expected` into `actual`: removed lines start with `-`, added ones with `+`,
+/// and runs of unchanged lines away from any change are cut down to `...`
+pub fn diff(expected: &str, actual: &str) -> String {
+    let (old, new) = (
+        expected.lines().collect::<Vec<&str>>(),
+        actual.lines().collect::<Vec<&str>>(),
+    );
+
+    // longest common subsequence of the lines following each position in both
+    let mut common = vec![vec![0; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i][j] = if old[i] == new[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            lines.push((' ', old[i]));
+            (i, j) = (i + 1, j + 1);
+        } else if i < old.len() && (j == new.len() || common[i + 1][j] >= common[i][j + 1]) {
+            // removals before additions, like most diffs
+            lines.push(('-', old[i]));
+            i += 1;
+        } else {
+            lines.push(('+', new[j]));
+            j += 1;
+        }
+    }
+
+    let changed = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, (kind, _))| *kind != ' ')
+        .map(|(idx, _)| idx)
+        .collect::<Vec<usize>>();
+    let near_change = |idx: usize| {
+        changed
+            .iter()
+            .any(|&change| idx + CONTEXT >= change && idx <= change + CONTEXT)
+    };
+
+    let mut result = String::new();
+    let mut skipping = false;
+    for (idx, (kind, line)) in lines.iter().enumerate() {
+        if near_change(idx) {
+            result.push_str(&format!("{} {}\n", kind, line));
+            skipping = false;
+        } else if !skipping {
+            result.push_str("  ...\n");
+            skipping = true;
+        }
+    }
+
+    result
+}
+
+/// check a render against the snapshot of the given name, panicking with a diff when they differ
+///
+/// with AOC_BLESS=1 the snapshot is written instead, which is how new snapshots are made and
+/// intended changes accepted: `AOC_BLESS=1 cargo test`, then review the changes to
+/// tests/snapshots/ like any other
+pub fn check(name: &str, actual: &str) {
+    let path = path(name);
+
+    if blessing() {
+        create_dir_all(DIR).unwrap_or_else(|e| panic!("{}: {}", DIR, e));
+        write(&path, actual).unwrap_or_else(|e| panic!("{}: {}", path.display(), e));
+        return;
+    }
+
+    let expected = match read_to_string(&path) {
+        Ok(expected) => expected,
+        Err(e) => panic!(
+            "snapshot `{}` couldn't be read ({}: {}), run with AOC_BLESS=1 to write it",
+            name,
+            path.display(),
+            e
+        ),
+    };
+
+    if expected != actual {
+        // a difference in the trailing newline alone wouldn't show up in a line diff
+        let note = match expected.trim_end_matches('\n') == actual.trim_end_matches('\n') {
+            true => "\n(only the trailing newlines differ)",
+            false => "",
+        };
+        panic!(
+            "snapshot `{}` doesn't match, run with AOC_BLESS=1 to accept the change\n{}{}",
+            name,
+            diff(&expected, actual),
+            note
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff() {
+        assert_eq!("  a\n- b\n+ B\n  c\n", diff("a\nb\nc", "a\nB\nc"));
+        assert_eq!("  a\n+ b\n", diff("a", "a\nb"));
+        assert_eq!("- a\n", diff("a", ""));
+        assert_eq!("", diff("", ""));
+
+        let expected = (1..=10).map(|n| n.to_string()).collect::<Vec<String>>();
+        let mut actual = expected.clone();
+        actual[7] = String::from("eight");
+        assert_eq!(
+            "  ...\n  6\n  7\n- 8\n+ eight\n  9\n  10\n",
+            diff(&expected.join("\n"), &actual.join("\n"))
+        );
+    }
+
+    #[test]
+    fn test_check() {
+        check("snapshot", "first line\nsecond line\n");
+    }
+
+    #[test]
+    #[should_panic(expected = "- second line\n+ 2nd line")]
+    fn test_check_mismatch() {
+        if blessing() {
+            panic!("- second line\n+ 2nd line");
+        }
+        check("snapshot", "first line\n2nd line\n");
+    }
+}
@@ -0,0 +1,145 @@
+use crate::error::ParseError;
+use std::io::BufRead;
+
+/// lines of input read one at a time, each with its (zero based) index, for days that only need
+/// a single pass over their input and so can run on inputs far bigger than memory
+///
+/// a read error, including a line that isn't utf-8, ends the iteration with an error on that line
+pub struct Lines<R> {
+    reader: R,
+    idx: usize,
+    buffer: String,
+    failed: bool,
+}
+
+impl<R: BufRead> Lines<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            idx: 0,
+            buffer: String::new(),
+            failed: false,
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for Lines<R> {
+    type Item = Result<(usize, String), ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+
+        self.buffer.clear();
+        match self.reader.read_line(&mut self.buffer) {
+            Ok(0) => None,
+            Ok(_) => {
+                // same line endings str::lines strips
+                let line = self.buffer.strip_suffix('\n').unwrap_or(&self.buffer);
+                let line = line.strip_suffix('\r').unwrap_or(line);
+
+                let item = (self.idx, line.to_string());
+                self.idx += 1;
+                Some(Ok(item))
+            }
+            Err(e) => {
+                self.failed = true;
+                Some(Err(ParseError::new(
+                    self.idx,
+                    format!("couldn't read input: {}", e),
+                )))
+            }
+        }
+    }
+}
+
+/// feed `solve` the value `parse` makes of each line, stopping at the first line that doesn't
+/// parse (or can't be read) and returning its error instead of the answer
+///
+/// only a line is held in memory at a time, so `solve` should consume the values as they come
+pub fn solve_lines<R, T, A, P, S>(reader: R, mut parse: P, solve: S) -> Result<A, ParseError>
+where
+    R: BufRead,
+    P: FnMut(usize, &str) -> Result<T, ParseError>,
+    S: FnOnce(&mut dyn Iterator<Item = T>) -> A,
+{
+    let mut error = None;
+
+    let mut values =
+        Lines::new(reader).map_while(
+            |line| match line.and_then(|(idx, line)| parse(idx, &line)) {
+                Ok(value) => Some(value),
+                Err(e) => {
+                    error = Some(e);
+                    None
+                }
+            },
+        );
+    let answer = solve(&mut values);
+
+    match error {
+        Some(e) => Err(e),
+        None => Ok(answer),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufReader, Read};
+
+    /// reader that fails once the given bytes are used up
+    struct Failing<'a>(&'a [u8]);
+
+    impl Read for Failing<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            if self.0.is_empty() {
+                return Err(std::io::Error::other("disconnected"));
+            }
+            let count = self.0.read(buf)?;
+            Ok(count)
+        }
+    }
+
+    #[test]
+    fn test_lines() {
+        let lines = Lines::new("a\r\nb\n\nc".as_bytes())
+            .collect::<Result<Vec<(usize, String)>, ParseError>>()
+            .unwrap();
+        assert_eq!(
+            vec![
+                (0, String::from("a")),
+                (1, String::from("b")),
+                (2, String::new()),
+                (3, String::from("c")),
+            ],
+            lines
+        );
+
+        let lines = Lines::new(BufReader::new(Failing(b"a\nb\n"))).collect::<Vec<_>>();
+        assert_eq!(3, lines.len());
+        assert_eq!(
+            "line 3: couldn't read input: disconnected",
+            lines[2].as_ref().unwrap_err().to_string()
+        );
+
+        let lines = Lines::new(&b"ok\n\xff\n"[..]).collect::<Vec<_>>();
+        assert_eq!(2, lines.len());
+        assert!(lines[1].is_err());
+    }
+
+    #[test]
+    fn test_solve_lines() {
+        let parse = |idx, line: &str| crate::error::parse_value::<i32>(line, idx);
+        let sum = |values: &mut dyn Iterator<Item = i32>| values.sum::<i32>();
+
+        assert_eq!(Ok(6), solve_lines("1\n2\n3\n".as_bytes(), parse, sum));
+        assert_eq!(
+            "line 2: couldn't parse \"x\": invalid digit found in string",
+            solve_lines("1\nx\n3".as_bytes(), parse, sum)
+                .unwrap_err()
+                .to_string()
+        );
+    }
+}
@@ -0,0 +1,138 @@
+use crate::error::ParseError;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// the first problem found, if any; lets a parser refuse anything its day's `validate` would
+pub fn ensure_valid(errors: Vec<ParseError>) -> Result<(), ParseError> {
+    match errors.into_iter().next() {
+        Some(error) => Err(error),
+        None => Ok(()),
+    }
+}
+
+/// (zero based) column, counted in characters, at which the given byte offset of `line` sits
+fn column(line: &str, offset: usize) -> usize {
+    line[..offset].chars().count()
+}
+
+/// pieces of `line` between each `separator`, along with the (zero based) column each starts at
+pub fn fields<'a>(line: &'a str, separator: &str) -> Vec<(usize, &'a str)> {
+    let mut offset = 0;
+
+    line.split(separator)
+        .map(|field| {
+            let start = column(line, offset);
+            offset += field.len() + separator.len();
+            (start, field)
+        })
+        .collect()
+}
+
+/// whitespace separated words of `line`, along with the (zero based) column each starts at
+pub fn words(line: &str) -> Vec<(usize, &str)> {
+    line.split_whitespace()
+        .map(|word| {
+            // split_whitespace hands back slices of line, so the pointers give away the offset
+            let offset = word.as_ptr() as usize - line.as_ptr() as usize;
+            (column(line, offset), word)
+        })
+        .collect()
+}
+
+/// parse a single value found at the given (zero based) line and column of input
+pub fn number<T>(value: &str, idx: usize, col_idx: usize) -> Result<T, ParseError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    value
+        .parse::<T>()
+        .map_err(|e| ParseError::at(idx, col_idx, format!("couldn't parse {:?}: {}", value, e)))
+}
+
+/// parse a value made of nothing but ascii digits, unlike FromStr, which lets a sign through
+pub fn digits<T>(value: &str, idx: usize, col_idx: usize) -> Result<T, ParseError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    match value.char_indices().find(|(_, ch)| !ch.is_ascii_digit()) {
+        Some((offset, ch)) => Err(ParseError::at(
+            idx,
+            col_idx + column(value, offset),
+            format!("{:?} isn't a digit", ch),
+        )),
+        None => number(value, idx, col_idx),
+    }
+}
+
+/// every character of `line` that `allowed` turns down, `what` describing what was expected
+pub fn bad_chars<F>(line: &str, idx: usize, allowed: F, what: &str) -> Vec<ParseError>
+where
+    F: Fn(char) -> bool,
+{
+    line.chars()
+        .enumerate()
+        .filter(|(_, ch)| !allowed(*ch))
+        .map(|(col_idx, ch)| ParseError::at(idx, col_idx, format!("{:?} isn't {}", ch, what)))
+        .collect()
+}
+
+/// every line that isn't as wide as the first, which also needs to exist and not be empty
+pub fn rectangular(input: &str) -> Vec<ParseError> {
+    let width = match input.lines().next() {
+        Some(line) if !line.is_empty() => line.chars().count(),
+        _ => return vec![ParseError::new(0, "expected at least one row of values")],
+    };
+
+    input
+        .lines()
+        .enumerate()
+        .filter_map(|(idx, line)| {
+            let found = line.chars().count();
+            (found != width).then(|| {
+                ParseError::at(
+                    idx,
+                    usize::min(found, width),
+                    format!("expected {} values, found {}", width, found),
+                )
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_columns() {
+        assert_eq!(
+            vec![(0, "12"), (3, ""), (4, "é"), (6, "7")],
+            fields("12,,é,7", ",")
+        );
+        assert_eq!(vec![(1, "ab"), (5, "cd")], words(" ab  cd"));
+    }
+
+    #[test]
+    fn test_diagnostics() {
+        assert_eq!(
+            "line 2, column 3: '+' isn't a digit",
+            digits::<i32>("12+", 1, 0).unwrap_err().to_string()
+        );
+
+        let errors = rectangular("123\n12\n1234");
+        assert_eq!(
+            vec![
+                ParseError::at(1, 2, "expected 3 values, found 2"),
+                ParseError::at(2, 3, "expected 3 values, found 4"),
+            ],
+            errors
+        );
+
+        assert_eq!(
+            2,
+            bad_chars("a1b2", 0, |ch| ch.is_ascii_digit(), "a digit").len()
+        );
+    }
+}
@@ -0,0 +1,193 @@
+use crate::Solver;
+use std::fmt::{Display, Formatter};
+use std::ops::RangeInclusive;
+
+/// one picture of a simulation, along with a caption saying what's going on in it
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Frame {
+    pub caption: String,
+    pub picture: String,
+}
+
+impl Frame {
+    /// frame showing anything with a `Display` impl, i.e. day 11's `Cave`
+    pub fn new(caption: impl Into<String>, picture: &impl Display) -> Self {
+        Self {
+            caption: caption.into(),
+            picture: picture.to_string(),
+        }
+    }
+}
+
+impl Display for Frame {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{}", self.caption)?;
+        write!(f, "{}", self.picture)
+    }
+}
+
+/// a simulation that can be watched a frame at a time
+pub trait Visualize {
+    /// every frame of the simulation solving the given part, starting with how things look
+    /// before anything has happened
+    fn frames(&mut self, solver: Solver) -> Vec<Frame>;
+}
+
+/// pictures laid out left to right, `per_row` to a row, with a blank line between rows
+pub fn tile(pictures: &[String], per_row: usize) -> String {
+    let mut result = String::new();
+
+    for (idx, row) in pictures.chunks(per_row.max(1)).enumerate() {
+        if idx > 0 {
+            result.push('\n');
+        }
+
+        let lines = row
+            .iter()
+            .map(|picture| picture.lines().collect::<Vec<&str>>())
+            .collect::<Vec<Vec<&str>>>();
+        let height = lines.iter().map(Vec::len).max().unwrap_or_default();
+        let width = lines
+            .iter()
+            .flatten()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or_default();
+
+        for line in 0..height {
+            let joined = lines
+                .iter()
+                .map(|picture| {
+                    format!(
+                        "{:<width$}",
+                        picture.get(line).unwrap_or(&""),
+                        width = width
+                    )
+                })
+                .collect::<Vec<String>>()
+                .join("  ");
+            result.push_str(joined.trim_end());
+            result.push('\n');
+        }
+    }
+
+    result
+}
+
+/// grid of characters that points get plotted onto, scaled down to fit when the area they cover
+/// is bigger than the grid; y grows upwards, like it does in the puzzles
+#[derive(Clone, Debug)]
+pub struct Canvas {
+    x: RangeInclusive<i64>,
+    y: RangeInclusive<i64>,
+    cells: Vec<Vec<char>>,
+}
+
+impl Canvas {
+    /// canvas covering the given area, at most `width` x `height` characters and filled with '.'
+    pub fn new(
+        width: usize,
+        height: usize,
+        x: RangeInclusive<i64>,
+        y: RangeInclusive<i64>,
+    ) -> Self {
+        let span = |range: &RangeInclusive<i64>| (range.end() - range.start()).max(0) as usize + 1;
+        let columns = span(&x).min(width.max(1));
+        let rows = span(&y).min(height.max(1));
+
+        Self {
+            x,
+            y,
+            cells: vec![vec!['.'; columns]; rows],
+        }
+    }
+
+    /// which of `cells` a coordinate lands in, if it's on the canvas at all
+    fn cell(range: &RangeInclusive<i64>, cells: usize, value: i64) -> Option<usize> {
+        if !range.contains(&value) {
+            return None;
+        }
+
+        let span = (range.end() - range.start()).max(1);
+        Some(((value - range.start()) * (cells as i64 - 1) / span) as usize)
+    }
+
+    fn column(&self, x: i64) -> Option<usize> {
+        Self::cell(&self.x, self.cells[0].len(), x)
+    }
+
+    fn row(&self, y: i64) -> Option<usize> {
+        Self::cell(&self.y, self.cells.len(), y).map(|row| self.cells.len() - 1 - row)
+    }
+
+    /// draw a single point; anything off the canvas is ignored
+    pub fn plot(&mut self, x: i64, y: i64, ch: char) {
+        if let (Some(column), Some(row)) = (self.column(x), self.row(y)) {
+            self.cells[row][column] = ch;
+        }
+    }
+
+    /// draw a rectangle, clipped to the canvas
+    pub fn fill(&mut self, x: RangeInclusive<i64>, y: RangeInclusive<i64>, ch: char) {
+        let clip = |area: &RangeInclusive<i64>, range: RangeInclusive<i64>| {
+            (*range.start()).max(*area.start())..=(*range.end()).min(*area.end())
+        };
+        let (x, y) = (clip(&self.x, x), clip(&self.y, y));
+        if x.is_empty() || y.is_empty() {
+            return;
+        }
+
+        if let (Some(left), Some(right), Some(top), Some(bottom)) = (
+            self.column(*x.start()),
+            self.column(*x.end()),
+            self.row(*y.end()),
+            self.row(*y.start()),
+        ) {
+            for row in &mut self.cells[top..=bottom] {
+                row[left..=right].fill(ch);
+            }
+        }
+    }
+}
+
+impl Display for Canvas {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for row in &self.cells {
+            writeln!(f, "{}", row.iter().collect::<String>())?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tile() {
+        let pictures = ["ab\ncd\n", "e\nf\ng\n", "hij\n"].map(String::from);
+
+        assert_eq!("ab  e\ncd  f\n    g\n\nhij\n", tile(&pictures, 2));
+    }
+
+    #[test]
+    fn test_canvas() {
+        let mut canvas = Canvas::new(10, 10, 0..=4, -2..=0);
+        canvas.plot(0, 0, 'S');
+        canvas.fill(3..=9, -2..=-1, 'T');
+        canvas.plot(100, 0, '#');
+
+        assert_eq!("S....\n...TT\n...TT\n", canvas.to_string());
+    }
+
+    #[test]
+    fn test_canvas_scales_down() {
+        let mut canvas = Canvas::new(3, 2, 0..=100, 0..=100);
+        canvas.plot(0, 0, 'a');
+        canvas.plot(50, 100, 'b');
+        canvas.plot(100, 0, 'c');
+
+        assert_eq!(".b.\na.c\n", canvas.to_string());
+    }
+}
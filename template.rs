@@ -1,39 +1,46 @@
-use advent_of_code_2021::{get_input, parse_args};
+use crate::error::ParseError;
+use crate::rng::Rng;
+use crate::validate::ensure_valid;
 
-fn part_one(input: &str) -> i32 {
-    0
+/// every structural problem with the input, see days::Validator
+pub fn validate(input: &str) -> Vec<ParseError> {
+    Vec::new()
 }
 
-fn part_two(input: &str) -> i32 {
-    0
+pub(crate) fn parse(input: &str) -> Result<Vec<&str>, ParseError> {
+    ensure_valid(validate(input))?;
+
+    Ok(input.lines().collect())
+}
+
+pub fn part_one(input: &str) -> Result<i32, ParseError> {
+    let _lines = parse(input)?;
+    Ok(0)
 }
 
-fn main() {
-    let args = parse_args();
-    let input = get_input(file!());
-
-    match args.part {
-        1 => println!("{}", part_one(&input)),
-        2 => println!("{}", part_two(&input)),
-        _ => println!(
-            "got unexpected value for --part: {} (try 1 or 2)",
-            args.part
-        ),
-    };
+pub fn part_two(input: &str) -> Result<i32, ParseError> {
+    let _lines = parse(input)?;
+    Ok(0)
 }
 
+/// random, but valid, input that loosely scales with `size`
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    String::new()
+}
+
+pub const EXAMPLE: &str = "";
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    static INPUT: &str = "";
 
     #[test]
     fn test_one() {
-        assert_eq!(0, part_one(INPUT));
+        assert_eq!(Ok(0), part_one(EXAMPLE));
     }
 
     #[test]
     fn test_two() {
-        assert_eq!(0, part_two(INPUT));
+        assert_eq!(Ok(0), part_two(EXAMPLE));
     }
 }
@@ -0,0 +1,67 @@
+//! running the day binaries the way someone would from the command line, on inputs of the test's
+//! choosing
+
+use std::fs::{create_dir_all, remove_dir_all, write};
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+/// every day's binary
+const BINARIES: [(u32, &str); 16] = [
+    (1, env!("CARGO_BIN_EXE_day-1")),
+    (2, env!("CARGO_BIN_EXE_day-2")),
+    (3, env!("CARGO_BIN_EXE_day-3")),
+    (4, env!("CARGO_BIN_EXE_day-4")),
+    (5, env!("CARGO_BIN_EXE_day-5")),
+    (6, env!("CARGO_BIN_EXE_day-6")),
+    (7, env!("CARGO_BIN_EXE_day-7")),
+    (8, env!("CARGO_BIN_EXE_day-8")),
+    (9, env!("CARGO_BIN_EXE_day-9")),
+    (10, env!("CARGO_BIN_EXE_day-10")),
+    (11, env!("CARGO_BIN_EXE_day-11")),
+    (12, env!("CARGO_BIN_EXE_day-12")),
+    (13, env!("CARGO_BIN_EXE_day-13")),
+    (14, env!("CARGO_BIN_EXE_day-14")),
+    (15, env!("CARGO_BIN_EXE_day-15")),
+    (17, env!("CARGO_BIN_EXE_day-17")),
+];
+
+/// a directory of 2021 inputs, with no config of its own, removed once it's dropped
+pub struct Inputs {
+    dir: PathBuf,
+}
+
+impl Inputs {
+    pub fn new(name: &str) -> Self {
+        let dir = std::env::temp_dir().join(format!("aoc-{}-{}", name, std::process::id()));
+        create_dir_all(dir.join("2021")).unwrap();
+        Self { dir }
+    }
+
+    pub fn write(&self, day: u32, input: &str) {
+        write(self.dir.join("2021").join(format!("input-{}", day)), input).unwrap();
+    }
+
+    /// run the day's binary on its input with the given args, after `--year 2021`
+    pub fn run(&self, day: u32, args: &[&str]) -> Output {
+        let binary = BINARIES
+            .iter()
+            .find(|(number, _)| *number == day)
+            .map(|(_, binary)| binary)
+            .unwrap_or_else(|| panic!("day {} has no binary", day));
+
+        Command::new(binary)
+            .args(["--year", "2021"])
+            .args(args)
+            .current_dir(&self.dir)
+            .env("AOC_INPUTS", &self.dir)
+            .env("XDG_CONFIG_HOME", &self.dir)
+            .output()
+            .unwrap()
+    }
+}
+
+impl Drop for Inputs {
+    fn drop(&mut self) {
+        remove_dir_all(&self.dir).ok();
+    }
+}
@@ -0,0 +1,37 @@
+mod common;
+
+use advent_of_code_2021::days::DAYS;
+use advent_of_code_2021::json::{parse, Value};
+use advent_of_code_2021::{Answer, Solver};
+use common::Inputs;
+
+/// `--explain --format json` has to print a json document and nothing else, whatever the solver
+/// gets up to along the way
+#[test]
+fn test_explain_json() {
+    let inputs = Inputs::new("explain");
+
+    for day in DAYS.iter().filter(|day| day.explain.is_some()) {
+        inputs.write(day.number, day.examples[0].input);
+
+        for (part, solver) in [("1", Solver::PartOne), ("2", Solver::PartTwo)] {
+            let output = inputs.run(
+                day.number,
+                &["--part", part, "--explain", "--format", "json"],
+            );
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            let context = format!("day {} {:?}:\n{}", day.number, solver, stdout);
+            assert!(output.status.success(), "{}", context);
+
+            let document = parse(&stdout).unwrap_or_else(|e| panic!("{}\n{}", e, context));
+            let expected = match (day.solution(solver))(day.examples[0].input).unwrap() {
+                Answer::Number(number) => Value::Number(number as f64),
+                Answer::Text(text) => Value::String(text),
+            };
+            assert_eq!(Some(&expected), document.get("answer"), "{}", context);
+
+            let events = document.get("events").and_then(Value::as_array);
+            assert!(!events.unwrap_or_default().is_empty(), "{}", context);
+        }
+    }
+}
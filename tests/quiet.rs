@@ -0,0 +1,31 @@
+mod common;
+
+use advent_of_code_2021::days::DAYS;
+use advent_of_code_2021::rng::Rng;
+use advent_of_code_2021::Solver;
+use common::Inputs;
+
+/// solvers print nothing of their own, just the answer the binary prints for them; anything else
+/// ends up in everything that solves through the registry
+#[test]
+fn test_answer_only() {
+    let inputs = Inputs::new("quiet");
+    let mut rng = Rng::new(2021);
+
+    for day in DAYS {
+        let input = (day.generate)(&mut rng, 4);
+        inputs.write(day.number, &input);
+
+        for (part, solver) in [("1", Solver::PartOne), ("2", Solver::PartTwo)] {
+            let output = inputs.run(day.number, &["--part", part]);
+            let answer = (day.solution(solver))(&input).unwrap();
+            assert_eq!(
+                format!("{}\n", answer),
+                String::from_utf8_lossy(&output.stdout),
+                "day {} {:?} printed more than its answer",
+                day.number,
+                solver
+            );
+        }
+    }
+}